@@ -0,0 +1,88 @@
+//! Lays out a log whose lines change every frame, with a deliberately slow measure function, with
+//! and without a `MeasureCache` in front of it.
+//!
+//! Clay remembers the words of the strings it already measured, but measures the words of every
+//! new string again. The lines are new every frame, their words are not.
+//!
+//! Run with `cargo bench --bench measure_cache --features measure-cache`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use clay_layout::{
+    layout::{LayoutDirection, Sizing},
+    math::Dimensions,
+    measure::MeasureCache,
+    text::TextConfig,
+    Clay, Declaration,
+};
+
+const WORDS: [&str; 12] = [
+    "layout", "render", "frame", "text", "cache", "font", "glyph", "shaping", "measure", "word",
+    "line", "clay",
+];
+const LINES: usize = 40;
+const FRAMES: usize = 50;
+
+/// Stands in for a shaper, taking about a microsecond per character.
+fn slow_measure(text: &str, config: &TextConfig) -> Dimensions {
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_micros(text.len() as u64) {
+        std::hint::spin_loop();
+    }
+    let font_size = config.font_size as f32;
+    (text.chars().count() as f32 * font_size / 2., font_size).into()
+}
+
+/// The lines of a frame, each one numbered so it's a new string every frame.
+fn lines(frame: usize) -> Vec<String> {
+    (0..LINES)
+        .map(|line| {
+            let words = (0..8).map(|word| WORDS[(frame + line * 3 + word * 5) % WORDS.len()]);
+            let words: Vec<_> = words.collect();
+            format!("{} {}", frame * LINES + line, words.join(" "))
+        })
+        .collect()
+}
+
+#[rustfmt::skip]
+fn run(clay: &mut Clay) -> Duration {
+    let start = Instant::now();
+    for frame in 0..FRAMES {
+        let mut scope = clay.begin::<(), ()>();
+        scope.with(Declaration::new()
+            .layout()
+                .width(Sizing::Grow(0., 0.))
+                .direction(LayoutDirection::TopToBottom)
+                .end(), |scope|
+        {
+            for line in lines(frame) {
+                scope.text_owned(line, TextConfig::new().font_size(16).end());
+            }
+        });
+        black_box(scope.end().count());
+    }
+    start.elapsed()
+}
+
+fn main() {
+    let mut clay = Clay::new((800., 600.).into());
+    clay.set_measure_text_function(slow_measure);
+    let uncached = run(&mut clay);
+
+    let cache = MeasureCache::new(1024, slow_measure);
+    let mut clay = Clay::new((800., 600.).into());
+    clay.set_measure_text_function(cache.measure_function());
+    let cached = run(&mut clay);
+
+    let stats = cache.stats();
+    println!("{FRAMES} frames of {LINES} new lines");
+    println!("uncached: {:>8.2?} per frame", uncached / FRAMES as u32);
+    println!("cached:   {:>8.2?} per frame", cached / FRAMES as u32);
+    println!(
+        "{} hits, {} misses, {:.1}% hit rate",
+        stats.hits,
+        stats.misses,
+        stats.hit_rate() * 100.
+    );
+}
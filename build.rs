@@ -40,6 +40,10 @@ fn main() {
         println!("cargo:rerun-if-changed=clay.h");
     }
 
+    println!("cargo:rerun-if-changed=build.c");
+    println!("cargo:rerun-if-changed=build.cpp");
+    println!("cargo:rerun-if-changed=clay_inspect.h");
+
     // Compile native code
     if target_os == "windows" {
         cc::Build::new()
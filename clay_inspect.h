@@ -0,0 +1,83 @@
+// Accessors for the internal layout element arrays of clay, used by the Rust `tree` module to
+// snapshot the layout after Clay_EndLayout. Must be included after the clay implementation.
+
+typedef struct {
+    int32_t layoutElementIndex;
+    // Id of the element a floating root is attached to, zero for the root of the layout
+    uint32_t parentId;
+    int16_t zIndex;
+} Clay__InspectRoot;
+
+typedef struct {
+    uint32_t id;
+    Clay_String label;
+    Clay_BoundingBox boundingBox;
+    Clay_LayoutConfig layout;
+    // Indices into the layout element array, only set for non text elements
+    int32_t *children;
+    uint16_t childCount;
+    bool isText;
+    Clay_String text;
+    bool hasClip;
+    Clay_ClipElementConfig clip;
+    bool hasFloating;
+    Clay_FloatingElementConfig floating;
+    bool culled;
+} Clay__InspectElement;
+
+int32_t Clay__InspectElementCount(void) {
+    return Clay_GetCurrentContext()->layoutElements.length;
+}
+
+int32_t Clay__InspectRootCount(void) {
+    return Clay_GetCurrentContext()->layoutElementTreeRoots.length;
+}
+
+bool Clay__InspectGetRoot(int32_t index, Clay__InspectRoot *out) {
+    Clay_Context *context = Clay_GetCurrentContext();
+    if (index < 0 || index >= context->layoutElementTreeRoots.length) {
+        return false;
+    }
+    Clay__LayoutElementTreeRoot *root = Clay__LayoutElementTreeRootArray_Get(&context->layoutElementTreeRoots, index);
+    out->layoutElementIndex = root->layoutElementIndex;
+    out->parentId = root->parentId;
+    out->zIndex = root->zIndex;
+    return true;
+}
+
+bool Clay__InspectGetElement(int32_t index, Clay__InspectElement *out) {
+    Clay_Context *context = Clay_GetCurrentContext();
+    if (index < 0 || index >= context->layoutElements.length) {
+        return false;
+    }
+    Clay_LayoutElement *element = Clay_LayoutElementArray_Get(&context->layoutElements, index);
+    Clay_LayoutElementHashMapItem *item = Clay__GetHashMapItem(element->id);
+    Clay_ElementConfigUnion config;
+
+    out->id = element->id;
+    out->label = index < context->layoutElementIdStrings.length ? context->layoutElementIdStrings.internalArray[index] : CLAY__INIT(Clay_String) CLAY__DEFAULT_STRUCT;
+    out->boundingBox = item->boundingBox;
+    out->layout = element->layoutConfig ? *element->layoutConfig : CLAY_LAYOUT_DEFAULT;
+
+    out->isText = Clay__ElementHasConfig(element, CLAY__ELEMENT_CONFIG_TYPE_TEXT);
+    if (out->isText) {
+        out->children = NULL;
+        out->childCount = 0;
+        out->text = element->childrenOrTextContent.textElementData->text;
+    } else {
+        out->children = element->childrenOrTextContent.children.elements;
+        out->childCount = element->childrenOrTextContent.children.length;
+        out->text = CLAY__INIT(Clay_String) CLAY__DEFAULT_STRUCT;
+    }
+
+    config = Clay__FindElementConfigWithType(element, CLAY__ELEMENT_CONFIG_TYPE_CLIP);
+    out->hasClip = config.clipElementConfig != NULL;
+    out->clip = out->hasClip ? *config.clipElementConfig : CLAY__INIT(Clay_ClipElementConfig) CLAY__DEFAULT_STRUCT;
+
+    config = Clay__FindElementConfigWithType(element, CLAY__ELEMENT_CONFIG_TYPE_FLOATING);
+    out->hasFloating = config.floatingElementConfig != NULL;
+    out->floating = out->hasFloating ? *config.floatingElementConfig : CLAY__INIT(Clay_FloatingElementConfig) CLAY__DEFAULT_STRUCT;
+
+    out->culled = Clay__ElementIsOffscreen(&item->boundingBox);
+    return true;
+}
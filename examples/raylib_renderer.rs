@@ -1,8 +1,11 @@
-use clay_layout::{grow, renderers::clay_raylib_render, Clay, Declaration};
+use clay_layout::{
+    fonts::FontRegistry, grow, renderers::clay_raylib_render, theme::Theme, Clay, Declaration,
+};
 use raylib::prelude::*;
 
 pub fn main() {
     let mut clay = Clay::new((800., 600.).into());
+    clay.set_theme(Theme::light());
 
     let (mut rl, thread) = raylib::init()
         .resizable()
@@ -18,6 +21,7 @@ pub fn main() {
         let mut d = rl.begin_drawing(&thread);
         d.clear_background(Color::WHITE);
 
+        let theme = *clay.theme();
         let mut clay = clay.begin::<_, ()>();
 
         #[rustfmt::skip]
@@ -35,9 +39,9 @@ pub fn main() {
                             .height(grow!())
                         .end()
                         .corner_radius()
-                            .all(24.)
+                            .all(theme.radii.lg)
                         .end()
-                        .background_color((0xFF, 0x00, 0x00).into()),
+                        .background_color(theme.palette.primary),
                     |_| {}
                 );
 
@@ -48,9 +52,9 @@ pub fn main() {
                             .height(grow!())
                         .end()
                         .corner_radius()
-                            .all(24.)
+                            .all(theme.radii.lg)
                         .end()
-                        .background_color((0x00, 0xFF, 0x00).into()),
+                        .background_color(theme.palette.surface_variant),
                     |_| {}
                 );
             },
@@ -58,6 +62,6 @@ pub fn main() {
 
         let commands = clay.end();
 
-        clay_raylib_render(&mut d, commands, &vec![],|_, _| {})
+        clay_raylib_render(&mut d, commands, &FontRegistry::new(),|_, _| {})
     }
 }
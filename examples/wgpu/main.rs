@@ -2,12 +2,13 @@ use winit::application::ApplicationHandler;
 use winit::dpi::LogicalSize;
 use winit::event::{MouseScrollDelta, WindowEvent};
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
+use winit::keyboard::Key;
 use winit::window::{Window, WindowId};
 
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use clay_layout::Clay;
+use clay_layout::{theme::Theme, Clay};
 
 use ui_renderer::UIState;
 mod graphics_context;
@@ -58,6 +59,7 @@ impl<'a> ApplicationHandler for App<'a> {
 
         let mut clay = Clay::new((size.width as f32, size.height as f32).into());
         clay.set_debug_mode(false);
+        clay.set_theme(Theme::dark());
 
         clay.set_measure_text_function_user_data(ui_state.clone(), ui_layout::measure_text);
 
@@ -119,9 +121,30 @@ impl<'a> ApplicationHandler for App<'a> {
             } => match button {
                 winit::event::MouseButton::Left => {
                     self.clay_user_data.mouse_down_rising_edge = state.is_pressed();
+                    self.clay_user_data.mouse_down = state.is_pressed();
                 }
                 _ => {}
             },
+            WindowEvent::KeyboardInput {
+                device_id: _,
+                event,
+                is_synthetic: _,
+            } => {
+                if !event.state.is_pressed() {
+                    return;
+                }
+                // Toggle the inspector on `i`
+                if let Key::Character(key) = event.logical_key.as_ref() {
+                    if self.clay_user_data.inspector.handle_key(key) {
+                        return;
+                    }
+                }
+                // Switch between the light and dark theme on `t`
+                if event.logical_key.as_ref() == Key::Character("t") {
+                    let clay = self.clay.as_mut().unwrap();
+                    clay.set_theme(clay.theme().toggled());
+                }
+            }
             WindowEvent::MouseWheel {
                 device_id: _,
                 delta,
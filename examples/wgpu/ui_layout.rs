@@ -1,12 +1,15 @@
 use clay_layout::{
     elements::FloatingAttachToElement,
     fixed, grow,
+    inspector::Inspector,
     layout::{Alignment, LayoutDirection::TopToBottom, Padding},
     math::Dimensions,
     percent,
     render_commands::RenderCommand,
+    style::{StateStyle, StyleOverride},
     text::TextConfig,
-    Clay, ClayLayoutScope, Color, Declaration,
+    theme::Theme,
+    Clay, ClayLayoutScope, Declaration,
 };
 
 const CLAY_ALIGN_Y_CENTER: Alignment = Alignment {
@@ -14,11 +17,9 @@ const CLAY_ALIGN_Y_CENTER: Alignment = Alignment {
     y: clay_layout::layout::LayoutAlignmentY::Center,
 };
 
-const WHITE: Color = Color::rgb(255.0, 255.0, 255.0);
-
 trait CustomStyles<ImageElementData, CustomElementData> {
     fn layout_expand(&mut self) -> &mut Self;
-    fn content_background_config(&mut self) -> &mut Self;
+    fn content_background_config(&mut self, theme: &Theme) -> &mut Self;
 }
 
 impl<ImageElementData, CustomElementData> CustomStyles<ImageElementData, CustomElementData>
@@ -30,10 +31,10 @@ impl<ImageElementData, CustomElementData> CustomStyles<ImageElementData, CustomE
         self
     }
 
-    fn content_background_config(&mut self) -> &mut Self {
-        self.background_color(Color::rgb(90.0, 90.0, 90.0))
+    fn content_background_config(&mut self, theme: &Theme) -> &mut Self {
+        self.background_color(theme.palette.surface)
             .corner_radius()
-            .all(8.0)
+            .all(theme.radii.md)
             .end();
 
         self
@@ -42,31 +43,53 @@ impl<ImageElementData, CustomElementData> CustomStyles<ImageElementData, CustomE
 
 fn render_header_button<'a, ImageElementData: 'a, CustomElementData: 'a>(
     clay: &mut ClayLayoutScope<'a, 'a, ImageElementData, CustomElementData>,
-    text: &str,
+    text: &'a str,
 ) {
+    let theme = *clay.theme();
     clay.with(
         &Declaration::new()
             .layout()
-            .padding(Padding::new(16, 16, 8, 8))
+            .padding(Padding::new(
+                theme.spacing.md,
+                theme.spacing.md,
+                theme.spacing.sm,
+                theme.spacing.sm,
+            ))
             .end()
-            .background_color(Color::rgb(140.0, 140.0, 140.0))
+            .background_color(theme.palette.control)
             .corner_radius()
-            .all(5.0)
+            .all(theme.radii.sm)
             .end(),
         |clay| {
-            clay.text(text, TextConfig::new().font_size(16).color(WHITE).end());
+            clay.text(
+                text,
+                TextConfig::new()
+                    .style(&theme.typography.body)
+                    .color(theme.palette.text)
+                    .end(),
+            );
         },
     );
 }
 
 fn render_dropdown_menu_item<'a, ImageElementData: 'a, CustomElementData: 'a>(
     clay: &mut ClayLayoutScope<'a, 'a, ImageElementData, CustomElementData>,
-    text: &str,
+    text: &'a str,
 ) {
+    let theme = *clay.theme();
     clay.with(
-        &Declaration::new().layout().padding(Padding::all(16)).end(),
+        &Declaration::new()
+            .layout()
+            .padding(Padding::all(theme.spacing.md))
+            .end(),
         |clay| {
-            clay.text(text, TextConfig::new().font_size(16).color(WHITE).end());
+            clay.text(
+                text,
+                TextConfig::new()
+                    .style(&theme.typography.body)
+                    .color(theme.palette.text)
+                    .end(),
+            );
         },
     );
 }
@@ -80,9 +103,11 @@ pub struct ClayState {
     pub documents: Vec<Document>,
     pub selected_document_index: u8,
     pub mouse_down_rising_edge: bool,
+    pub mouse_down: bool,
     pub mouse_position: (f32, f32),
     pub scroll_delta: (f32, f32),
     pub size: (f32, f32),
+    pub inspector: Inspector,
 }
 
 pub fn initialize_user_data(user_data: &mut ClayState) {
@@ -100,13 +125,15 @@ pub fn initialize_user_data(user_data: &mut ClayState) {
 
 pub fn create_layout<'render>(
     clay: &'render mut Clay,
-    user_data: &mut ClayState,
+    user_data: &'render mut ClayState,
     time_delta: f32,
 ) -> impl Iterator<Item = RenderCommand<'render, (), ()>> {
     clay.set_layout_dimensions(user_data.size.into());
-    clay.pointer_state(user_data.mouse_position.into(), false);
+    clay.pointer_state(user_data.mouse_position.into(), user_data.mouse_down);
     clay.update_scroll_containers(false, user_data.scroll_delta.into(), time_delta);
+    clay.update_transitions(time_delta);
 
+    let theme = *clay.theme();
     let mut clay = clay.begin::<(), ()>();
 
     clay.with(
@@ -118,25 +145,25 @@ pub fn create_layout<'render>(
             .id(clay.id("outer_container"))
             .layout()
             .direction(TopToBottom)
-            .padding(Padding::all(16))
-            .child_gap(16)
+            .padding(Padding::all(theme.spacing.md))
+            .child_gap(theme.spacing.md)
             .end()
-            .background_color(Color::rgb(43.0, 41.0, 51.0)),
+            .background_color(theme.palette.background),
         |clay| {
             clay.with(
                 &Declaration::new()
-                    .content_background_config()
+                    .content_background_config(&theme)
                     .id(clay.id("header_bar"))
                     .layout()
                     .width(grow!())
                     .height(fixed!(120.0))
                     .padding(Padding {
-                        left: 16,
-                        right: 16,
-                        top: 8,
-                        bottom: 8,
+                        left: theme.spacing.md,
+                        right: theme.spacing.md,
+                        top: theme.spacing.sm,
+                        bottom: theme.spacing.sm,
                     })
-                    .child_gap(16)
+                    .child_gap(theme.spacing.md)
                     .child_alignment(CLAY_ALIGN_Y_CENTER)
                     .end(),
                 |clay| {
@@ -145,18 +172,24 @@ pub fn create_layout<'render>(
                             .id(clay.id("file_button"))
                             .layout()
                             .padding(Padding {
-                                left: 16,
-                                right: 16,
-                                top: 8,
-                                bottom: 8,
+                                left: theme.spacing.md,
+                                right: theme.spacing.md,
+                                top: theme.spacing.sm,
+                                bottom: theme.spacing.sm,
                             })
                             .end()
-                            .background_color(Color::rgb(140.0, 140.0, 140.0))
+                            .background_color(theme.palette.control)
                             .corner_radius()
-                            .all(5.0)
+                            .all(theme.radii.sm)
                             .end(),
                         |clay| {
-                            clay.text("File", TextConfig::new().font_size(16).color(WHITE).end());
+                            clay.text(
+                                "File",
+                                TextConfig::new()
+                                    .style(&theme.typography.body)
+                                    .color(theme.palette.text)
+                                    .end(),
+                            );
 
                             let file_menu_visible = clay.pointer_over(clay.id("file_button"))
                                 || clay.pointer_over(clay.id("file_menu"));
@@ -169,7 +202,7 @@ pub fn create_layout<'render>(
                                         .attach_to(FloatingAttachToElement::Parent)
                                         .end()
                                         .layout()
-                                        .padding(Padding::new(0, 0, 8, 8))
+                                        .padding(Padding::vertical(theme.spacing.sm))
                                         .end(),
                                     |clay| {
                                         clay.with(
@@ -178,9 +211,9 @@ pub fn create_layout<'render>(
                                                 .direction(TopToBottom)
                                                 .width(fixed!(200.0))
                                                 .end()
-                                                .background_color(Color::rgb(40.0, 40.0, 40.0))
+                                                .background_color(theme.palette.overlay)
                                                 .corner_radius()
-                                                .all(8.0)
+                                                .all(theme.radii.md)
                                                 .end(),
                                             |clay| {
                                                 render_dropdown_menu_item(clay, "New");
@@ -207,118 +240,101 @@ pub fn create_layout<'render>(
                     .layout_expand()
                     .id(clay.id("lower_content"))
                     .layout()
-                    .child_gap(16)
+                    .child_gap(theme.spacing.md)
                     .end(),
                 |clay| {
                     clay.with(
                         &Declaration::new()
-                            .content_background_config()
+                            .content_background_config(&theme)
                             .id(clay.id("sidebar"))
                             .layout()
                             .direction(TopToBottom)
-                            .padding(Padding::all(16))
-                            .child_gap(8)
+                            .padding(Padding::all(theme.spacing.md))
+                            .child_gap(theme.spacing.sm)
                             .width(percent!(0.25))
                             .height(grow!())
                             .end(),
                         |clay| {
                             for i in 0..user_data.documents.len() {
-                                let document = user_data.documents.get_mut(i).unwrap();
+                                let button_id = clay.id_index("sidebar_button", i as u32);
+                                if clay.pointer_over(button_id) && user_data.mouse_down_rising_edge
+                                {
+                                    user_data.selected_document_index = i as u8;
+                                }
+
                                 let mut side_bar_button_layout: Declaration<'_, (), ()> =
                                     Declaration::new()
+                                        .id(button_id)
                                         .layout()
                                         .width(grow!())
-                                        .padding(Padding::all(16))
+                                        .padding(Padding::all(theme.spacing.md))
                                         .end()
                                         .to_owned();
 
                                 if i as u8 == user_data.selected_document_index {
-                                    clay.with_styling(
-                                        |clay| {
-                                            if clay.hovered() {
-                                                if user_data.mouse_down_rising_edge {
-                                                    user_data.selected_document_index = i as u8;
-                                                }
+                                    side_bar_button_layout
+                                        .background_color(theme.palette.surface_variant)
+                                        .corner_radius()
+                                        .all(theme.radii.md)
+                                        .end();
+                                }
 
-                                                *side_bar_button_layout
-                                                    .background_color(Color::rgb(
-                                                        120.0, 120.0, 120.0,
-                                                    ))
-                                                    .corner_radius()
-                                                    .all(8.0)
-                                                    .end()
-                                                    .border()
-                                                    .all_directions(3)
-                                                    .color(WHITE)
-                                                    .end()
-                                            } else {
-                                                *side_bar_button_layout
-                                                    .background_color(Color::rgb(
-                                                        120.0, 120.0, 120.0,
-                                                    ))
-                                                    .corner_radius()
-                                                    .all(8.0)
-                                                    .end()
-                                            }
-                                        },
-                                        |clay| {
-                                            clay.text(
-                                                &document.title,
-                                                TextConfig::new().font_size(20).color(WHITE).end(),
-                                            );
-                                        },
-                                    );
-                                } else {
-                                    clay.with_styling(
-                                        |clay| {
-                                            if clay.hovered() {
-                                                if user_data.mouse_down_rising_edge {
-                                                    user_data.selected_document_index = i as u8;
-                                                }
+                                let mut side_bar_button_style =
+                                    StateStyle::new(&side_bar_button_layout);
+                                side_bar_button_style
+                                    .hover(
+                                        *StyleOverride::new()
+                                            .border_width(3)
+                                            .border_color(theme.palette.border),
+                                    )
+                                    .active(
+                                        *StyleOverride::new()
+                                            .background_color(theme.palette.control),
+                                    )
+                                    .transition(0.15);
 
-                                                *side_bar_button_layout
-                                                    .border()
-                                                    .all_directions(3)
-                                                    .color(WHITE)
-                                                    .end()
-                                            } else {
-                                                side_bar_button_layout
-                                            }
-                                        },
-                                        |clay| {
-                                            clay.text(
-                                                &document.title,
-                                                TextConfig::new().font_size(20).color(WHITE).end(),
-                                            );
-                                        },
+                                let document = &user_data.documents[i];
+                                clay.with_state_style(&side_bar_button_style, false, |clay| {
+                                    clay.text(
+                                        &document.title,
+                                        TextConfig::new()
+                                            .style(&theme.typography.title)
+                                            .color(theme.palette.text)
+                                            .end(),
                                     );
-                                }
+                                });
                             }
                         },
                     );
 
                     clay.with(
                         Declaration::new()
-                            .content_background_config()
+                            .content_background_config(&theme)
                             .layout_expand()
                             .id(clay.id("main_content"))
                             .clip(false, true, clay.scroll_offset())
                             .layout()
                             .direction(TopToBottom)
-                            .child_gap(16)
-                            .padding(Padding::all(16))
+                            .child_gap(theme.spacing.md)
+                            .padding(Padding::all(theme.spacing.md))
                             .end(),
                         |clay| {
                             let selected_documtent =
                                 &user_data.documents[user_data.selected_document_index as usize];
                             clay.text(
                                 &selected_documtent.title,
-                                TextConfig::new().font_size(24).color(WHITE).end(),
+                                TextConfig::new()
+                                    .style(&theme.typography.heading)
+                                    .color(theme.palette.text)
+                                    .end(),
                             );
 
                             clay.text(
                                 &selected_documtent.contents,
-                                TextConfig::new().font_size(24).color(WHITE).end(),
+                                TextConfig::new()
+                                    .style(&theme.typography.heading)
+                                    .color(theme.palette.text)
+                                    .end(),
                             );
                         },
                     );
@@ -327,7 +343,10 @@ pub fn create_layout<'render>(
         },
     );
 
-    clay.end()
+    user_data.inspector.layout(&mut clay);
+    let (tree, commands) = clay.end_with_tree();
+    user_data.inspector.update(tree);
+    commands
 }
 
 use crate::UIState;
@@ -8,8 +8,11 @@ use wgpu::util::DeviceExt;
 use wgpu::MultisampleState;
 use winit::dpi::PhysicalSize;
 
-use clay_layout::math::Dimensions;
-use clay_layout::render_commands::RenderCommand;
+use clay_layout::clip::Clip;
+use clay_layout::math::{BoundingBox, Dimensions};
+use clay_layout::render_commands::{
+    Border, CornerRadii, Custom, Image, Rectangle, RenderCommand, Renderer, Scissor, Text,
+};
 
 pub struct TextLine {
     line: glyphon::Buffer,
@@ -757,104 +760,12 @@ impl UIState {
         queue: &wgpu::Queue,
         surface_config: &wgpu::SurfaceConfiguration,
     ) {
-        let mut scissor_position = UIPosition::new();
-        let mut scissor_bounds = UIPosition::new();
-        let mut scissor_active = false;
-        let mut depth: f32 = 0.1;
-
-        for command in render_commands {
-            match command.config {
-                clay_layout::render_commands::RenderCommandConfig::Rectangle(r) => {
-                    self.filled_rectangle(
-                        UIPosition {
-                            x: command.bounding_box.x,
-                            y: command.bounding_box.y,
-                            z: depth as f32,
-                        },
-                        UIPosition {
-                            x: command.bounding_box.width,
-                            y: command.bounding_box.height,
-                            z: depth as f32,
-                        },
-                        UIColor {
-                            r: r.color.r / 255.0,
-                            g: r.color.g / 255.0,
-                            b: r.color.b / 255.0,
-                        },
-                        UICornerRadii {
-                            top_left: r.corner_radii.top_left,
-                            top_right: r.corner_radii.top_right,
-                            bottom_left: r.corner_radii.bottom_left,
-                            bottom_right: r.corner_radii.bottom_right,
-                        },
-                    );
-                }
-                clay_layout::render_commands::RenderCommandConfig::Border(b) => {
-                    self.rectangle(
-                        UIPosition {
-                            x: command.bounding_box.x,
-                            y: command.bounding_box.y,
-                            z: depth as f32,
-                        },
-                        UIPosition {
-                            x: command.bounding_box.width,
-                            y: command.bounding_box.height,
-                            z: depth as f32,
-                        },
-                        UIBorderThickness {
-                            top: (b.width.top as f32),
-                            left: (b.width.left as f32),
-                            bottom: (b.width.bottom as f32),
-                            right: (b.width.right as f32),
-                        },
-                        UIColor {
-                            r: b.color.r / 255.0,
-                            g: b.color.g / 255.0,
-                            b: b.color.b / 255.0,
-                        },
-                        UICornerRadii {
-                            top_left: (b.corner_radii.top_left),
-                            top_right: (b.corner_radii.top_right),
-                            bottom_left: b.corner_radii.bottom_left,
-                            bottom_right: b.corner_radii.bottom_right,
-                        },
-                    );
-                }
-                clay_layout::render_commands::RenderCommandConfig::Text(text) => {
-                    self.text(
-                        text.text,
-                        (text.font_size as f32) * self.dpi_scale,
-                        match text.line_height {
-                            0 => (text.font_size as f32) * 1.5 * self.dpi_scale,
-                            _ => (text.line_height as f32) * self.dpi_scale,
-                        },
-                        UIPosition {
-                            x: command.bounding_box.x,
-                            y: command.bounding_box.y,
-                            z: depth as f32,
-                        },
-                        match scissor_active {
-                            true => Some((scissor_position.clone(), scissor_bounds.clone())),
-                            false => None,
-                        },
-                        Color::rgb(text.color.r as u8, text.color.g as u8, text.color.b as u8),
-                        depth,
-                    );
-                }
-                clay_layout::render_commands::RenderCommandConfig::ScissorStart() => {
-                    scissor_position.x = command.bounding_box.x;
-                    scissor_position.y = command.bounding_box.y;
-                    scissor_bounds.x = command.bounding_box.width;
-                    scissor_bounds.y = command.bounding_box.height;
-                    scissor_active = true;
-                }
-                clay_layout::render_commands::RenderCommandConfig::ScissorEnd() => {
-                    scissor_active = false;
-                }
-                _ => {}
-            }
-            depth -= 0.0001;
+        ClayPass {
+            state: self,
+            depth: 0.1,
+            scissor: None,
         }
+        .render(render_commands);
 
         if self.number_of_vertices > 0 {
             self.render(render_pass, queue);
@@ -865,6 +776,125 @@ impl UIState {
     }
 }
 
+fn ui_color(color: clay_layout::Color) -> UIColor {
+    UIColor {
+        r: color.r / 255.0,
+        g: color.g / 255.0,
+        b: color.b / 255.0,
+    }
+}
+
+fn ui_corner_radii(radii: &CornerRadii) -> UICornerRadii {
+    UICornerRadii {
+        top_left: radii.top_left,
+        top_right: radii.top_right,
+        bottom_left: radii.bottom_left,
+        bottom_right: radii.bottom_right,
+    }
+}
+
+/// Queues the render commands of one frame into the UI state.
+struct ClayPass<'s> {
+    state: &'s mut UIState,
+    depth: f32,
+    /// Position and size of the clip text is limited to
+    scissor: Option<(UIPosition, UIPosition)>,
+}
+
+impl ClayPass<'_> {
+    fn position(&self, bounds: BoundingBox) -> UIPosition {
+        UIPosition {
+            x: bounds.x,
+            y: bounds.y,
+            z: self.depth,
+        }
+    }
+
+    fn size(&self, bounds: BoundingBox) -> UIPosition {
+        UIPosition {
+            x: bounds.width,
+            y: bounds.height,
+            z: self.depth,
+        }
+    }
+
+    fn next_depth(&mut self) {
+        self.depth -= 0.0001;
+    }
+}
+
+impl<'a, ImageElementData: 'a, CustomElementData: 'a>
+    Renderer<'a, ImageElementData, CustomElementData> for ClayPass<'_>
+{
+    fn rectangle(&mut self, bounds: BoundingBox, rectangle: &Rectangle) {
+        let (position, size) = (self.position(bounds), self.size(bounds));
+        self.state.filled_rectangle(
+            position,
+            size,
+            ui_color(rectangle.color),
+            ui_corner_radii(&rectangle.corner_radii),
+        );
+        self.next_depth();
+    }
+
+    fn border(&mut self, bounds: BoundingBox, border: &Border) {
+        let (position, size) = (self.position(bounds), self.size(bounds));
+        self.state.rectangle(
+            position,
+            size,
+            UIBorderThickness {
+                top: border.width.top as f32,
+                left: border.width.left as f32,
+                bottom: border.width.bottom as f32,
+                right: border.width.right as f32,
+            },
+            ui_color(border.color),
+            ui_corner_radii(&border.corner_radii),
+        );
+        self.next_depth();
+    }
+
+    fn text(&mut self, bounds: BoundingBox, text: &Text<'a>) {
+        let dpi_scale = self.state.dpi_scale;
+        let position = self.position(bounds);
+        self.state.text(
+            text.text,
+            (text.font_size as f32) * dpi_scale,
+            match text.line_height {
+                0 => (text.font_size as f32) * 1.5 * dpi_scale,
+                _ => (text.line_height as f32) * dpi_scale,
+            },
+            position,
+            self.scissor.clone(),
+            Color::rgb(text.color.r as u8, text.color.g as u8, text.color.b as u8),
+            self.depth,
+        );
+        self.next_depth();
+    }
+
+    fn image(&mut self, _bounds: BoundingBox, _image: &Image<'a, ImageElementData>) {
+        self.next_depth();
+    }
+
+    fn push_clip(&mut self, _bounds: BoundingBox, _scissor: &Scissor, clip: &Clip) {
+        self.scissor = Some((self.position(clip.bounds), self.size(clip.bounds)));
+        self.next_depth();
+    }
+
+    fn pop_clip(&mut self, clip: Option<&Clip>) {
+        self.scissor = clip.map(|clip| (self.position(clip.bounds), self.size(clip.bounds)));
+        self.next_depth();
+    }
+
+    fn custom(
+        &mut self,
+        _command: &RenderCommand<'a, ImageElementData, CustomElementData>,
+        _custom: &Custom<'a, CustomElementData>,
+    ) {
+        self.next_depth();
+    }
+}
+
 fn make_ui_buffer(
     device: &wgpu::Device,
     label: &str,
@@ -0,0 +1,361 @@
+//! Grouping of render commands into draw calls for instanced GPU renderers.
+//!
+//! [`batch`] merges consecutive commands drawn with the same pipeline state into a [`Batch`],
+//! so a renderer issues one draw per batch instead of one per command. Rectangles, borders and
+//! image quads become [`Instance`]s for a signed distance field rounded rectangle shader, text
+//! runs are kept as is for the text renderer.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{
+    clip::{Clip, ClipStack},
+    color::Color,
+    fonts::FontId,
+    math::BoundingBox,
+    render_commands::{CornerRadii, HandleMapper, RenderCommand, RenderCommandConfig, Text},
+};
+
+/// Pipeline state shared by the commands of a [`Batch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchKind {
+    /// Filled rectangles and borders.
+    Shapes,
+    /// Images of the texture with the given handle.
+    Image { texture: u64 },
+    /// Text runs of the font atlas of `font_id`.
+    Text { font_id: FontId },
+    /// Custom elements with the given handle, drawn by the application.
+    Custom { handle: u64 },
+}
+
+/// Per-instance data of a rounded rectangle, laid out to be uploaded as is to an instance buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[repr(C)]
+pub struct Instance {
+    /// `x`, `y`, `width` and `height`.
+    pub rect: [f32; 4],
+    /// Top left, top right, bottom right and bottom left radii.
+    pub corner_radii: [f32; 4],
+    /// Left, top, right and bottom widths of a border, all `0` to fill the rectangle.
+    pub border_widths: [f32; 4],
+    /// RGBA color from 0 to 1: the fill or border color, or the tint of an image.
+    pub color: [f32; 4],
+}
+
+impl Instance {
+    fn new(bounds: BoundingBox, corner_radii: &CornerRadii, color: Color) -> Self {
+        Self {
+            rect: [bounds.x, bounds.y, bounds.width, bounds.height],
+            corner_radii: [
+                corner_radii.top_left,
+                corner_radii.top_right,
+                corner_radii.bottom_right,
+                corner_radii.bottom_left,
+            ],
+            border_widths: [0.; 4],
+            color: [
+                color.r / 255.,
+                color.g / 255.,
+                color.b / 255.,
+                color.a / 255.,
+            ],
+        }
+    }
+}
+
+/// A text run of a text batch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextRun<'a> {
+    pub bounds: BoundingBox,
+    pub text: Text<'a>,
+}
+
+/// Consecutive commands drawn with one draw call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Batch {
+    pub kind: BatchKind,
+    /// Clip of every command of the batch, `None` outside of any scissor.
+    pub clip: Option<Clip>,
+    /// Range of the commands in [`Batches::text_runs`] for text batches, in
+    /// [`Batches::instances`] otherwise.
+    pub range: Range<usize>,
+}
+
+/// Counters of a [`batch`] pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BatchStats {
+    /// Drawable commands, scissors excluded.
+    pub commands: usize,
+    /// Commands dropped because they are entirely outside of their clip.
+    pub culled: usize,
+    pub batches: usize,
+}
+
+/// Batches of a frame, in draw order.
+#[derive(Debug, Clone, Default)]
+pub struct Batches<'a> {
+    pub batches: Vec<Batch>,
+    pub instances: Vec<Instance>,
+    pub text_runs: Vec<TextRun<'a>>,
+    pub stats: BatchStats,
+}
+
+impl<'a> Batches<'a> {
+    /// Instances of a shape, image or custom batch.
+    pub fn instances(&self, batch: &Batch) -> &[Instance] {
+        match batch.kind {
+            BatchKind::Text { .. } => &[],
+            _ => &self.instances[batch.range.clone()],
+        }
+    }
+
+    /// Text runs of a text batch.
+    pub fn text_runs(&self, batch: &Batch) -> &[TextRun<'a>] {
+        match batch.kind {
+            BatchKind::Text { .. } => &self.text_runs[batch.range.clone()],
+            _ => &[],
+        }
+    }
+
+    /// Extends the last batch if it has the same state, or starts a new one.
+    fn push(&mut self, kind: BatchKind, clip: Option<Clip>, index: usize) {
+        if let Some(last) = self.batches.last_mut() {
+            if last.kind == kind && last.clip == clip && last.range.end == index {
+                last.range.end += 1;
+                return;
+            }
+        }
+        self.batches.push(Batch {
+            kind,
+            clip,
+            range: index..index + 1,
+        });
+    }
+}
+
+/// Groups the commands of a frame into batches, in the order they are given. Image textures and
+/// custom elements are told apart by the handles of `handles`.
+///
+/// Commands entirely outside of their clip are dropped, and text runs split the shape batches
+/// around them to keep the drawing order.
+/// ```
+/// use clay_layout::{batch::batch, layout::Sizing, render_commands::NoHandles, Clay, Declaration};
+///
+/// let mut clay = Clay::new((800., 600.).into());
+/// let mut scope = clay.begin::<(), ()>();
+/// for _ in 0..3 {
+///     scope.with(Declaration::new()
+///         .layout()
+///             .width(Sizing::Fixed(100.))
+///             .height(Sizing::Fixed(100.))
+///             .end()
+///         .background_color((255., 0., 0.).into()), |_| {});
+/// }
+///
+/// let batches = batch(scope.end(), &mut NoHandles);
+/// assert_eq!(batches.stats.commands, 3);
+/// assert_eq!(batches.stats.batches, 1);
+/// assert_eq!(batches.instances.len(), 3);
+/// ```
+pub fn batch<'a, ImageElementData: 'a, CustomElementData: 'a>(
+    render_commands: impl IntoIterator<Item = RenderCommand<'a, ImageElementData, CustomElementData>>,
+    handles: &mut impl HandleMapper<ImageElementData, CustomElementData>,
+) -> Batches<'a> {
+    let mut batches = Batches::default();
+    let mut clips = ClipStack::new();
+
+    for command in render_commands {
+        let bounds = command.bounding_box;
+        match &command.config {
+            RenderCommandConfig::ScissorStart(scissor) => {
+                clips.push(bounds, scissor);
+                continue;
+            }
+            RenderCommandConfig::ScissorEnd() => {
+                clips.pop();
+                continue;
+            }
+            RenderCommandConfig::None() => continue,
+            _ => {}
+        }
+        batches.stats.commands += 1;
+        if !clips.is_visible(&bounds) {
+            batches.stats.culled += 1;
+            continue;
+        }
+        let clip = clips.current().cloned();
+
+        let index = batches.instances.len();
+        match &command.config {
+            RenderCommandConfig::Rectangle(rectangle) => {
+                let instance = Instance::new(bounds, &rectangle.corner_radii, rectangle.color);
+                batches.instances.push(instance);
+                batches.push(BatchKind::Shapes, clip, index);
+            }
+            RenderCommandConfig::Border(border) => {
+                let width = &border.width;
+                if width.left == 0 && width.top == 0 && width.right == 0 && width.bottom == 0 {
+                    continue;
+                }
+                let mut instance = Instance::new(bounds, &border.corner_radii, border.color);
+                instance.border_widths = [
+                    width.left as f32,
+                    width.top as f32,
+                    width.right as f32,
+                    width.bottom as f32,
+                ];
+                batches.instances.push(instance);
+                batches.push(BatchKind::Shapes, clip, index);
+            }
+            RenderCommandConfig::Image(image) => {
+                // Like Clay's renderers, a transparent background leaves the image untinted
+                let tint = if image.background_color.a > 0. {
+                    image.background_color
+                } else {
+                    Color::rgba(255., 255., 255., 255.)
+                };
+                batches
+                    .instances
+                    .push(Instance::new(bounds, &image.corner_radii, tint));
+                let texture = handles.image_handle(image.data);
+                batches.push(BatchKind::Image { texture }, clip, index);
+            }
+            RenderCommandConfig::Custom(custom) => {
+                let instance = Instance::new(bounds, &custom.corner_radii, custom.background_color);
+                batches.instances.push(instance);
+                let handle = handles.custom_handle(custom.data);
+                batches.push(BatchKind::Custom { handle }, clip, index);
+            }
+            RenderCommandConfig::Text(text) => {
+                let index = batches.text_runs.len();
+                batches.text_runs.push(TextRun {
+                    bounds,
+                    text: text.clone(),
+                });
+                let font_id = text.font_id;
+                batches.push(BatchKind::Text { font_id }, clip, index);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    batches.stats.batches = batches.batches.len();
+    batches
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        render_commands::NoHandles,
+        testing::{examples, LayoutHarness},
+        ClayLayoutScope,
+    };
+
+    fn batch_layout(
+        harness: &mut LayoutHarness,
+        layout: impl FnOnce(&mut ClayLayoutScope<'_, '_, (), ()>),
+    ) -> (Vec<BatchKind>, BatchStats) {
+        let clay = harness.clay();
+        let mut scope = clay.begin::<(), ()>();
+        layout(&mut scope);
+        let batches = batch(scope.end(), &mut NoHandles);
+        let kinds = batches.batches.iter().map(|batch| batch.kind).collect();
+        (kinds, batches.stats)
+    }
+
+    #[test]
+    fn example_layouts() {
+        let mut harness = LayoutHarness::new();
+
+        let (kinds, stats) = batch_layout(&mut harness, examples::basic_rectangle);
+        assert_eq!(kinds, [BatchKind::Shapes]);
+        assert_eq!(
+            stats,
+            BatchStats {
+                commands: 1,
+                culled: 0,
+                batches: 1
+            }
+        );
+
+        let (kinds, stats) = batch_layout(&mut harness, examples::dashboard);
+        let text = BatchKind::Text { font_id: FontId(0) };
+        assert_eq!(
+            kinds,
+            [
+                // Background, header and first button
+                BatchKind::Shapes,
+                text,
+                // Second button
+                BatchKind::Shapes,
+                text,
+                // Sidebar and its entry
+                BatchKind::Shapes,
+                text,
+                // Border of the entry, drawn after its text
+                BatchKind::Shapes,
+                // The clipped document and its three lines
+                BatchKind::Shapes,
+                text,
+            ]
+        );
+        assert_eq!(
+            stats,
+            BatchStats {
+                commands: 14,
+                culled: 0,
+                batches: 9
+            }
+        );
+    }
+
+    #[test]
+    fn clips_split_batches_and_cull() {
+        let scissor = crate::render_commands::Scissor {
+            horizontal: true,
+            vertical: true,
+            corner_radii: CornerRadii::default(),
+        };
+        let rectangle = || {
+            RenderCommandConfig::Rectangle(crate::render_commands::Rectangle {
+                color: Color::rgb(255., 0., 0.),
+                corner_radii: CornerRadii::default(),
+            })
+        };
+        let commands: [RenderCommand<'static, (), ()>; 6] = [
+            RenderCommand::new(BoundingBox::new(0., 0., 100., 100.), rectangle(), 1, 0),
+            RenderCommand::new(
+                BoundingBox::new(0., 0., 50., 50.),
+                RenderCommandConfig::ScissorStart(scissor),
+                2,
+                0,
+            ),
+            RenderCommand::new(BoundingBox::new(0., 0., 10., 10.), rectangle(), 3, 0),
+            RenderCommand::new(BoundingBox::new(60., 60., 10., 10.), rectangle(), 4, 0),
+            RenderCommand::new(BoundingBox::new(20., 20., 10., 10.), rectangle(), 5, 0),
+            RenderCommand::new(
+                BoundingBox::default(),
+                RenderCommandConfig::ScissorEnd(),
+                2,
+                0,
+            ),
+        ];
+        let batches = batch(commands, &mut NoHandles);
+        assert_eq!(
+            batches.stats,
+            BatchStats {
+                commands: 4,
+                culled: 1,
+                batches: 2
+            }
+        );
+        assert_eq!(batches.batches[0].clip, None);
+        assert_eq!(batches.batches[1].range, 1..3);
+        assert_eq!(
+            batches.instances(&batches.batches[1])[1].rect,
+            [20., 20., 10., 10.]
+        );
+    }
+}
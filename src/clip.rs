@@ -0,0 +1,398 @@
+//! Resolution of nested scissor regions.
+//!
+//! Clay emits scissors as a flat stream of `ScissorStart` and `ScissorEnd` commands around the
+//! commands they clip. [`ClipStack`] tracks their nesting and gives every drawable command the
+//! [`Clip`] that applies to it, the intersection of all the open scissors.
+
+use alloc::vec::Vec;
+
+use crate::{
+    math::BoundingBox,
+    render_commands::{CornerRadii, RenderCommand, RenderCommandConfig, Scissor},
+};
+
+/// Region the commands inside one or more scissors are clipped to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clip {
+    /// Intersection of the bounds of the open scissors.
+    pub bounds: BoundingBox,
+    /// Rounding of the corners of `bounds`. A corner is rounded when it is also the corner of a
+    /// rounded scissor, with the largest radius if several scissors share it. Rounded corners of
+    /// scissors cut by another scissor are dropped.
+    pub corner_radii: CornerRadii,
+}
+
+impl Clip {
+    fn new(bounds: BoundingBox, corner_radii: &CornerRadii) -> Self {
+        Self {
+            bounds,
+            corner_radii: corner_radii.clone(),
+        }
+    }
+
+    /// Returns the clip shared by `self` and the scissor `bounds`.
+    fn intersection(&self, bounds: BoundingBox, corner_radii: &CornerRadii) -> Self {
+        let shared = self.bounds.intersection(&bounds);
+        if shared.is_empty() {
+            return Self::new(shared, &CornerRadii::default());
+        }
+        let (left, top) = (shared.x, shared.y);
+        let (right, bottom) = (shared.x + shared.width, shared.y + shared.height);
+        // A corner of the intersection keeps the rounding of the boxes it is also the corner of
+        let radius = |x: f32, y: f32, parent: f32, scissor: f32| {
+            let at = |rect: &BoundingBox, radius: f32| {
+                let corner = (x == rect.x || x == rect.x + rect.width)
+                    && (y == rect.y || y == rect.y + rect.height);
+                if corner {
+                    radius
+                } else {
+                    0.
+                }
+            };
+            at(&self.bounds, parent).max(at(&bounds, scissor))
+        };
+        let parent = &self.corner_radii;
+        Self {
+            bounds: shared,
+            corner_radii: CornerRadii {
+                top_left: radius(left, top, parent.top_left, corner_radii.top_left),
+                top_right: radius(right, top, parent.top_right, corner_radii.top_right),
+                bottom_left: radius(left, bottom, parent.bottom_left, corner_radii.bottom_left),
+                bottom_right: radius(
+                    right,
+                    bottom,
+                    parent.bottom_right,
+                    corner_radii.bottom_right,
+                ),
+            },
+        }
+    }
+}
+
+/// A drawable render command with the clip it is drawn in.
+#[derive(Debug)]
+pub struct ClippedCommand<'a, ImageElementData, CustomElementData> {
+    pub command: RenderCommand<'a, ImageElementData, CustomElementData>,
+    /// `None` outside of any scissor.
+    pub clip: Option<Clip>,
+}
+
+impl<ImageElementData, CustomElementData> Clone
+    for ClippedCommand<'_, ImageElementData, CustomElementData>
+{
+    fn clone(&self) -> Self {
+        Self {
+            command: self.command.clone(),
+            clip: self.clip.clone(),
+        }
+    }
+}
+
+/// Stack of the open scissor regions, each one intersected with the enclosing ones.
+///
+/// Feed it the render commands in order with [`ClipStack::clip`], or a whole frame with
+/// [`ClipStack::resolve`]. Unbalanced `ScissorEnd` commands are ignored.
+/// ```
+/// use clay_layout::{clip::ClipStack, layout::Sizing, math::BoundingBox, Clay, Declaration};
+///
+/// let mut clay = Clay::new((800., 600.).into());
+/// let mut scope = clay.begin::<(), ()>();
+/// scope.with(Declaration::new()
+///     .layout()
+///         .width(Sizing::Fixed(100.))
+///         .height(Sizing::Fixed(100.))
+///         .end()
+///     .clip(true, true, (0., 0.).into()), |scope| {
+///     scope.with(Declaration::new()
+///         .layout()
+///             .width(Sizing::Fixed(200.))
+///             .height(Sizing::Fixed(50.))
+///             .end()
+///         .background_color((255., 0., 0.).into()), |_| {});
+/// });
+///
+/// let commands: Vec<_> = ClipStack::new().resolve(scope.end()).collect();
+/// assert_eq!(commands.len(), 1);
+/// assert_eq!(commands[0].clip.as_ref().unwrap().bounds, BoundingBox::new(0., 0., 100., 100.));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ClipStack {
+    clips: Vec<Clip>,
+    cull: bool,
+}
+
+impl ClipStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops the drawable commands entirely outside of their clip.
+    pub fn cull(mut self, cull: bool) -> Self {
+        self.cull = cull;
+        self
+    }
+
+    /// The clip of the innermost open scissor.
+    pub fn current(&self) -> Option<&Clip> {
+        self.clips.last()
+    }
+
+    /// Number of open scissors.
+    pub fn depth(&self) -> usize {
+        self.clips.len()
+    }
+
+    /// Opens a scissor over `bounds`, returning its clip.
+    pub fn push(&mut self, bounds: BoundingBox, scissor: &Scissor) -> &Clip {
+        let clip = match self.clips.last() {
+            Some(parent) => parent.intersection(bounds, &scissor.corner_radii),
+            None => Clip::new(bounds, &scissor.corner_radii),
+        };
+        self.clips.push(clip);
+        self.clips.last().unwrap()
+    }
+
+    /// Closes the innermost scissor, returning its clip or `None` if none is open.
+    pub fn pop(&mut self) -> Option<Clip> {
+        self.clips.pop()
+    }
+
+    /// Returns whether any part of `bounds` is inside the current clip.
+    pub fn is_visible(&self, bounds: &BoundingBox) -> bool {
+        self.clips
+            .last()
+            .is_none_or(|clip| !clip.bounds.intersection(bounds).is_empty())
+    }
+
+    /// Updates the stack with a scissor command, or returns a drawable command with its clip.
+    /// Returns `None` for scissors and, when culling, for commands outside of their clip.
+    pub fn clip<'a, ImageElementData, CustomElementData>(
+        &mut self,
+        command: RenderCommand<'a, ImageElementData, CustomElementData>,
+    ) -> Option<ClippedCommand<'a, ImageElementData, CustomElementData>> {
+        match &command.config {
+            RenderCommandConfig::ScissorStart(scissor) => {
+                self.push(command.bounding_box, scissor);
+                None
+            }
+            RenderCommandConfig::ScissorEnd() => {
+                self.pop();
+                None
+            }
+            RenderCommandConfig::None() => None,
+            _ if self.cull && !self.is_visible(&command.bounding_box) => None,
+            _ => Some(ClippedCommand {
+                clip: self.current().cloned(),
+                command,
+            }),
+        }
+    }
+
+    /// Resolves the clips of a frame of commands, see [`ClipStack::clip`].
+    pub fn resolve<'a, ImageElementData: 'a, CustomElementData: 'a>(
+        mut self,
+        render_commands: impl IntoIterator<
+            Item = RenderCommand<'a, ImageElementData, CustomElementData>,
+        >,
+    ) -> impl Iterator<Item = ClippedCommand<'a, ImageElementData, CustomElementData>> {
+        render_commands
+            .into_iter()
+            .filter_map(move |command| self.clip(command))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{color::Color, render_commands::Rectangle};
+
+    fn radii(radius: f32) -> CornerRadii {
+        CornerRadii {
+            top_left: radius,
+            top_right: radius,
+            bottom_left: radius,
+            bottom_right: radius,
+        }
+    }
+
+    fn scissor_start(bounds: BoundingBox, radius: f32) -> RenderCommand<'static, (), ()> {
+        let config = RenderCommandConfig::ScissorStart(Scissor {
+            horizontal: true,
+            vertical: true,
+            corner_radii: radii(radius),
+        });
+        RenderCommand::new(bounds, config, 0, 0)
+    }
+
+    fn scissor_end() -> RenderCommand<'static, (), ()> {
+        RenderCommand::new(
+            BoundingBox::default(),
+            RenderCommandConfig::ScissorEnd(),
+            0,
+            0,
+        )
+    }
+
+    fn rectangle(bounds: BoundingBox, id: u32) -> RenderCommand<'static, (), ()> {
+        let config = RenderCommandConfig::Rectangle(Rectangle {
+            color: Color::rgb(255., 0., 0.),
+            corner_radii: radii(0.),
+        });
+        RenderCommand::new(bounds, config, id, 0)
+    }
+
+    #[test]
+    fn nested_clips_keep_shared_rounded_corners() {
+        let mut stack = ClipStack::new();
+        stack.push(
+            BoundingBox::new(0., 0., 100., 100.),
+            &Scissor {
+                horizontal: true,
+                vertical: true,
+                corner_radii: radii(10.),
+            },
+        );
+        let clip = stack.push(
+            BoundingBox::new(0., 0., 50., 200.),
+            &Scissor {
+                horizontal: true,
+                vertical: true,
+                corner_radii: radii(4.),
+            },
+        );
+        assert_eq!(clip.bounds, BoundingBox::new(0., 0., 50., 100.));
+        assert_eq!(
+            clip.corner_radii,
+            CornerRadii {
+                top_left: 10.,
+                top_right: 4.,
+                bottom_left: 10.,
+                bottom_right: 0.,
+            }
+        );
+        assert_eq!(stack.depth(), 2);
+        stack.pop();
+        assert_eq!(stack.current().unwrap().corner_radii, radii(10.));
+    }
+
+    #[test]
+    fn unbalanced_ends_are_ignored() {
+        let commands = [
+            scissor_end(),
+            rectangle(BoundingBox::new(0., 0., 10., 10.), 1),
+            scissor_start(BoundingBox::new(0., 0., 5., 5.), 0.),
+            rectangle(BoundingBox::new(0., 0., 10., 10.), 2),
+        ];
+        let clipped: Vec<_> = ClipStack::new().resolve(commands).collect();
+        assert_eq!(clipped.len(), 2);
+        assert_eq!(clipped[0].clip, None);
+        assert_eq!(
+            clipped[1].clip.as_ref().unwrap().bounds,
+            BoundingBox::new(0., 0., 5., 5.)
+        );
+    }
+
+    /// Xorshift generator, enough to build random scissor nestings reproducibly.
+    struct Random(u64);
+
+    impl Random {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, max: u64) -> u64 {
+            self.next() % max
+        }
+
+        fn bounds(&mut self) -> BoundingBox {
+            BoundingBox::new(
+                self.below(200) as f32,
+                self.below(200) as f32,
+                self.below(150) as f32,
+                self.below(150) as f32,
+            )
+        }
+    }
+
+    /// Builds a random stream of nested scissors and rectangles, with the scissors open at each
+    /// rectangle, by id.
+    fn random_frame(
+        random: &mut Random,
+    ) -> (Vec<RenderCommand<'static, (), ()>>, Vec<Vec<BoundingBox>>) {
+        let mut commands = Vec::new();
+        let mut open = Vec::new();
+        let mut expected = Vec::new();
+        for _ in 0..random.below(60) {
+            match random.below(3) {
+                0 => {
+                    let bounds = random.bounds();
+                    open.push(bounds);
+                    commands.push(scissor_start(bounds, random.below(10) as f32));
+                }
+                1 if !open.is_empty() => {
+                    open.pop();
+                    commands.push(scissor_end());
+                }
+                _ => {
+                    commands.push(rectangle(random.bounds(), expected.len() as u32));
+                    expected.push(open.clone());
+                }
+            }
+        }
+        (commands, expected)
+    }
+
+    #[test]
+    fn random_nesting_resolves_the_intersection_of_open_scissors() {
+        let mut random = Random(0x2545_f491_4f6c_dd1d);
+        for _ in 0..500 {
+            let (commands, expected) = random_frame(&mut random);
+            let clipped: Vec<_> = ClipStack::new().resolve(commands).collect();
+            assert_eq!(clipped.len(), expected.len());
+            for (command, open) in clipped.iter().zip(&expected) {
+                let intersection = open
+                    .iter()
+                    .copied()
+                    .reduce(|clip, bounds| clip.intersection(&bounds));
+                let clip = command.clip.as_ref().map(|clip| clip.bounds);
+                match (clip, intersection) {
+                    (Some(clip), Some(intersection)) if intersection.is_empty() => {
+                        assert!(clip.is_empty())
+                    }
+                    (clip, intersection) => assert_eq!(clip, intersection),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_nesting_culls_only_hidden_commands() {
+        let mut random = Random(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..500 {
+            let (commands, expected) = random_frame(&mut random);
+            let bounds: Vec<_> = commands
+                .iter()
+                .filter(|command| matches!(command.config, RenderCommandConfig::Rectangle(_)))
+                .map(|command| command.bounding_box)
+                .collect();
+            let kept: Vec<_> = ClipStack::new()
+                .cull(true)
+                .resolve(commands)
+                .map(|command| command.command.id as usize)
+                .collect();
+            let visible: Vec<_> = (0..expected.len())
+                .filter(|&id| {
+                    let area = expected[id]
+                        .iter()
+                        .fold(bounds[id], |area, open| area.intersection(open));
+                    // Commands outside of any scissor are never culled
+                    expected[id].is_empty() || !area.is_empty()
+                })
+                .collect();
+            assert_eq!(kept, visible);
+        }
+    }
+}
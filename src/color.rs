@@ -2,6 +2,7 @@ use crate::bindings::*;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     pub r: f32,
     pub g: f32,
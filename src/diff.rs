@@ -0,0 +1,450 @@
+//! Comparison of the render commands of consecutive frames.
+//!
+//! [`FrameDiff`] matches the commands of two frames by element id and reports the added, removed
+//! and changed ones, with the regions of the screen that need to be redrawn. To only know whether
+//! anything changed, use [`Clay::layout_changed`](crate::Clay::layout_changed).
+
+use alloc::vec::Vec;
+use core::hash::Hasher;
+
+use crate::{
+    bindings::Clay_RenderCommand,
+    color::Color,
+    math::BoundingBox,
+    render_commands::{CornerRadii, RenderCommand, RenderCommandConfig},
+};
+
+/// Differences between the render commands of two frames.
+///
+/// Commands are matched by element id and command type, so the rectangle, border and scissor
+/// commands of an element are compared separately. Image and custom data are compared by address.
+/// ```
+/// use clay_layout::{diff::FrameDiff, layout::Sizing, Clay, Declaration};
+///
+/// let mut clay = Clay::new((800., 600.).into());
+/// let mut frame = |color: (f32, f32, f32)| {
+///     let mut scope = clay.begin::<(), ()>();
+///     scope.with(Declaration::new()
+///         .layout()
+///             .width(Sizing::Fixed(100.))
+///             .height(Sizing::Fixed(100.))
+///             .end()
+///         .background_color(color.into()), |_| {});
+///     scope.end().collect::<Vec<_>>()
+/// };
+/// let previous = frame((255., 0., 0.));
+/// let current = frame((0., 255., 0.));
+///
+/// let diff = FrameDiff::new(&previous, &current);
+/// assert_eq!(diff.changed(), [(0, 0)]);
+/// assert_eq!(diff.dirty_regions(), [current[0].bounding_box]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrameDiff {
+    added: Vec<usize>,
+    removed: Vec<usize>,
+    changed: Vec<(usize, usize)>,
+    dirty_regions: Vec<BoundingBox>,
+}
+
+impl FrameDiff {
+    /// Compares the commands of the `previous` frame with the ones of the `current` frame.
+    pub fn new<ImageElementData, CustomElementData>(
+        previous: &[RenderCommand<'_, ImageElementData, CustomElementData>],
+        current: &[RenderCommand<'_, ImageElementData, CustomElementData>],
+    ) -> Self {
+        let previous_keys = keys(previous);
+        let current_keys = keys(current);
+
+        let mut diff = Self::default();
+        let mut damage = Vec::new();
+        let (mut p, mut c) = (0, 0);
+        while p < previous_keys.len() || c < current_keys.len() {
+            let ordering = match (previous_keys.get(p), current_keys.get(c)) {
+                (Some(previous), Some(current)) => previous.0.cmp(&current.0),
+                (Some(_), None) => core::cmp::Ordering::Less,
+                _ => core::cmp::Ordering::Greater,
+            };
+
+            match ordering {
+                core::cmp::Ordering::Less => {
+                    let index = previous_keys[p].1;
+                    diff.removed.push(index);
+                    damage.push(previous[index].bounding_box);
+                    p += 1;
+                }
+                core::cmp::Ordering::Greater => {
+                    let index = current_keys[c].1;
+                    diff.added.push(index);
+                    damage.push(current[index].bounding_box);
+                    c += 1;
+                }
+                core::cmp::Ordering::Equal => {
+                    let (old, new) = (previous_keys[p].1, current_keys[c].1);
+                    if !same_command(&previous[old], &current[new]) {
+                        diff.changed.push((old, new));
+                        damage.push(previous[old].bounding_box);
+                        damage.push(current[new].bounding_box);
+                    }
+                    p += 1;
+                    c += 1;
+                }
+            }
+        }
+
+        diff.added.sort_unstable();
+        diff.removed.sort_unstable();
+        diff.changed.sort_unstable();
+        diff.dirty_regions = merge_regions(damage);
+        diff
+    }
+
+    /// Indices in the current frame of the commands that weren't in the previous frame.
+    pub fn added(&self) -> &[usize] {
+        &self.added
+    }
+
+    /// Indices in the previous frame of the commands that aren't in the current frame anymore.
+    pub fn removed(&self) -> &[usize] {
+        &self.removed
+    }
+
+    /// Indices in the previous and current frame of the commands whose content changed.
+    pub fn changed(&self) -> &[(usize, usize)] {
+        &self.changed
+    }
+
+    /// Areas to redraw, as non touching boxes covering every added, removed and changed command.
+    pub fn dirty_regions(&self) -> &[BoundingBox] {
+        &self.dirty_regions
+    }
+
+    /// Returns whether both frames render the same.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Sorted `(id, command type, occurrence)` keys of `commands`, with the index of their command.
+fn keys<ImageElementData, CustomElementData>(
+    commands: &[RenderCommand<'_, ImageElementData, CustomElementData>],
+) -> Vec<((u32, u8, usize), usize)> {
+    let mut keys: Vec<_> = commands
+        .iter()
+        .enumerate()
+        .map(|(index, command)| ((command.id, kind(&command.config), 0), index))
+        .collect();
+
+    // Commands of an element sharing a type keep their order, such as the lines of a text
+    keys.sort_by_key(|&(key, index)| (key, index));
+    for i in 1..keys.len() {
+        let (previous, _) = keys[i - 1];
+        let (key, _) = &mut keys[i];
+        if (key.0, key.1) == (previous.0, previous.1) {
+            key.2 = previous.2 + 1;
+        }
+    }
+    keys
+}
+
+fn kind<ImageElementData, CustomElementData>(
+    config: &RenderCommandConfig<'_, ImageElementData, CustomElementData>,
+) -> u8 {
+    match config {
+        RenderCommandConfig::None() => 0,
+        RenderCommandConfig::Rectangle(_) => 1,
+        RenderCommandConfig::Border(_) => 2,
+        RenderCommandConfig::Text(_) => 3,
+        RenderCommandConfig::Image(_) => 4,
+        RenderCommandConfig::ScissorStart(_) => 5,
+        RenderCommandConfig::ScissorEnd() => 6,
+        RenderCommandConfig::Custom(_) => 7,
+    }
+}
+
+fn same_command<ImageElementData, CustomElementData>(
+    a: &RenderCommand<'_, ImageElementData, CustomElementData>,
+    b: &RenderCommand<'_, ImageElementData, CustomElementData>,
+) -> bool {
+    if a.bounding_box != b.bounding_box || a.z_index != b.z_index {
+        return false;
+    }
+
+    match (&a.config, &b.config) {
+        (RenderCommandConfig::None(), RenderCommandConfig::None())
+        | (RenderCommandConfig::ScissorEnd(), RenderCommandConfig::ScissorEnd()) => true,
+        (RenderCommandConfig::Rectangle(a), RenderCommandConfig::Rectangle(b)) => a == b,
+        (RenderCommandConfig::Border(a), RenderCommandConfig::Border(b)) => a == b,
+        (RenderCommandConfig::Text(a), RenderCommandConfig::Text(b)) => a == b,
+        (RenderCommandConfig::ScissorStart(a), RenderCommandConfig::ScissorStart(b)) => a == b,
+        (RenderCommandConfig::Image(a), RenderCommandConfig::Image(b)) => {
+            a.background_color == b.background_color
+                && a.corner_radii == b.corner_radii
+                && core::ptr::eq(a.data, b.data)
+        }
+        (RenderCommandConfig::Custom(a), RenderCommandConfig::Custom(b)) => {
+            a.background_color == b.background_color
+                && a.corner_radii == b.corner_radii
+                && core::ptr::eq(a.data, b.data)
+        }
+        _ => false,
+    }
+}
+
+/// Merges the touching boxes of `regions` until none touch, dropping the empty ones.
+fn merge_regions(regions: Vec<BoundingBox>) -> Vec<BoundingBox> {
+    let mut merged: Vec<BoundingBox> = Vec::with_capacity(regions.len());
+
+    for mut region in regions.into_iter().filter(|region| !region.is_empty()) {
+        // A merged box can touch boxes it didn't before, so keep absorbing until stable
+        while let Some(index) = merged.iter().position(|other| other.touches(&region)) {
+            region = region.union(&merged.swap_remove(index));
+        }
+        merged.push(region);
+    }
+
+    merged
+}
+
+/// 64 bit FNV-1a, stable across runs and platforms unlike the std hashers.
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// Hashes the content of a frame, used by [`Clay::layout_changed`](crate::Clay::layout_changed).
+///
+/// # Safety
+/// `commands` must come from the current call to `Clay_EndLayout`.
+pub(crate) unsafe fn hash_frame<ImageElementData, CustomElementData>(
+    commands: &[Clay_RenderCommand],
+) -> u64 {
+    let mut state = Fnv::default();
+
+    for command in commands {
+        let config = unsafe {
+            RenderCommandConfig::<ImageElementData, CustomElementData>::from_clay_render_command(
+                command,
+            )
+        };
+        let bounding_box: BoundingBox = command.boundingBox.into();
+        write_f32s(
+            &mut state,
+            &[
+                bounding_box.x,
+                bounding_box.y,
+                bounding_box.width,
+                bounding_box.height,
+            ],
+        );
+        state.write_u32(command.id);
+        state.write_i16(command.zIndex);
+        state.write_u8(kind(&config));
+
+        match &config {
+            RenderCommandConfig::None() | RenderCommandConfig::ScissorEnd() => {}
+            RenderCommandConfig::Rectangle(rectangle) => {
+                write_color(&mut state, rectangle.color);
+                write_radii(&mut state, &rectangle.corner_radii);
+            }
+            RenderCommandConfig::Border(border) => {
+                write_color(&mut state, border.color);
+                write_radii(&mut state, &border.corner_radii);
+                let width = &border.width;
+                for side in [
+                    width.left,
+                    width.right,
+                    width.top,
+                    width.bottom,
+                    width.between_children,
+                ] {
+                    state.write_u16(side);
+                }
+            }
+            RenderCommandConfig::Text(text) => {
+                write_color(&mut state, text.color);
+                for value in [
+                    text.font_id.0,
+                    text.font_size,
+                    text.letter_spacing,
+                    text.line_height,
+                ] {
+                    state.write_u16(value);
+                }
+                state.write_usize(text.text.len());
+                state.write(text.text.as_bytes());
+            }
+            RenderCommandConfig::Image(image) => {
+                write_color(&mut state, image.background_color);
+                write_radii(&mut state, &image.corner_radii);
+                state.write_usize(image.data as *const _ as usize);
+            }
+            RenderCommandConfig::ScissorStart(scissor) => {
+                state.write_u8(scissor.horizontal as u8 | (scissor.vertical as u8) << 1);
+                write_radii(&mut state, &scissor.corner_radii);
+            }
+            RenderCommandConfig::Custom(custom) => {
+                write_color(&mut state, custom.background_color);
+                write_radii(&mut state, &custom.corner_radii);
+                state.write_usize(custom.data as *const _ as usize);
+            }
+        }
+    }
+
+    state.finish()
+}
+
+fn write_f32s(state: &mut Fnv, values: &[f32]) {
+    for value in values {
+        state.write_u32(value.to_bits());
+    }
+}
+
+fn write_color(state: &mut Fnv, color: Color) {
+    write_f32s(state, &[color.r, color.g, color.b, color.a]);
+}
+
+fn write_radii(state: &mut Fnv, radii: &CornerRadii) {
+    write_f32s(
+        state,
+        &[
+            radii.top_left,
+            radii.top_right,
+            radii.bottom_left,
+            radii.bottom_right,
+        ],
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        layout::Sizing, testing::LayoutHarness, text::TextConfig, ClayLayoutScope, Declaration,
+    };
+
+    #[rustfmt::skip]
+    fn layout(clay: &mut ClayLayoutScope<'_, '_, (), ()>, color: Color, extra: bool) {
+        clay.with(Declaration::new()
+            .id(clay.id("left"))
+            .layout()
+                .width(Sizing::Fixed(100.))
+                .height(Sizing::Fixed(100.))
+                .end()
+            .background_color(color), |clay|
+        {
+            clay.text("label", TextConfig::new().font_size(10).end());
+        });
+        clay.with(Declaration::new()
+            .id(clay.id("right"))
+            .layout()
+                .width(Sizing::Fixed(50.))
+                .height(Sizing::Fixed(50.))
+                .end()
+            .background_color(Color::rgb(0., 0., 255.)), |_| {});
+        if extra {
+            clay.with(Declaration::new()
+                .id(clay.id("extra"))
+                .layout()
+                    .width(Sizing::Fixed(20.))
+                    .height(Sizing::Fixed(20.))
+                    .end()
+                .background_color(Color::rgb(0., 255., 0.)), |_| {});
+        }
+    }
+
+    #[test]
+    fn added_removed_changed() {
+        let red = Color::rgb(255., 0., 0.);
+        let mut harness = LayoutHarness::new();
+        let clay = harness.clay();
+
+        let mut scope = clay.begin::<(), ()>();
+        layout(&mut scope, red, true);
+        let previous: Vec<_> = scope.end().collect();
+
+        let mut scope = clay.begin::<(), ()>();
+        layout(&mut scope, red, true);
+        let same: Vec<_> = scope.end().collect();
+        let diff = FrameDiff::new(&previous, &same);
+        assert!(diff.is_empty());
+        assert!(diff.dirty_regions().is_empty());
+
+        let mut scope = clay.begin::<(), ()>();
+        layout(&mut scope, Color::rgb(255., 255., 0.), false);
+        let current: Vec<_> = scope.end().collect();
+        let diff = FrameDiff::new(&previous, &current);
+
+        assert!(diff.added().is_empty());
+        assert_eq!(diff.removed().len(), 1);
+        assert_eq!(previous[diff.removed()[0]].id, clay.id("extra").id.id);
+        assert_eq!(diff.changed().len(), 1);
+        let (old, new) = diff.changed()[0];
+        assert_eq!(previous[old].id, clay.id("left").id.id);
+        assert_eq!(current[new].id, clay.id("left").id.id);
+
+        // The left box and the extra box don't touch
+        assert_eq!(
+            diff.dirty_regions(),
+            [
+                BoundingBox::new(0., 0., 100., 100.),
+                BoundingBox::new(150., 0., 20., 20.)
+            ]
+        );
+
+        let reverse = FrameDiff::new(&current, &previous);
+        assert_eq!(reverse.added(), diff.removed());
+    }
+
+    #[test]
+    fn merge_touching_regions() {
+        let merged = merge_regions(vec![
+            BoundingBox::new(0., 0., 10., 10.),
+            BoundingBox::new(50., 0., 10., 10.),
+            BoundingBox::new(0., 0., 0., 0.),
+            // Bridges the two first boxes
+            BoundingBox::new(10., 0., 40., 5.),
+            BoundingBox::new(100., 100., 5., 5.),
+        ]);
+        assert_eq!(
+            merged,
+            [
+                BoundingBox::new(0., 0., 60., 10.),
+                BoundingBox::new(100., 100., 5., 5.)
+            ]
+        );
+    }
+
+    #[test]
+    fn layout_changed() {
+        let mut harness = LayoutHarness::new();
+        let clay = harness.clay();
+        let mut frame = |color| {
+            let mut scope = clay.begin::<(), ()>();
+            layout(&mut scope, color, false);
+            let _ = scope.end();
+            clay.layout_changed()
+        };
+
+        assert!(frame(Color::rgb(255., 0., 0.)));
+        assert!(!frame(Color::rgb(255., 0., 0.)));
+        assert!(frame(Color::rgb(0., 0., 0.)));
+        assert!(!frame(Color::rgb(0., 0., 0.)));
+    }
+}
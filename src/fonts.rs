@@ -0,0 +1,263 @@
+//! Fonts of the text elements, shared by a text measurer and the renderer drawing the text.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Id of a font of a [`FontRegistry`], given to
+/// [`TextConfig::font_id`](crate::text::TextConfig::font_id) and found back in the `font_id` of
+/// [`Text`](crate::render_commands::Text).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FontId(pub u16);
+
+impl From<u16> for FontId {
+    fn from(value: u16) -> Self {
+        Self(value)
+    }
+}
+
+impl From<FontId> for u16 {
+    fn from(value: FontId) -> Self {
+        value.0
+    }
+}
+
+impl core::fmt::Display for FontId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Fonts able to tell whether they have a glyph for a character, to fall back to the next font
+/// of a fallback chain when they don't.
+pub trait HasGlyph {
+    fn has_glyph(&self, character: char) -> bool;
+}
+
+#[derive(Debug, Clone)]
+struct Entry<F> {
+    name: String,
+    font: F,
+    fallbacks: Vec<FontId>,
+}
+
+/// Fonts registered by name, indexed by the [`FontId`] given to the text elements. The same
+/// registry is given to the text measurer and to the renderer, so text is drawn with the fonts
+/// it was measured with.
+///
+/// The font type is the one of the library measuring and drawing the text: a `fontdue::Font`,
+/// an `ab_glyph::FontArc`, a `cosmic_text::FamilyOwned`, a raylib `Font`, a skia `Typeface`, or
+/// the CSS family of the SVG and HTML renderers.
+/// ```
+/// use clay_layout::fonts::{FontId, FontRegistry};
+///
+/// let mut fonts = FontRegistry::new();
+/// let body = fonts.add("body", "Inter");
+/// let heading = fonts.add("heading", "Playfair");
+/// assert_eq!((body, heading), (FontId(0), FontId(1)));
+/// assert_eq!(fonts.id("heading"), Some(heading));
+/// assert_eq!(fonts.get(heading), Some(&"Playfair"));
+/// assert_eq!(fonts.get(FontId(2)), None);
+///
+/// let emoji = fonts.add("emoji", "Noto Color Emoji");
+/// fonts.set_fallbacks(body, [emoji]);
+/// assert!(fonts.chain(body).eq([&"Inter", &"Noto Color Emoji"]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FontRegistry<F> {
+    fonts: Vec<Entry<F>>,
+}
+
+impl<F> Default for FontRegistry<F> {
+    fn default() -> Self {
+        Self { fonts: Vec::new() }
+    }
+}
+
+impl<F> AsRef<FontRegistry<F>> for FontRegistry<F> {
+    fn as_ref(&self) -> &FontRegistry<F> {
+        self
+    }
+}
+
+impl<F> FontRegistry<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a font under `name` and returns its id. A font already registered under `name`
+    /// is replaced, keeping its id and fallbacks.
+    pub fn add(&mut self, name: impl Into<String>, font: F) -> FontId {
+        let name = name.into();
+        if let Some(id) = self.id(&name) {
+            self.fonts[id.0 as usize].font = font;
+            return id;
+        }
+
+        let id = FontId(self.fonts.len() as u16);
+        self.fonts.push(Entry {
+            name,
+            font,
+            fallbacks: Vec::new(),
+        });
+        id
+    }
+
+    /// Id of the font registered under `name`.
+    pub fn id(&self, name: &str) -> Option<FontId> {
+        self.fonts
+            .iter()
+            .position(|entry| entry.name == name)
+            .map(|index| FontId(index as u16))
+    }
+
+    pub fn name(&self, id: impl Into<FontId>) -> Option<&str> {
+        self.entry(id.into()).map(|entry| entry.name.as_str())
+    }
+
+    pub fn get(&self, id: impl Into<FontId>) -> Option<&F> {
+        self.entry(id.into()).map(|entry| &entry.font)
+    }
+
+    /// Sets the fonts drawing the characters `id` has no glyph for, tried in order.
+    pub fn set_fallbacks(&mut self, id: FontId, fallbacks: impl IntoIterator<Item = FontId>) {
+        if let Some(entry) = self.fonts.get_mut(id.0 as usize) {
+            entry.fallbacks = fallbacks.into_iter().collect();
+        }
+    }
+
+    pub fn fallbacks(&self, id: impl Into<FontId>) -> &[FontId] {
+        self.entry(id.into())
+            .map_or(&[], |entry| entry.fallbacks.as_slice())
+    }
+
+    /// The font of `id` followed by its fallbacks, skipping the ids not registered.
+    pub fn chain(&self, id: impl Into<FontId>) -> impl Iterator<Item = &F> {
+        let id = id.into();
+        let fallbacks = self.fallbacks(id).iter().copied();
+        core::iter::once(id)
+            .chain(fallbacks)
+            .filter_map(|id| self.get(id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.fonts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty()
+    }
+
+    /// The fonts in the order of their ids.
+    pub fn iter(&self) -> impl Iterator<Item = &F> {
+        self.fonts.iter().map(|entry| &entry.font)
+    }
+
+    fn entry(&self, id: FontId) -> Option<&Entry<F>> {
+        self.fonts.get(id.0 as usize)
+    }
+}
+
+impl<F: HasGlyph> FontRegistry<F> {
+    /// Splits `text` into runs of characters drawn with the same font: the first font of the
+    /// chain of `id` having a glyph for them, or the font of `id` when none has. Text of an id
+    /// not registered has no runs.
+    pub fn runs<'t>(&self, id: impl Into<FontId>, text: &'t str) -> FontRuns<'_, 't, F> {
+        let id = id.into();
+        FontRuns {
+            fonts: self,
+            id,
+            fallbacks: self.fallbacks(id),
+            text,
+        }
+    }
+
+    fn resolve(&self, id: FontId, fallbacks: &[FontId], character: char) -> Option<FontId> {
+        self.get(id)?;
+        let fallbacks = fallbacks.iter().copied();
+        let found = core::iter::once(id)
+            .chain(fallbacks)
+            .find(|&id| self.get(id).is_some_and(|font| font.has_glyph(character)));
+        Some(found.unwrap_or(id))
+    }
+}
+
+/// Iterator over the runs of text drawn with the same font, see [`FontRegistry::runs`].
+pub struct FontRuns<'r, 't, F> {
+    fonts: &'r FontRegistry<F>,
+    id: FontId,
+    fallbacks: &'r [FontId],
+    text: &'t str,
+}
+
+impl<'r, 't, F: HasGlyph> Iterator for FontRuns<'r, 't, F> {
+    type Item = (&'r F, &'t str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (fonts, id, fallbacks) = (self.fonts, self.id, self.fallbacks);
+        let mut characters = self.text.char_indices();
+        let (_, first) = characters.next()?;
+        let font = fonts.resolve(id, fallbacks, first)?;
+        let end = characters
+            .find(|&(_, character)| fonts.resolve(id, fallbacks, character) != Some(font))
+            .map_or(self.text.len(), |(index, _)| index);
+
+        let (run, rest) = self.text.split_at(end);
+        self.text = rest;
+        Some((fonts.get(font)?, run))
+    }
+}
+
+impl<F, S: Into<String>> FromIterator<(S, F)> for FontRegistry<F> {
+    fn from_iter<I: IntoIterator<Item = (S, F)>>(fonts: I) -> Self {
+        let mut registry = Self::new();
+        for (name, font) in fonts {
+            registry.add(name, font);
+        }
+        registry
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A font having the glyphs of the characters of a string.
+    #[derive(Debug, PartialEq)]
+    struct Charset(&'static str);
+
+    impl HasGlyph for Charset {
+        fn has_glyph(&self, character: char) -> bool {
+            self.0.contains(character)
+        }
+    }
+
+    #[test]
+    fn runs_follow_fallback_chains() {
+        let mut fonts = FontRegistry::new();
+        let latin = fonts.add("latin", Charset("abc "));
+        let greek = fonts.add("greek", Charset("αβγ "));
+        let symbols = fonts.add("symbols", Charset("→"));
+        fonts.set_fallbacks(latin, [FontId(7), greek, symbols]);
+
+        let runs: Vec<_> = fonts.runs(latin, "ab αβ → c?").collect();
+        assert_eq!(
+            runs,
+            [
+                (&Charset("abc "), "ab "),
+                (&Charset("αβγ "), "αβ"),
+                (&Charset("abc "), " "),
+                (&Charset("→"), "→"),
+                (&Charset("abc "), " c?"),
+            ]
+        );
+
+        assert!(fonts.runs(greek, "abc").eq([(&Charset("αβγ "), "abc")]));
+        assert_eq!(fonts.runs(FontId(7), "abc").count(), 0);
+        assert_eq!(fonts.runs(latin, "").count(), 0);
+
+        assert_eq!(fonts.add("greek", Charset("αβγδ ")), greek);
+        assert_eq!(fonts.len(), 3);
+        assert_eq!(fonts.fallbacks(latin), [FontId(7), greek, symbols]);
+    }
+}
@@ -0,0 +1,525 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    color::Color,
+    elements::{FloatingAttachPointType, FloatingAttachToElement, PointerCaptureMode},
+    id::Id,
+    layout::{Alignment, LayoutAlignmentX, LayoutAlignmentY, LayoutDirection, Padding, Sizing},
+    math::BoundingBox,
+    text::TextConfig,
+    theme::{TextStyle, Theme},
+    tree::{LayoutElement, LayoutTree},
+    ClayLayoutScope, Declaration,
+};
+
+const PANEL: &str = "ClayInspectorPanel";
+const TREE: &str = "ClayInspectorTree";
+const ROW: &str = "ClayInspectorRow";
+const HIGHLIGHT: &str = "ClayInspectorHighlight";
+
+/// Z index of the inspector, above the elements of the application.
+const Z_INDEX: i16 = 32000;
+
+/// Side of the window the inspector panel is docked to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InspectorDock {
+    Left,
+    #[default]
+    Right,
+}
+
+/// Debug overlay showing the element tree of the previous frame, the configuration and box
+/// model of the selected element, and a highlight over the hovered element.
+///
+/// Unlike the debug view of clay enabled with [`Clay::set_debug_mode`](crate::Clay::set_debug_mode),
+/// the inspector is made of regular elements and [`TextConfig`], so it uses the fonts and the
+/// theme of the application.
+/// ```
+/// use clay_layout::{inspector::Inspector, Clay};
+///
+/// let mut clay = Clay::new((800., 600.).into());
+/// let mut inspector = Inspector::new();
+/// inspector.handle_key("i");
+///
+/// let mut scope = clay.begin::<(), ()>();
+/// // ... application layout ...
+/// inspector.layout(&mut scope);
+/// let (tree, _commands) = scope.end_with_tree();
+/// inspector.update(tree);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Inspector {
+    enabled: bool,
+    toggle_key: String,
+    dock: InspectorDock,
+    width: f32,
+    highlight_color: Color,
+    text_style: Option<TextStyle>,
+    tree: LayoutTree,
+    /// Id of the selected element
+    selected: Option<u32>,
+    /// Id of the element whose row is hovered
+    hovered: Option<u32>,
+    /// Pointer state of the previous layout, used to detect clicks
+    pointer_was_down: bool,
+}
+
+impl Default for Inspector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Inspector {
+    /// Creates a disabled inspector docked to the right, toggled with the `i` key.
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            toggle_key: "i".to_string(),
+            dock: InspectorDock::default(),
+            width: 400.,
+            highlight_color: Color::rgba(168., 66., 28., 100.),
+            text_style: None,
+            tree: LayoutTree::default(),
+            selected: None,
+            hovered: None,
+            pointer_was_down: false,
+        }
+    }
+
+    /// Sets the key toggling the inspector in [`handle_key`](Self::handle_key).
+    pub fn toggle_key(&mut self, key: &str) -> &mut Self {
+        self.toggle_key = key.to_string();
+        self
+    }
+
+    /// Sets the side of the window the panel is docked to.
+    pub fn dock(&mut self, dock: InspectorDock) -> &mut Self {
+        self.dock = dock;
+        self
+    }
+
+    /// Sets the width of the panel.
+    pub fn width(&mut self, width: f32) -> &mut Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the color drawn over the hovered element.
+    pub fn highlight_color(&mut self, color: Color) -> &mut Self {
+        self.highlight_color = color;
+        self
+    }
+
+    /// Sets the text style of the panel, the caption style of the theme is used by default.
+    pub fn text_style(&mut self, style: TextStyle) -> &mut Self {
+        self.text_style = Some(style);
+        self
+    }
+
+    /// Returns if the inspector is shown.
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Shows or hides the inspector.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Handles a key press forwarded by the application, toggling the inspector if `key` is the
+    /// toggle key. Returns `true` if the key was consumed.
+    pub fn handle_key(&mut self, key: &str) -> bool {
+        if key == self.toggle_key {
+            self.enabled = !self.enabled;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the id of the selected element.
+    pub fn selected(&self) -> Option<u32> {
+        self.selected
+    }
+
+    /// Selects the element with the given id, or clears the selection with `None`.
+    pub fn select(&mut self, id: Option<Id>) {
+        self.selected = id.map(|id| id.id.id);
+    }
+
+    /// Stores the tree of the layout that just ended, shown during the next layout.
+    pub fn update(&mut self, tree: LayoutTree) {
+        self.tree = tree;
+    }
+
+    /// Adds the inspector to the layout. Should be called last, right before ending the layout.
+    pub fn layout<'render, ImageElementData: 'render, CustomElementData: 'render>(
+        &mut self,
+        clay: &mut ClayLayoutScope<'_, 'render, ImageElementData, CustomElementData>,
+    ) {
+        if !self.enabled {
+            self.pointer_was_down = clay.pointer_down();
+            return;
+        }
+
+        let rows = self.rows();
+        self.handle_pointer(clay, &rows);
+
+        let theme = *clay.theme();
+        let text_style = self.text_style.unwrap_or(theme.typography.caption);
+
+        if let Some(element) = self
+            .hovered
+            .or(self.selected)
+            .and_then(|id| self.element(id))
+        {
+            self.highlight(clay, element.bounding_box);
+        }
+
+        let attach = match self.dock {
+            InspectorDock::Left => FloatingAttachPointType::LeftTop,
+            InspectorDock::Right => FloatingAttachPointType::RightTop,
+        };
+
+        #[rustfmt::skip]
+        clay.with(Declaration::new()
+            .id(clay.id(PANEL))
+            .layout()
+                .width(Sizing::Fixed(self.width))
+                .height(Sizing::Grow(0., f32::MAX))
+                .direction(LayoutDirection::TopToBottom)
+                .padding(Padding::all(theme.spacing.sm))
+                .child_gap(theme.spacing.sm)
+                .end()
+            .floating()
+                .attach_to(FloatingAttachToElement::Root)
+                .attach_points(attach, attach)
+                .z_index(Z_INDEX)
+                .end()
+            .border()
+                .all_directions(1)
+                .color(theme.palette.border)
+                .end()
+            .background_color(theme.palette.overlay), |clay|
+        {
+            clay.with_styling(|clay| {
+                let mut declaration = Declaration::new();
+                declaration
+                    .id(clay.id(TREE))
+                    .layout()
+                        .width(Sizing::Grow(0., f32::MAX))
+                        .height(Sizing::Grow(0., f32::MAX))
+                        .direction(LayoutDirection::TopToBottom)
+                        .end()
+                    .clip(false, true, clay.scroll_offset())
+                    .background_color(theme.palette.surface);
+                declaration
+            }, |clay|
+            {
+                for (row, &(index, depth)) in rows.iter().enumerate() {
+                    self.tree_row(clay, &theme, &text_style, row, index, depth);
+                }
+            });
+
+            if let Some(element) = self.selected.and_then(|id| self.element(id)) {
+                self.details(clay, &theme, &text_style, element);
+            }
+        });
+    }
+
+    /// Depth first list of the elements of the application as (index, depth), skipping the
+    /// elements of the inspector.
+    fn rows(&self) -> Vec<(usize, u16)> {
+        let hidden = [Id::new(PANEL).id.id, Id::new(HIGHLIGHT).id.id];
+        let mut rows = Vec::new();
+        let mut stack: Vec<(usize, u16)> = self
+            .tree
+            .roots()
+            .iter()
+            .rev()
+            .map(|&root| (root, 0))
+            .collect();
+
+        while let Some((index, depth)) = stack.pop() {
+            let Some(element) = self.tree.get(index) else {
+                continue;
+            };
+            if hidden.contains(&element.id) {
+                continue;
+            }
+            rows.push((index, depth));
+            stack.extend(
+                element
+                    .children
+                    .iter()
+                    .rev()
+                    .map(|&child| (child, depth + 1)),
+            );
+        }
+
+        rows
+    }
+
+    fn handle_pointer<ImageElementData, CustomElementData>(
+        &mut self,
+        clay: &ClayLayoutScope<'_, '_, ImageElementData, CustomElementData>,
+        rows: &[(usize, u16)],
+    ) {
+        let clicked = clay.pointer_down() && !self.pointer_was_down;
+        self.pointer_was_down = clay.pointer_down();
+
+        self.hovered = (0..rows.len())
+            .find(|&row| clay.pointer_over(clay.id_index(ROW, row as u32)))
+            .and_then(|row| self.tree.get(rows[row].0))
+            .map(|element| element.id);
+
+        if clicked && self.hovered.is_some() {
+            self.selected = self.hovered;
+        }
+    }
+
+    fn element(&self, id: u32) -> Option<&LayoutElement> {
+        self.tree.elements().iter().find(|element| element.id == id)
+    }
+
+    fn highlight<'render, ImageElementData: 'render, CustomElementData: 'render>(
+        &self,
+        clay: &mut ClayLayoutScope<'_, 'render, ImageElementData, CustomElementData>,
+        bounding_box: BoundingBox,
+    ) {
+        #[rustfmt::skip]
+        clay.with(Declaration::new()
+            .id(clay.id(HIGHLIGHT))
+            .layout()
+                .width(Sizing::Fixed(bounding_box.width))
+                .height(Sizing::Fixed(bounding_box.height))
+                .end()
+            .floating()
+                .attach_to(FloatingAttachToElement::Root)
+                .offset((bounding_box.x, bounding_box.y).into())
+                .pointer_capture_mode(PointerCaptureMode::Passthrough)
+                .z_index(Z_INDEX - 1)
+                .end()
+            .background_color(self.highlight_color), |_| {});
+    }
+
+    fn tree_row<'render, ImageElementData: 'render, CustomElementData: 'render>(
+        &self,
+        clay: &mut ClayLayoutScope<'_, 'render, ImageElementData, CustomElementData>,
+        theme: &Theme,
+        text_style: &TextStyle,
+        row: usize,
+        index: usize,
+        depth: u16,
+    ) {
+        let Some(element) = self.tree.get(index) else {
+            return;
+        };
+
+        let background = if Some(element.id) == self.selected {
+            theme.palette.surface_variant
+        } else if Some(element.id) == self.hovered {
+            theme.palette.control
+        } else {
+            Color::rgba(0., 0., 0., 0.)
+        };
+        let text_color = if element.culled {
+            theme.palette.text_muted
+        } else {
+            theme.palette.text
+        };
+
+        #[rustfmt::skip]
+        clay.with(Declaration::new()
+            .id(clay.id_index(ROW, row as u32))
+            .layout()
+                .width(Sizing::Grow(0., f32::MAX))
+                .padding(Padding::new(
+                    theme.spacing.xs + depth * theme.spacing.md,
+                    theme.spacing.xs,
+                    theme.spacing.xs / 2,
+                    theme.spacing.xs / 2,
+                ))
+                .end()
+            .background_color(background), |clay|
+        {
+            clay.text_owned(element_name(element), TextConfig::new()
+                .style(text_style)
+                .color(text_color)
+                .end());
+        });
+    }
+
+    fn details<'render, ImageElementData: 'render, CustomElementData: 'render>(
+        &self,
+        clay: &mut ClayLayoutScope<'_, 'render, ImageElementData, CustomElementData>,
+        theme: &Theme,
+        text_style: &TextStyle,
+        element: &LayoutElement,
+    ) {
+        let text =
+            |clay: &mut ClayLayoutScope<'_, 'render, ImageElementData, CustomElementData>,
+             text: String| {
+                clay.text_owned(
+                    text,
+                    TextConfig::new()
+                        .style(text_style)
+                        .color(theme.palette.text)
+                        .end(),
+                );
+            };
+
+        let bounding_box = element.bounding_box;
+        let padding = element.padding;
+
+        #[rustfmt::skip]
+        clay.with(Declaration::new()
+            .layout()
+                .width(Sizing::Grow(0., f32::MAX))
+                .direction(LayoutDirection::TopToBottom)
+                .padding(Padding::all(theme.spacing.sm))
+                .child_gap(theme.spacing.xs)
+                .end()
+            .background_color(theme.palette.surface), |clay|
+        {
+            text(clay, element_name(element));
+
+            // Box model: the padding around the content box
+            clay.with(Declaration::new()
+                .layout()
+                    .width(Sizing::Grow(0., f32::MAX))
+                    .direction(LayoutDirection::TopToBottom)
+                    .child_alignment(Alignment::new(LayoutAlignmentX::Center, LayoutAlignmentY::Center))
+                    .padding(Padding::all(theme.spacing.xs))
+                    .end()
+                .border()
+                    .all_directions(1)
+                    .color(theme.palette.border)
+                    .end()
+                .background_color(theme.palette.surface_variant), |clay|
+            {
+                text(clay, format!("{}", padding.top));
+                clay.with(Declaration::new()
+                    .layout()
+                        .child_alignment(Alignment::new(LayoutAlignmentX::Center, LayoutAlignmentY::Center))
+                        .child_gap(theme.spacing.sm)
+                        .end(), |clay|
+                {
+                    text(clay, format!("{}", padding.left));
+                    clay.with(Declaration::new()
+                        .layout()
+                            .padding(Padding::all(theme.spacing.xs))
+                            .end()
+                        .background_color(theme.palette.control), |clay|
+                    {
+                        text(clay, format!("{} x {}", bounding_box.width, bounding_box.height));
+                    });
+                    text(clay, format!("{}", padding.right));
+                });
+                text(clay, format!("{}", padding.bottom));
+            });
+
+            text(clay, format!("position: {}, {}", bounding_box.x, bounding_box.y));
+            text(clay, format!("width: {:?}", element.width));
+            text(clay, format!("height: {:?}", element.height));
+            text(clay, format!("direction: {:?}", element.direction));
+            text(clay, format!("child gap: {}", element.child_gap));
+            text(clay, format!(
+                "child alignment: {:?}, {:?}",
+                element.child_alignment.x, element.child_alignment.y
+            ));
+            if let Some(clip) = element.clip {
+                text(clay, format!(
+                    "clip: horizontal {}, vertical {}, offset {}, {}",
+                    clip.horizontal, clip.vertical, clip.child_offset.x, clip.child_offset.y
+                ));
+            }
+            if let Some(floating) = &element.floating {
+                text(clay, format!(
+                    "floating: {:?}, offset {}, {}, z index {}",
+                    floating.attach_to, floating.offset.x, floating.offset.y, floating.z_index
+                ));
+            }
+            if element.culled {
+                text(clay, "culled".to_string());
+            }
+        });
+    }
+}
+
+/// Name of an element in the tree: its label, its text or its hashed id.
+fn element_name(element: &LayoutElement) -> String {
+    if let Some(label) = &element.label {
+        label.clone()
+    } else if let Some(text) = &element.text {
+        let preview: String = text.chars().take(24).collect();
+        if preview.len() < text.len() {
+            format!("\"{preview}...\"")
+        } else {
+            format!("\"{preview}\"")
+        }
+    } else {
+        format!("#{:08x}", element.id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{render_commands::RenderCommandConfig, testing::LayoutHarness, Clay, Vector2};
+
+    #[rustfmt::skip]
+    fn frame(clay: &mut Clay, inspector: &mut Inspector) -> Vec<String> {
+        let mut clay = clay.begin::<(), ()>();
+
+        clay.with(Declaration::new()
+            .id(clay.id("content"))
+            .layout()
+                .width(Sizing::Fixed(100.))
+                .height(Sizing::Fixed(100.))
+                .end(), |_| {});
+
+        inspector.layout(&mut clay);
+        let (tree, commands) = clay.end_with_tree();
+        let texts = commands
+            .filter_map(|command| match command.config {
+                RenderCommandConfig::Text(text) => Some(text.text.to_string()),
+                _ => None,
+            })
+            .collect();
+        inspector.update(tree);
+        texts
+    }
+
+    #[test]
+    fn toggle_and_select() {
+        let mut harness = LayoutHarness::new();
+        let clay = harness.clay();
+        let mut inspector = Inspector::new();
+
+        assert!(frame(clay, &mut inspector).is_empty());
+        assert!(!inspector.handle_key("t"));
+        assert!(inspector.handle_key("i"));
+        assert!(inspector.enabled());
+
+        let texts = frame(clay, &mut inspector);
+        assert!(texts.iter().any(|text| text == "content"));
+        assert!(!texts.iter().any(|text| text == PANEL));
+
+        // Click on the row of the content element, the second one after the root container
+        let row = clay.bounding_box(clay.id_index(ROW, 1)).unwrap();
+        clay.pointer_state(Vector2::new(row.x + 1., row.y + 1.), true);
+        frame(clay, &mut inspector);
+        assert_eq!(inspector.selected(), Some(clay.id("content").id.id));
+
+        clay.pointer_state(Vector2::new(0., 0.), false);
+        let texts = frame(clay, &mut inspector);
+        assert!(texts.iter().any(|text| text == "100 x 100"));
+        assert!(texts.iter().any(|text| text == "width: Fixed(100.0)"));
+    }
+}
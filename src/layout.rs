@@ -1,5 +1,6 @@
-use std::fmt::{Debug, Formatter};
-use std::os::raw::c_void;
+use alloc::{boxed::Box, format};
+use core::ffi::c_void;
+use core::fmt::{Debug, Formatter};
 use crate::{bindings::*, Declaration};
 
 /// Defines different sizing behaviors for an element.
@@ -33,7 +34,7 @@ pub enum Sizing<'render> {
 }
 
 impl Debug for Sizing<'_> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             Sizing::Constrained(_) => { f.write_str("Constrained") },
             Sizing::Fit(min,max) => { f.write_str(&format!("Fit({min},{max})")) },
@@ -107,7 +108,7 @@ where
 }
 
 /// Represents padding values for each side of an element.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct Padding {
     /// Padding on the left side.
     pub left: u16,
@@ -266,8 +267,8 @@ impl<'declaration, 'render, ImageElementData: 'render, CustomElementData: 'rende
     /// Returns the modified `Declaration`.
     #[inline]
     pub fn end(&mut self) -> &mut Declaration<'render, ImageElementData, CustomElementData> {
-        if self.parent.inner.layout.sizing.width.type_ == SizingType::Constrained as _ { panic!("Width sizing can not be constrained.") }
-        if self.parent.inner.layout.sizing.height.type_ == SizingType::Constrained as _ && self.parent.inner.layout.sizing.width.type_ == SizingType::Fit as _ { panic!("Constrained height sizing needs a fit width to work.") }
+        if self.parent.inner.layout.sizing.width.type_ == SizingType::Constrained as Clay__SizingType { panic!("Width sizing can not be constrained.") }
+        if self.parent.inner.layout.sizing.height.type_ == SizingType::Constrained as Clay__SizingType && self.parent.inner.layout.sizing.width.type_ == SizingType::Fit as Clay__SizingType { panic!("Constrained height sizing needs a fit width to work.") }
         self.parent
     }
 }
@@ -1,27 +1,52 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+pub mod batch;
 pub mod bindings;
+pub mod clip;
 pub mod color;
+pub mod diff;
 pub mod elements;
 pub mod errors;
+pub mod fonts;
 pub mod id;
+pub mod inspector;
 pub mod layout;
+pub mod lint;
 pub mod math;
+pub mod measure;
 pub mod render_commands;
+#[cfg(feature = "serde")]
+pub mod serialization;
+#[cfg(feature = "shaping")]
+pub mod shaping;
+#[cfg(feature = "std")]
+pub mod stream;
+pub mod style;
+#[cfg(any(feature = "std", feature = "libm"))]
+pub mod tessellate;
+#[cfg(feature = "std")]
+pub mod testing;
 pub mod text;
+pub mod theme;
+pub mod tree;
 
 mod mem;
 pub mod renderers;
 
+#[cfg(feature = "std")]
 use core::ffi::c_void;
 use core::marker::PhantomData;
-use std::fmt::{Debug, Formatter};
-use std::sync::{Arc};
+use alloc::{boxed::Box, string::String, sync::Arc, vec, vec::Vec};
+use core::fmt::{Debug, Formatter};
 pub use crate::bindings::*;
 use errors::Error;
 use id::Id;
 use math::{BoundingBox, Dimensions, Vector2};
 use render_commands::RenderCommand;
+use style::{InteractionState, StateStyle};
+use theme::Theme;
 
 pub use color::Color;
 
@@ -177,6 +202,18 @@ pub struct Clay {
     _memory: *const core::ffi::c_void,
     /// Stores the raw pointer to the callback data for later cleanup
     text_measure_callback: Option<&'static mut core::ffi::c_void>,
+    /// Design tokens used by the layouts built with this instance
+    theme: Theme,
+    /// Id of the element that has the focus, `0` if none
+    focused: u32,
+    /// Pointer pressed state given to the last call of `pointer_state`
+    pointer_down: core::cell::Cell<bool>,
+    /// Running transitions of the elements declared with a [`StateStyle`]
+    transitions: Vec<style::Transition>,
+    /// Hash of the render commands of the last layout
+    frame_hash: Option<u64>,
+    /// Whether the last layout rendered differently than the one before it
+    layout_changed: bool,
 }
 
 struct ClayLayoutScopeInternal<'clay> {
@@ -191,7 +228,7 @@ struct OwnedDataInner<'render>  {
 }
 
 impl Debug for OwnedDataInner<'_> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.write_str("OwnedDataInner")
     }
 }
@@ -344,15 +381,45 @@ impl<'render, 'clay, ImageElementData: 'render, CustomElementData: 'render>
     }
 
     pub fn end(
-        mut self,
+        self,
     ) -> impl Iterator<Item = RenderCommand<'render, ImageElementData, CustomElementData>> {
+        self.end_layout(|| ()).1
+    }
+
+    /// Ends the layout like [`end`](Self::end), also returning a snapshot of every element of the
+    /// layout.
+    pub fn end_with_tree(
+        self,
+    ) -> (
+        tree::LayoutTree,
+        impl Iterator<Item = RenderCommand<'render, ImageElementData, CustomElementData>>,
+    ) {
+        self.end_layout(|| unsafe { tree::LayoutTree::snapshot() })
+    }
+
+    /// Ends the layout and records its frame, calling `inspect` while the context of the layout
+    /// is current.
+    fn end_layout<T>(
+        mut self,
+        inspect: impl FnOnce() -> T,
+    ) -> (
+        T,
+        impl Iterator<Item = RenderCommand<'render, ImageElementData, CustomElementData>>,
+    ) {
         let array = unsafe { Clay_EndLayout() };
         self.inter.dropped = true;
+        let inspected = unsafe {
+            Clay_SetCurrentContext(self.inter.clay.context);
+            inspect()
+        };
         let slice = unsafe { core::slice::from_raw_parts(array.internalArray, array.length as _) };
+        let hash = unsafe { diff::hash_frame::<ImageElementData, CustomElementData>(slice) };
+        self.inter.clay.record_frame(hash);
         let owned = Arc::new(self.owned);
-        slice
-            .iter()
-            .map(move |command| unsafe { RenderCommand::from_clay_render_command(*command, owned.clone()) })
+        let commands = slice.iter().map(move |command| unsafe {
+            RenderCommand::from_clay_render_command(*command, owned.clone())
+        });
+        (inspected, commands)
     }
 
     /// Adds a text element to the current open element or to the root layout
@@ -373,6 +440,68 @@ impl<'render, 'clay, ImageElementData: 'render, CustomElementData: 'render>
         unsafe { Clay_Hovered() }
     }
 
+    /// Returns the interaction state of the current open element, based on the pointer state of
+    /// the previous frame. Elements are never reported as disabled.
+    pub fn interaction_state(&self) -> InteractionState {
+        let id = unsafe { Clay_CurrentElementId() };
+        let hovered = self.hovered();
+        InteractionState {
+            hovered,
+            active: hovered && self.pointer_down(),
+            focused: id != 0 && id == self.focused,
+            disabled: false,
+        }
+    }
+
+    /// Create an element styled according to its interaction state, passing a function to add
+    /// childrens. Replaces the manual `if clay.hovered() { ... }` checks in
+    /// [`with_styling`](Self::with_styling).
+    pub fn with_state_style<
+        F: FnOnce(&mut ClayLayoutScope<'clay, 'render, ImageElementData, CustomElementData>),
+    >(
+        &mut self,
+        style: &StateStyle<'render, ImageElementData, CustomElementData>,
+        disabled: bool,
+        f: F,
+    ) {
+        unsafe {
+            Clay_SetCurrentContext(self.inter.clay.context);
+            Clay__OpenElement();
+        }
+
+        let mut state = self.interaction_state();
+        state.disabled = disabled;
+        let declared_id = style.base.inner.id.id;
+        if declared_id != 0 {
+            state.focused = declared_id == self.focused;
+        }
+
+        let mut declaration = style.resolve(state);
+        if style.transition > 0. {
+            let id = if declared_id != 0 {
+                declared_id
+            } else {
+                unsafe { Clay_CurrentElementId() }
+            };
+            style::animate(
+                &mut self.inter.clay.transitions,
+                id,
+                style.transition,
+                &mut declaration,
+            );
+        }
+
+        unsafe {
+            Clay__ConfigureOpenElement(declaration.inner);
+        }
+
+        f(self);
+
+        unsafe {
+            Clay__CloseElement();
+        }
+    }
+
     pub fn scroll_offset(&self) -> Vector2 {
         unsafe { Clay_GetScrollOffset().into() }
     }
@@ -459,6 +588,12 @@ impl Clay {
             _memory: memory,
             context,
             text_measure_callback: None,
+            theme: Theme::default(),
+            focused: 0,
+            pointer_down: core::cell::Cell::new(false),
+            transitions: Vec::new(),
+            frame_hash: None,
+            layout_changed: true,
         }
     }
 
@@ -498,6 +633,11 @@ impl Clay {
         unsafe { Clay_PointerOver(cfg.id) }
     }
 
+    /// Creates a Clay instance using `memory` instead of allocating it.
+    ///
+    /// # Safety
+    /// `memory` must point to at least [`required_memory_size`](Self::required_memory_size)
+    /// writable bytes, valid for as long as the instance is used.
     #[cfg(not(feature = "std"))]
     pub unsafe fn new_with_memory(dimensions: Dimensions, memory: *mut core::ffi::c_void) -> Self {
         let memory_size = Self::required_memory_size();
@@ -514,8 +654,14 @@ impl Clay {
 
         Self {
             _memory: memory,
-            context,
+            context: &mut *context,
             text_measure_callback: None,
+            theme: Theme::default(),
+            focused: 0,
+            pointer_down: core::cell::Cell::new(false),
+            transitions: Vec::new(),
+            frame_hash: None,
+            layout_changed: true,
         }
     }
 
@@ -599,7 +745,14 @@ impl Clay {
     /// **Use only if you know what you are doing or your getting errors from clay**
     pub fn max_measure_text_cache_word_count(&self, count: u32) {
         unsafe {
-            Clay_SetMaxElementCount(count as _);
+            Clay_SetMaxMeasureTextCacheWordCount(count as _);
+        }
+    }
+
+    /// Forgets the words measured by clay, to measure them again after the fonts changed
+    pub fn reset_measure_text_cache(&self) {
+        unsafe {
+            Clay_ResetMeasureTextCache();
         }
     }
 
@@ -610,6 +763,30 @@ impl Clay {
         }
     }
 
+    /// Sets the width of the debug view of clay, in pixels
+    pub fn set_debug_view_width(&self, width: u32) {
+        unsafe {
+            Clay__debugViewWidth = width;
+        }
+    }
+
+    /// Sets the color drawn over the element hovered in the debug view of clay
+    pub fn set_debug_view_highlight_color(&self, color: Color) {
+        unsafe {
+            Clay__debugViewHighlightColor = color.into();
+        }
+    }
+
+    /// Returns the active theme
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Replaces the active theme, layouts built after this call use the new tokens
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
     /// Sets the dimensions of the global layout, use if, for example the window size you render to
     /// changed
     pub fn set_layout_dimensions(&self, dimensions: Dimensions) {
@@ -620,6 +797,7 @@ impl Clay {
     /// Updates the state of the pointer for clay. Used to update scroll containers and for
     /// interactions functions
     pub fn pointer_state(&self, position: Vector2, is_down: bool) {
+        self.pointer_down.set(is_down);
         unsafe {
             Clay_SetPointerState(position.into(), is_down);
         }
@@ -640,6 +818,50 @@ impl Clay {
         unsafe { Clay_Hovered() }
     }
 
+    /// Returns if the pointer is pressed, as set by the last call to
+    /// [`pointer_state`](Self::pointer_state)
+    pub fn pointer_down(&self) -> bool {
+        self.pointer_down.get()
+    }
+
+    /// Gives the focus to the element with the given id, or removes it with `None`
+    pub fn set_focus(&mut self, id: Option<Id>) {
+        self.focused = id.map_or(0, |id| id.id.id);
+    }
+
+    /// Returns if the element with the given id has the focus
+    pub fn is_focused(&self, id: Id) -> bool {
+        self.focused != 0 && self.focused == id.id.id
+    }
+
+    /// Advances the transitions of the elements declared with a [`StateStyle`]. Should be called
+    /// once per frame, before beginning the layout.
+    pub fn update_transitions(&mut self, delta_time: f32) {
+        style::update_transitions(&mut self.transitions, delta_time);
+    }
+
+    /// Checks a layout snapshot, taken with
+    /// [`ClayLayoutScope::end_with_tree`], for overflowing, collapsed, off-screen and detached
+    /// elements
+    pub fn lint(&self, tree: &tree::LayoutTree) -> Vec<lint::Lint> {
+        unsafe {
+            Clay_SetCurrentContext(self.context as *const _ as *mut _);
+            lint::lint(tree)
+        }
+    }
+
+    /// Returns whether the render commands of the last layout differ from the ones of the layout
+    /// before it, to skip redrawing identical frames. Always `true` after the first layout.
+    /// Use [`FrameDiff`](diff::FrameDiff) to find what changed.
+    pub fn layout_changed(&self) -> bool {
+        self.layout_changed
+    }
+
+    fn record_frame(&mut self, hash: u64) {
+        self.layout_changed = self.frame_hash != Some(hash);
+        self.frame_hash = Some(hash);
+    }
+
     fn element_data(id: Id) -> Clay_ElementData {
         unsafe { Clay_GetElementData(id.id) }
     }
@@ -730,35 +952,23 @@ mod tests {
     use super::*;
     use color::Color;
     use layout::{Padding, Sizing};
+    use testing::{monospace_measure, LayoutHarness};
 
     #[rustfmt::skip]
     #[test]
     fn test_begin() {
         let mut callback_data = 0u32;
 
-        let mut clay = Clay::new(Dimensions::new(800.0, 600.0));
+        let mut harness = LayoutHarness::new();
 
-        clay.set_measure_text_function_user_data(&mut callback_data, |text, _config, data| {
-            println!(
-                "set_measure_text_function_user_data {:?} count {:?}",
-                text, data
-            );
+        harness.clay().set_measure_text_function_user_data(&mut callback_data, |text, config, data| {
             **data += 1;
-            Dimensions::default()
+            monospace_measure(text, config)
         });
 
-        let mut clay = clay.begin::<(), ()>();
-
-        clay.with(&Declaration::new()
-            .id(clay.id("parent_rect"))
-            .layout()
-                .width(Sizing::Fixed(100.0))
-                .height(Sizing::Fixed(100.0))
-                .padding(Padding::all(10))
-                .end()
-            .background_color(Color::rgb(255., 255., 255.)), |clay|
-        {
+        harness.assert_snapshot("test_begin", Dimensions::new(800.0, 600.0), |clay| {
             clay.with(&Declaration::new()
+                .id(clay.id("parent_rect"))
                 .layout()
                     .width(Sizing::Fixed(100.0))
                     .height(Sizing::Fixed(100.0))
@@ -767,52 +977,54 @@ mod tests {
                 .background_color(Color::rgb(255., 255., 255.)), |clay|
             {
                 clay.with(&Declaration::new()
-                    .id(clay.id("rect_under_rect"))
                     .layout()
                         .width(Sizing::Fixed(100.0))
                         .height(Sizing::Fixed(100.0))
                         .padding(Padding::all(10))
                         .end()
                     .background_color(Color::rgb(255., 255., 255.)), |clay|
-                    {
-                        clay.text("test", TextConfig::new()
-                            .color(Color::rgb(255., 255., 255.))
-                            .font_size(24)
-                            .end());
-                    },
-                );
+                {
+                    clay.with(&Declaration::new()
+                        .id(clay.id("rect_under_rect"))
+                        .layout()
+                            .width(Sizing::Fixed(100.0))
+                            .height(Sizing::Fixed(100.0))
+                            .padding(Padding::all(10))
+                            .end()
+                        .background_color(Color::rgb(255., 255., 255.)), |clay|
+                        {
+                            clay.text("test", TextConfig::new()
+                                .color(Color::rgb(255., 255., 255.))
+                                .font_size(24)
+                                .end());
+                        },
+                    );
+                });
             });
-        });
 
-        clay.with(&Declaration::new()
-            .id(clay.id_index("border_container", 1))
-            .layout()
-                .padding(Padding::all(16))
-                .end()
-            .border()
-                .color(Color::rgb(255., 255., 0.))
-                .all_directions(2)
-                .end()
-            .corner_radius().all(10.0).end(), |clay|
-        {
             clay.with(&Declaration::new()
-                .id(clay.id("rect_under_border"))
+                .id(clay.id_index("border_container", 1))
                 .layout()
-                    .width(Sizing::Fixed(50.0))
-                    .height(Sizing::Fixed(50.0))
+                    .padding(Padding::all(16))
                     .end()
-                .background_color(Color::rgb(0., 255., 255.)), |_clay| {},
-            );
+                .border()
+                    .color(Color::rgb(255., 255., 0.))
+                    .all_directions(2)
+                    .end()
+                .corner_radius().all(10.0).end(), |clay|
+            {
+                clay.with(&Declaration::new()
+                    .id(clay.id("rect_under_border"))
+                    .layout()
+                        .width(Sizing::Fixed(50.0))
+                        .height(Sizing::Fixed(50.0))
+                        .end()
+                    .background_color(Color::rgb(0., 255., 255.)), |_clay| {},
+                );
+            });
         });
 
-        let items = clay.end();
-
-        for item in items {
-            println!(
-                "id: {}\nbbox: {:?}\nconfig: {:?}",
-                item.id, item.bounding_box, item.config,
-            );
-        }
+        assert!(callback_data > 0);
     }
 
     #[rustfmt::skip]
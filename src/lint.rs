@@ -0,0 +1,336 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::{Display, Formatter};
+
+use crate::{
+    bindings::*,
+    elements::FloatingAttachToElement,
+    math::Dimensions,
+    tree::{LayoutElement, LayoutTree},
+};
+
+/// Content may exceed the bounds by this amount before being reported, to absorb rounding.
+const OVERFLOW_TOLERANCE: f32 = 0.5;
+
+/// How likely a [`Lint`] is to be a bug in the layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Often intentional, such as elements scrolled out of view.
+    Info,
+    /// Usually a bug, such as text overflowing its container.
+    Warning,
+    /// The layout doesn't do what it declares.
+    Error,
+}
+
+/// The problem reported by a [`Lint`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LintKind {
+    /// The children of the element don't fit in its bounds on an axis that isn't clipped.
+    Overflow {
+        content: Dimensions,
+        bounds: Dimensions,
+    },
+    /// The element has children but a zero width or height, usually a collapsed `Grow`.
+    ZeroSize,
+    /// The element is entirely outside the layout dimensions.
+    OffScreen,
+    /// The floating element is attached to an element that wasn't declared during the frame.
+    MissingFloatingParent { parent_id: u32 },
+}
+
+/// A problem found in a layout by [`Clay::lint`](crate::Clay::lint).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lint {
+    pub kind: LintKind,
+    pub severity: Severity,
+    /// Index of the element in the [`LayoutTree`].
+    pub index: usize,
+    pub id: u32,
+    pub label: Option<String>,
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}: ", self.severity)?;
+        match &self.label {
+            Some(label) => write!(f, "{label}")?,
+            None => write!(f, "#{:08x}", self.id)?,
+        }
+        match self.kind {
+            LintKind::Overflow { content, bounds } => write!(
+                f,
+                " content {}x{} overflows bounds {}x{}",
+                content.width, content.height, bounds.width, bounds.height
+            ),
+            LintKind::ZeroSize => write!(f, " has children but a zero area"),
+            LintKind::OffScreen => write!(f, " is outside of the layout"),
+            LintKind::MissingFloatingParent { parent_id } => {
+                write!(f, " is attached to missing parent #{parent_id:08x}")
+            }
+        }
+    }
+}
+
+/// Runs every lint over `tree`.
+///
+/// # Safety
+/// The context of the layout `tree` was taken from must be the current context.
+pub(crate) unsafe fn lint(tree: &LayoutTree) -> Vec<Lint> {
+    let mut lints = Vec::new();
+
+    for (index, element) in tree.elements().iter().enumerate() {
+        let mut report = |kind, severity| {
+            lints.push(Lint {
+                kind,
+                severity,
+                index,
+                id: element.id,
+                label: element.label.clone(),
+            })
+        };
+
+        if let Some((content, bounds)) = overflow(tree, index, element) {
+            report(LintKind::Overflow { content, bounds }, Severity::Warning);
+        }
+
+        // Floating children are laid out on their own and can't collapse their parent
+        let has_children = tree.children(index).any(|child| child.floating.is_none());
+        let bounding_box = element.bounding_box;
+        if has_children && (bounding_box.width <= 0. || bounding_box.height <= 0.) {
+            report(LintKind::ZeroSize, Severity::Warning);
+        }
+
+        if element.culled {
+            report(LintKind::OffScreen, Severity::Info);
+        }
+
+        if let Some(floating) = &element.floating {
+            let attached_to_id =
+                matches!(floating.attach_to, FloatingAttachToElement::ElementWithId);
+            if attached_to_id && element.parent.is_none() {
+                report(
+                    LintKind::MissingFloatingParent {
+                        parent_id: floating.parent_id,
+                    },
+                    Severity::Error,
+                );
+            }
+        }
+    }
+
+    lints
+}
+
+/// Returns the content and bounds dimensions of `element` if its content overflows on an axis
+/// that isn't clipped.
+unsafe fn overflow(
+    tree: &LayoutTree,
+    index: usize,
+    element: &LayoutElement,
+) -> Option<(Dimensions, Dimensions)> {
+    let bounding_box = element.bounding_box;
+    let bounds = Dimensions::new(bounding_box.width, bounding_box.height);
+
+    let (content, clip_horizontal, clip_vertical) = match element.clip {
+        // Clip containers know their content size, which ignores the scroll offset
+        Some(clip) => {
+            let data = Clay_GetScrollContainerData(Clay_ElementId {
+                id: element.id,
+                offset: 0,
+                baseId: 0,
+                stringId: "".into(),
+            });
+            if !data.found {
+                return None;
+            }
+            (
+                data.contentDimensions.into(),
+                clip.horizontal,
+                clip.vertical,
+            )
+        }
+        None => {
+            let mut content = Dimensions::new(0., 0.);
+            for child in tree.children(index) {
+                if child.floating.is_some() {
+                    continue;
+                }
+                let child_box = child.bounding_box;
+                content.width = content.width.max(
+                    child_box.x + child_box.width - bounding_box.x + element.padding.right as f32,
+                );
+                content.height = content.height.max(
+                    child_box.y + child_box.height - bounding_box.y + element.padding.bottom as f32,
+                );
+            }
+            (content, false, false)
+        }
+    };
+
+    let overflows_horizontally =
+        !clip_horizontal && content.width > bounds.width + OVERFLOW_TOLERANCE;
+    let overflows_vertically =
+        !clip_vertical && content.height > bounds.height + OVERFLOW_TOLERANCE;
+    (overflows_horizontally || overflows_vertically).then_some((content, bounds))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        layout::{LayoutDirection, Sizing},
+        testing::LayoutHarness,
+        text::TextConfig,
+        Clay, ClayLayoutScope, Declaration,
+    };
+
+    fn run(clay: &mut Clay, f: impl FnOnce(&mut ClayLayoutScope<'_, '_, (), ()>)) -> Vec<Lint> {
+        let mut scope = clay.begin::<(), ()>();
+        f(&mut scope);
+        let (tree, _) = scope.end_with_tree();
+        clay.lint(&tree)
+    }
+
+    fn kinds(lints: &[Lint], label: &str) -> Vec<LintKind> {
+        lints
+            .iter()
+            .filter(|lint| lint.label.as_deref() == Some(label))
+            .map(|lint| lint.kind)
+            .collect()
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn text_overflow() {
+        let mut harness = LayoutHarness::new();
+        let clay = harness.clay();
+        let lints = run(clay, |clay| {
+            clay.with(Declaration::new()
+                .id(clay.id("fixed"))
+                .layout()
+                    .width(Sizing::Fixed(50.))
+                    .height(Sizing::Fixed(20.))
+                    .end(), |clay|
+            {
+                clay.text("overflowing", TextConfig::new().font_size(10).end());
+            });
+        });
+
+        assert_eq!(
+            kinds(&lints, "fixed"),
+            [LintKind::Overflow {
+                content: Dimensions::new(55., 10.),
+                bounds: Dimensions::new(50., 20.),
+            }]
+        );
+        assert_eq!(lints[0].severity, Severity::Warning);
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn clipped_overflow() {
+        let mut harness = LayoutHarness::new();
+        let clay = harness.clay();
+        let layout = |clay: &mut ClayLayoutScope<'_, '_, (), ()>| {
+            clay.with(Declaration::new()
+                .id(clay.id("scroll"))
+                .layout()
+                    .width(Sizing::Fixed(50.))
+                    .height(Sizing::Fixed(50.))
+                    .direction(LayoutDirection::TopToBottom)
+                    .end()
+                .clip(false, true, (0., 0.).into()), |clay|
+            {
+                clay.with(Declaration::new()
+                    .layout()
+                        .width(Sizing::Fixed(100.))
+                        .height(Sizing::Fixed(100.))
+                        .end(), |_| {});
+            });
+        };
+        // The scroll container data is created during the first frame
+        run(clay, layout);
+        let lints = run(clay, layout);
+
+        assert_eq!(
+            kinds(&lints, "scroll"),
+            [LintKind::Overflow {
+                content: Dimensions::new(100., 100.),
+                bounds: Dimensions::new(50., 50.),
+            }]
+        );
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn zero_size() {
+        let mut harness = LayoutHarness::new();
+        let clay = harness.clay();
+        let lints = run(clay, |clay| {
+            clay.with(Declaration::new()
+                .layout()
+                    .width(Sizing::Fixed(800.))
+                    .end(), |_| {});
+            clay.with(Declaration::new()
+                .id(clay.id("collapsed"))
+                .layout()
+                    .width(Sizing::Grow(0., f32::MAX))
+                    .end(), |clay|
+            {
+                clay.with(&Declaration::new(), |_| {});
+            });
+        });
+
+        assert_eq!(kinds(&lints, "collapsed"), [LintKind::ZeroSize]);
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn off_screen() {
+        let mut harness = LayoutHarness::new();
+        let clay = harness.clay();
+        let lints = run(clay, |clay| {
+            clay.with(Declaration::new()
+                .id(clay.id("outside"))
+                .layout()
+                    .width(Sizing::Fixed(10.))
+                    .height(Sizing::Fixed(10.))
+                    .end()
+                .floating()
+                    .attach_to(FloatingAttachToElement::Root)
+                    .offset((900., 0.).into())
+                    .end(), |_| {});
+        });
+
+        assert_eq!(kinds(&lints, "outside"), [LintKind::OffScreen]);
+        assert_eq!(lints[0].severity, Severity::Info);
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn missing_floating_parent() {
+        let mut harness = LayoutHarness::new();
+        let clay = harness.clay();
+        let layout = |declare_anchor: bool| move |clay: &mut ClayLayoutScope<'_, '_, (), ()>| {
+            if declare_anchor {
+                clay.with(Declaration::new().id(clay.id("anchor")), |_| {});
+            }
+            clay.with(Declaration::new()
+                .id(clay.id("tooltip"))
+                .floating()
+                    .attach_to(FloatingAttachToElement::ElementWithId)
+                    .parent_id(clay.id("anchor").id.id)
+                    .end(), |_| {});
+        };
+
+        assert!(run(clay, layout(true)).is_empty());
+        let lints = run(clay, layout(false));
+
+        assert_eq!(
+            kinds(&lints, "tooltip"),
+            [LintKind::MissingFloatingParent { parent_id: clay.id("anchor").id.id }]
+        );
+        assert_eq!(lints[0].severity, Severity::Error);
+        assert!(lints[0].to_string().starts_with("Error: tooltip"));
+    }
+}
@@ -2,6 +2,7 @@ use crate::bindings::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 #[repr(C)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector2 {
     pub x: f32,
     pub y: f32,
@@ -30,8 +31,33 @@ impl From<(f32, f32)> for Vector2 {
     }
 }
 
+impl core::ops::Add for Vector2 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl core::ops::Sub for Vector2 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl core::ops::Mul<f32> for Vector2 {
+    type Output = Self;
+
+    fn mul(self, factor: f32) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 #[repr(C)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Dimensions {
     pub width: f32,
     pub height: f32,
@@ -62,6 +88,7 @@ impl From<(f32, f32)> for Dimensions {
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 #[repr(C)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoundingBox {
     pub x: f32,
     pub y: f32,
@@ -78,6 +105,43 @@ impl BoundingBox {
             height,
         }
     }
+
+    /// Returns whether the box has no area.
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0. || self.height <= 0.
+    }
+
+    /// Returns whether the two boxes overlap or share an edge.
+    pub fn touches(&self, other: &Self) -> bool {
+        self.x <= other.x + other.width
+            && other.x <= self.x + self.width
+            && self.y <= other.y + other.height
+            && other.y <= self.y + self.height
+    }
+
+    /// Returns the area shared by both boxes, empty if they don't overlap.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        Self {
+            x,
+            y,
+            width: ((self.x + self.width).min(other.x + other.width) - x).max(0.),
+            height: ((self.y + self.height).min(other.y + other.height) - y).max(0.),
+        }
+    }
+
+    /// Returns the smallest box containing both boxes.
+    pub fn union(&self, other: &Self) -> Self {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Self {
+            x,
+            y,
+            width: (self.x + self.width).max(other.x + other.width) - x,
+            height: (self.y + self.height).max(other.y + other.height) - y,
+        }
+    }
 }
 
 impl From<Clay_BoundingBox> for BoundingBox {
@@ -0,0 +1,173 @@
+//! Text measurement and layout with [`ab_glyph`]. Renderers drawing the glyphs of
+//! [`layout_line`] draw text the size [`measure`] gives.
+
+use ab_glyph::{Font, FontArc, FontRef, FontVec, Glyph, GlyphId, PxScale, ScaleFont};
+use alloc::vec::Vec;
+
+use crate::fonts::{FontRegistry, HasGlyph};
+use crate::math::Dimensions;
+use crate::text::TextConfig;
+
+macro_rules! impl_has_glyph {
+    ($($font:ty),*) => {$(
+        impl HasGlyph for $font {
+            fn has_glyph(&self, character: char) -> bool {
+                self.glyph_id(character).0 != 0
+            }
+        }
+    )*};
+}
+
+impl_has_glyph!(FontArc, FontRef<'_>, FontVec);
+
+/// Scale of `font` for `font_size` pixels per em, the font size of the other font libraries.
+/// [`PxScale`] is the height from the descent to the ascent instead.
+pub fn px_scale(font: &impl Font, font_size: f32) -> PxScale {
+    match font.units_per_em() {
+        Some(units_per_em) => PxScale::from(font_size * font.height_unscaled() / units_per_em),
+        None => PxScale::from(font_size),
+    }
+}
+
+/// Calls `place` with each glyph of `text` and its font and horizontal position, the runs of
+/// [`FontRegistry::runs`] following each other and the glyphs of a run with their kerning, with
+/// `letter_spacing` after each glyph. Returns the width of the line.
+fn place<'f, F: Font + HasGlyph>(
+    fonts: &'f FontRegistry<F>,
+    text: &str,
+    config: &TextConfig,
+    mut place: impl FnMut(&'f F, PxScale, GlyphId, f32),
+) -> f32 {
+    let mut x = 0.;
+    for (font, run) in fonts.runs(config.font_id, text) {
+        let scale = px_scale(font, config.font_size as f32);
+        let scaled = font.as_scaled(scale);
+        let mut previous = None;
+        for character in run.chars() {
+            let id = scaled.glyph_id(character);
+            if let Some(previous) = previous {
+                x += scaled.kern(previous, id);
+            }
+            previous = Some(id);
+            place(font, scale, id, x);
+            x += scaled.h_advance(id) + config.letter_spacing as f32;
+        }
+    }
+    x
+}
+
+/// Line height of the font at the scale of `font`, at least the `line_height` of `config`.
+fn line_height<F: Font>(font: &impl ScaleFont<F>, config: &TextConfig) -> f32 {
+    (font.height() + font.line_gap()).max(config.line_height as f32)
+}
+
+/// Measures a line of text laid out by [`layout_line`]. Text of a font id missing from `fonts`
+/// measures nothing.
+pub fn measure<F: Font + HasGlyph>(
+    fonts: &FontRegistry<F>,
+    text: &str,
+    config: &TextConfig,
+) -> Dimensions {
+    let Some(font) = fonts.get(config.font_id) else {
+        return Dimensions::default();
+    };
+    let font = font.as_scaled(px_scale(font, config.font_size as f32));
+    let width = place(fonts, text, config, |_, _, _, _| {});
+    (width, line_height(&font, config)).into()
+}
+
+/// Glyphs of a line of text with its top left corner at `position`, with the fonts drawing them.
+/// The glyphs are on the baseline of the font of the text, centered in the line height.
+pub fn layout_line<'f, F: Font + HasGlyph>(
+    fonts: &'f FontRegistry<F>,
+    text: &str,
+    config: &TextConfig,
+    position: ab_glyph::Point,
+) -> Vec<(&'f F, Glyph)> {
+    let Some(font) = fonts.get(config.font_id) else {
+        return Vec::new();
+    };
+    let font = font.as_scaled(px_scale(font, config.font_size as f32));
+    let natural_height = font.height() + font.line_gap();
+    let baseline = position.y + (line_height(&font, config) - natural_height) / 2. + font.ascent();
+
+    let mut glyphs = Vec::new();
+    place(fonts, text, config, |font, scale, id, x| {
+        let position = ab_glyph::point(position.x + x, baseline);
+        glyphs.push((font, id.with_scale_and_position(scale, position)))
+    });
+    glyphs
+}
+
+/// Measure function for [`Clay::set_measure_text_function`](crate::Clay::set_measure_text_function),
+/// measuring text with the fonts the glyphs of [`layout_line`] are drawn with.
+pub fn ab_glyph_measure_text<F: Font + HasGlyph + 'static>(
+    fonts: impl AsRef<FontRegistry<F>> + 'static,
+) -> impl Fn(&str, &TextConfig) -> Dimensions + 'static {
+    move |text, config| measure(fonts.as_ref(), text, config)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ab_glyph::FontRef;
+
+    const TUFFY: &[u8] = include_bytes!("../../tests/fonts/Tuffy.ttf");
+
+    #[test]
+    fn layouts_fit_measurements() {
+        let font = FontRef::try_from_slice(TUFFY).unwrap();
+        let fonts: FontRegistry<_> = [("Tuffy", font.clone())].into_iter().collect();
+        let measure_text = ab_glyph_measure_text(fonts.clone());
+
+        for font_size in [12, 16, 32] {
+            for (letter_spacing, line_height) in [(0, 0), (3, 60)] {
+                let mut config = TextConfig::new();
+                config
+                    .font_size(font_size)
+                    .letter_spacing(letter_spacing)
+                    .line_height(line_height);
+
+                let text = "Hello, Wordly";
+                let size = measure_text(text, &config);
+                let (mut left, mut right) = (f32::MAX, f32::MIN);
+                let (mut top, mut bottom) = (f32::MAX, f32::MIN);
+                for (font, glyph) in layout_line(&fonts, text, &config, ab_glyph::point(0., 0.)) {
+                    let Some(outline) = font.outline_glyph(glyph) else {
+                        continue;
+                    };
+                    let bounds = outline.px_bounds();
+                    outline.draw(|x, y, coverage| {
+                        if coverage > 0. {
+                            left = left.min(bounds.min.x + x as f32);
+                            right = right.max(bounds.min.x + x as f32 + 1.);
+                            top = top.min(bounds.min.y + y as f32);
+                            bottom = bottom.max(bounds.min.y + y as f32 + 1.);
+                        }
+                    });
+                }
+
+                let context = format!("{font_size} {letter_spacing}: {size:?}");
+                assert!(left >= -1., "{context} {left}");
+                // The spacing after the last glyph and the side bearing of "y" are not inked
+                let trailing = size.width - letter_spacing as f32 - right;
+                assert!(
+                    (-1. ..font_size as f32 * 0.2).contains(&trailing),
+                    "{context} {right}"
+                );
+                assert!(
+                    top >= -1. && bottom <= size.height + 1.,
+                    "{context} {top} {bottom}"
+                );
+                // The glyphs are vertically centered in tall lines
+                if line_height > 0 {
+                    let (above, below) = (top, size.height - bottom);
+                    assert!(
+                        (above - below).abs() < font_size as f32,
+                        "{context} {top} {bottom}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! Memoization of a measure function, for measurers too slow to be called for every new string.
+//!
+//! Clay caches the words it measured itself, but calls the measure function again for every
+//! word it hasn't seen, and forgets them all when its cache is reset. A [`MeasureCache`] keeps
+//! the most recently used measurements of whole strings on the Rust side instead.
+
+use alloc::rc::Rc;
+use alloc::string::String;
+use core::cell::RefCell;
+use core::num::NonZeroUsize;
+
+use lru::LruCache;
+
+use crate::fonts::FontId;
+use crate::math::Dimensions;
+use crate::text::TextConfig;
+
+/// Text and the parts of its config changing its size.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Key {
+    text: String,
+    font_id: FontId,
+    font_size: u16,
+    letter_spacing: u16,
+    line_height: u16,
+}
+
+/// Counters of a [`MeasureCache`] since it was created or its counters were reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    /// Measurements found in the cache.
+    pub hits: u64,
+    /// Measurements made by the measure function.
+    pub misses: u64,
+    /// Least recently used measurements dropped to make room for new ones.
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    /// The share of the measurements found in the cache, `0` before any measurement.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+struct Inner {
+    entries: LruCache<Key, Dimensions>,
+    /// Key of the last lookup, reused to look up strings without allocating
+    lookup: Key,
+    stats: CacheStats,
+}
+
+/// Measure function remembering the size of the last `capacity` strings it measured. Clones share
+/// the same cache, so one can be given to [`Clay`](crate::Clay) with [`measure_function`](Self::measure_function)
+/// while another one invalidates it when the fonts change.
+/// ```
+/// use clay_layout::{measure::MeasureCache, text::TextConfig, Clay};
+///
+/// let cache = MeasureCache::new(1024, |text: &str, config: &TextConfig| {
+///     (text.len() as f32 * config.font_size as f32 / 2., config.font_size as f32).into()
+/// });
+/// let mut clay = Clay::new((800., 600.).into());
+/// clay.set_measure_text_function(cache.measure_function());
+///
+/// // After loading other fonts
+/// cache.invalidate();
+/// clay.reset_measure_text_cache();
+/// ```
+pub struct MeasureCache<F> {
+    measure: Rc<F>,
+    inner: Rc<RefCell<Inner>>,
+}
+
+impl<F> Clone for MeasureCache<F> {
+    fn clone(&self) -> Self {
+        Self {
+            measure: self.measure.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<F: Fn(&str, &TextConfig) -> Dimensions> MeasureCache<F> {
+    /// Creates a cache of `capacity` measurements, at least one, in front of `measure`.
+    pub fn new(capacity: usize, measure: F) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        let inner = Inner {
+            entries: LruCache::new(capacity),
+            lookup: Key {
+                text: String::new(),
+                font_id: FontId(0),
+                font_size: 0,
+                letter_spacing: 0,
+                line_height: 0,
+            },
+            stats: CacheStats::default(),
+        };
+        Self {
+            measure: Rc::new(measure),
+            inner: Rc::new(RefCell::new(inner)),
+        }
+    }
+
+    /// Measures text, with the measure function only if it isn't in the cache.
+    pub fn measure(&self, text: &str, config: &TextConfig) -> Dimensions {
+        let mut inner = self.inner.borrow_mut();
+        let Inner {
+            entries,
+            lookup,
+            stats,
+        } = &mut *inner;
+
+        lookup.text.clear();
+        lookup.text.push_str(text);
+        lookup.font_id = config.font_id;
+        lookup.font_size = config.font_size;
+        lookup.letter_spacing = config.letter_spacing;
+        lookup.line_height = config.line_height;
+        if let Some(dimensions) = entries.get(lookup) {
+            stats.hits += 1;
+            return *dimensions;
+        }
+
+        stats.misses += 1;
+        let key = lookup.clone();
+        // The measure function may use this cache too, it mustn't find it borrowed
+        drop(inner);
+        let dimensions = (self.measure)(text, config);
+
+        let mut inner = self.inner.borrow_mut();
+        // It may also have measured the same text, replacing it isn't an eviction
+        let replaced = inner.entries.contains(&key);
+        if inner.entries.push(key, dimensions).is_some() && !replaced {
+            inner.stats.evictions += 1;
+        }
+        dimensions
+    }
+
+    /// Measure function for [`Clay::set_measure_text_function`](crate::Clay::set_measure_text_function),
+    /// sharing the cache of `self`.
+    pub fn measure_function(&self) -> impl Fn(&str, &TextConfig) -> Dimensions + 'static
+    where
+        F: 'static,
+    {
+        let cache = self.clone();
+        move |text, config| cache.measure(text, config)
+    }
+}
+
+impl<F> MeasureCache<F> {
+    pub fn stats(&self) -> CacheStats {
+        self.inner.borrow().stats
+    }
+
+    pub fn reset_stats(&self) {
+        self.inner.borrow_mut().stats = CacheStats::default();
+    }
+
+    /// Number of measurements in the cache.
+    pub fn len(&self) -> usize {
+        self.inner.borrow().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.inner.borrow().entries.cap().get()
+    }
+
+    /// Changes the number of measurements kept, dropping the least recently used ones if there
+    /// are more.
+    pub fn set_capacity(&self, capacity: usize) {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        self.inner.borrow_mut().entries.resize(capacity);
+    }
+
+    /// Forgets every measurement, when the fonts change. Clay's own cache should be reset too,
+    /// with [`Clay::reset_measure_text_cache`](crate::Clay::reset_measure_text_cache).
+    pub fn invalidate(&self) {
+        self.inner.borrow_mut().entries.clear();
+    }
+
+    /// Forgets the measurements of the text of `font_id`, when that font changes.
+    pub fn invalidate_font(&self, font_id: impl Into<FontId>) {
+        let font_id = font_id.into();
+        let entries = &mut self.inner.borrow_mut().entries;
+        let stale: alloc::vec::Vec<Key> = entries
+            .iter()
+            .filter(|(key, _)| key.font_id == font_id)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in stale {
+            entries.pop(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::boxed::Box;
+    use core::cell::{Cell, OnceCell};
+
+    /// A cache and the number of calls made to the measure function behind it.
+    type Counted<F> = (MeasureCache<F>, Rc<Cell<u32>>);
+
+    /// A cache in front of a measure function counting its calls.
+    fn counting_cache(capacity: usize) -> Counted<impl Fn(&str, &TextConfig) -> Dimensions> {
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let cache = MeasureCache::new(capacity, move |text: &str, config: &TextConfig| {
+            counter.set(counter.get() + 1);
+            (
+                text.len() as f32 * config.font_size as f32,
+                config.line_height as f32,
+            )
+                .into()
+        });
+        (cache, calls)
+    }
+
+    #[test]
+    fn measurements_are_memoized() {
+        let (cache, calls) = counting_cache(2);
+        let mut config = TextConfig::new();
+        config.font_size(10);
+
+        assert_eq!(cache.measure("abc", &config), Dimensions::new(30., 0.));
+        assert_eq!(cache.measure("abc", &config), Dimensions::new(30., 0.));
+        assert_eq!(calls.get(), 1);
+
+        // Every field changing the size is part of the key
+        let mut other = config;
+        other.line_height(20);
+        assert_eq!(cache.measure("abc", &other), Dimensions::new(30., 20.));
+        assert_eq!(calls.get(), 2);
+        let mut colored = config;
+        colored.color(crate::Color::rgb(255., 0., 0.));
+        cache.measure("abc", &colored);
+        assert_eq!(calls.get(), 2);
+
+        // "abc" was used last, the other config is evicted
+        cache.measure("de", &config);
+        assert_eq!(calls.get(), 3);
+        cache.measure("abc", &config);
+        assert_eq!(calls.get(), 3);
+        cache.measure("abc", &other);
+        assert_eq!(calls.get(), 4);
+
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 3,
+                misses: 4,
+                evictions: 2
+            }
+        );
+        assert_eq!(cache.stats().hit_rate(), 3. / 7.);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn measure_function_can_use_the_cache() {
+        type Measure = Box<dyn Fn(&str, &TextConfig) -> Dimensions>;
+        let words: Rc<OnceCell<Measure>> = Rc::default();
+        let handle = words.clone();
+        let cache = MeasureCache::new(4, move |text: &str, config: &TextConfig| {
+            if !text.contains(' ') {
+                return (text.len() as f32 * config.font_size as f32, 0.).into();
+            }
+            // Lines are measured word by word, through the same cache
+            let measure = handle.get().unwrap();
+            text.split(' ')
+                .map(|word| measure(word, config))
+                .fold(Dimensions::new(0., 0.), |line, word| {
+                    Dimensions::new(line.width + word.width, 0.)
+                })
+        });
+        assert!(words.set(Box::new(cache.measure_function())).is_ok());
+        let mut config = TextConfig::new();
+        config.font_size(10);
+
+        assert_eq!(cache.measure("ab cd ab", &config), Dimensions::new(60., 0.));
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 1,
+                misses: 3,
+                evictions: 0
+            }
+        );
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn invalidation() {
+        let (cache, calls) = counting_cache(8);
+        let shared = cache.clone();
+        let measure = shared.measure_function();
+        let mut config = TextConfig::new();
+        config.font_size(10);
+        let mut heading = config;
+        heading.font_id(1);
+
+        measure("a", &config);
+        measure("a", &heading);
+        cache.invalidate_font(1);
+        assert_eq!(shared.len(), 1);
+        measure("a", &config);
+        measure("a", &heading);
+        assert_eq!(calls.get(), 3);
+
+        cache.invalidate();
+        assert!(shared.is_empty());
+        measure("a", &config);
+        assert_eq!(calls.get(), 4);
+
+        shared.set_capacity(0);
+        assert_eq!(cache.capacity(), 1);
+        cache.reset_stats();
+        assert_eq!(cache.stats(), CacheStats::default());
+        assert_eq!(cache.stats().hit_rate(), 0.);
+    }
+}
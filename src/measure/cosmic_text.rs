@@ -0,0 +1,188 @@
+//! Text measurement with [`cosmic_text`], matching the text drawn by the wgpu renderer.
+//!
+//! Cosmic text has no letter spacing, the `letter_spacing` of the text is ignored. It falls back
+//! to the fonts of the font system having the glyphs missing from a font by itself, the fallbacks
+//! of the [`FontRegistry`] are ignored.
+
+use cosmic_text::{Attrs, Buffer, Family, FamilyOwned, FontSystem, Metrics, Shaping};
+
+use crate::fonts::{FontId, FontRegistry};
+use crate::math::Dimensions;
+use crate::text::TextConfig;
+
+/// Measures text with the fonts of a [`FontSystem`], the font ids being mapped to font families.
+/// Renderers shape their text with [`CosmicTextMeasurer::shape`] to draw it the size it was
+/// measured.
+pub struct CosmicTextMeasurer {
+    font_system: FontSystem,
+    fonts: FontRegistry<FamilyOwned>,
+    buffer: Buffer,
+}
+
+impl CosmicTextMeasurer {
+    /// Creates a measurer with the fonts of `font_system`. The font ids missing from `fonts` are
+    /// sans serif.
+    pub fn new(mut font_system: FontSystem, fonts: FontRegistry<FamilyOwned>) -> Self {
+        let buffer = Buffer::new(&mut font_system, Metrics::new(16., 24.));
+        Self {
+            font_system,
+            fonts,
+            buffer,
+        }
+    }
+
+    pub fn font_system(&self) -> &FontSystem {
+        &self.font_system
+    }
+
+    /// The font system, to load more fonts.
+    pub fn font_system_mut(&mut self) -> &mut FontSystem {
+        &mut self.font_system
+    }
+
+    pub fn fonts(&self) -> &FontRegistry<FamilyOwned> {
+        &self.fonts
+    }
+
+    /// Shapes a line of text into `buffer` without wrapping it, in layout units.
+    pub fn shape(
+        &mut self,
+        buffer: &mut Buffer,
+        text: &str,
+        font_id: FontId,
+        font_size: u16,
+        line_height: u16,
+    ) {
+        shape(
+            &mut self.font_system,
+            &self.fonts,
+            buffer,
+            text,
+            font_id,
+            font_size,
+            line_height,
+        );
+    }
+
+    /// Measures a line of text: the width of its widest line, and the line height times the
+    /// number of lines.
+    pub fn measure(&mut self, text: &str, config: &TextConfig) -> Dimensions {
+        let buffer = &mut self.buffer;
+        shape(
+            &mut self.font_system,
+            &self.fonts,
+            buffer,
+            text,
+            config.font_id,
+            config.font_size,
+            config.line_height,
+        );
+
+        let (width, lines) = buffer.layout_runs().fold((0f32, 0), |(width, lines), run| {
+            (width.max(run.line_w), lines + 1)
+        });
+        (width, lines.max(1) as f32 * buffer.metrics().line_height).into()
+    }
+}
+
+/// Metrics of text in layout units, a line height of `0` is one and a half times the font size.
+pub fn metrics(font_size: u16, line_height: u16) -> Metrics {
+    let font_size = font_size as f32;
+    let line_height = match line_height {
+        0 => font_size * 1.5,
+        line_height => line_height as f32,
+    };
+    Metrics::new(font_size, line_height)
+}
+
+fn shape(
+    font_system: &mut FontSystem,
+    fonts: &FontRegistry<FamilyOwned>,
+    buffer: &mut Buffer,
+    text: &str,
+    font_id: FontId,
+    font_size: u16,
+    line_height: u16,
+) {
+    let family = fonts
+        .get(font_id)
+        .map_or(Family::SansSerif, FamilyOwned::as_family);
+    buffer.set_metrics_and_size(font_system, metrics(font_size, line_height), None, None);
+    buffer.set_text(
+        font_system,
+        text,
+        Attrs::new().family(family),
+        Shaping::Advanced,
+    );
+    buffer.shape_until_scroll(font_system, false);
+}
+
+/// Measure function for [`Clay::set_measure_text_function_user_data`](crate::Clay::set_measure_text_function_user_data),
+/// with the measurer as user data.
+pub fn cosmic_text_measure_text(
+    text: &str,
+    config: &TextConfig,
+    measurer: &mut CosmicTextMeasurer,
+) -> Dimensions {
+    measurer.measure(text, config)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmic_text::{fontdb, SwashCache};
+
+    const TUFFY: &[u8] = include_bytes!("../../tests/fonts/Tuffy.ttf");
+
+    #[test]
+    fn widths_cover_rasterized_glyphs() {
+        let mut db = fontdb::Database::new();
+        db.load_font_data(TUFFY.to_vec());
+        let font_system = FontSystem::new_with_locale_and_db("en-US".into(), db);
+        let fonts = [("Tuffy", FamilyOwned::Name("Tuffy".into()))]
+            .into_iter()
+            .collect();
+        let mut measurer = CosmicTextMeasurer::new(font_system, fonts);
+        let mut swash_cache = SwashCache::new();
+        let mut buffer = Buffer::new(measurer.font_system_mut(), Metrics::new(16., 24.));
+
+        for font_size in [12, 16, 32] {
+            for line_height in [0, 60] {
+                let mut config = TextConfig::new();
+                config.font_size(font_size).line_height(line_height);
+
+                let text = "Hello, World";
+                let size = measurer.measure(text, &config);
+                let expected_height = match line_height {
+                    0 => font_size as f32 * 1.5,
+                    line_height => line_height as f32,
+                };
+                assert_eq!(size.height, expected_height);
+
+                measurer.shape(&mut buffer, text, FontId(0), font_size, line_height);
+                let (mut left, mut right) = (i32::MAX, i32::MIN);
+                for run in buffer.layout_runs() {
+                    for glyph in run.glyphs {
+                        let physical = glyph.physical((0., 0.), 1.);
+                        let image = swash_cache
+                            .get_image_uncached(measurer.font_system_mut(), physical.cache_key)
+                            .unwrap();
+                        let placement = image.placement;
+                        if placement.width > 0 {
+                            left = left.min(physical.x + placement.left);
+                            right = right.max(physical.x + placement.left + placement.width as i32);
+                        }
+                    }
+                }
+
+                let context = format!("{font_size} {line_height}: {size:?}");
+                assert!(left >= -1, "{context} {left}");
+                let trailing = size.width - right as f32;
+                assert!(
+                    (-1. ..font_size as f32 * 0.2).contains(&trailing),
+                    "{context} {right}"
+                );
+            }
+        }
+    }
+}
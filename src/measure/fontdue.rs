@@ -0,0 +1,157 @@
+//! Text measurement with [`fontdue`], matching the text drawn by the tiny-skia renderer.
+
+use fontdue::Font;
+
+use crate::fonts::{FontRegistry, HasGlyph};
+use crate::math::Dimensions;
+use crate::text::TextConfig;
+
+impl HasGlyph for Font {
+    fn has_glyph(&self, character: char) -> bool {
+        self.lookup_glyph_index(character) != 0
+    }
+}
+
+/// Width of a run of text drawn with `font`: the advances of the glyphs with their kerning and
+/// `letter_spacing` after each glyph.
+pub fn run_width(font: &Font, run: &str, font_size: f32, letter_spacing: f32) -> f32 {
+    let mut width = 0.;
+    let mut previous = None;
+    for character in run.chars() {
+        if let Some(previous) = previous {
+            width += font
+                .horizontal_kern(previous, character, font_size)
+                .unwrap_or(0.);
+        }
+        previous = Some(character);
+        width += font.metrics(character, font_size).advance_width + letter_spacing;
+    }
+    width
+}
+
+/// Measures a line of text: the width of its runs of [`FontRegistry::runs`], and the line height
+/// of its font, at least the `line_height` of `config`. Text of a font id missing from `fonts`
+/// measures nothing.
+pub fn measure(fonts: &FontRegistry<Font>, text: &str, config: &TextConfig) -> Dimensions {
+    let Some(font) = fonts.get(config.font_id) else {
+        return Dimensions::default();
+    };
+    let font_size = config.font_size as f32;
+    let letter_spacing = config.letter_spacing as f32;
+
+    let width = fonts
+        .runs(config.font_id, text)
+        .map(|(font, run)| run_width(font, run, font_size, letter_spacing))
+        .sum();
+    let height = font
+        .horizontal_line_metrics(font_size)
+        .map_or(font_size, |metrics| metrics.new_line_size);
+    (width, height.max(config.line_height as f32)).into()
+}
+
+/// Measure function for [`Clay::set_measure_text_function`](crate::Clay::set_measure_text_function),
+/// measuring text with the fonts given to the tiny-skia renderer.
+pub fn fontdue_measure_text(
+    fonts: impl AsRef<FontRegistry<Font>> + 'static,
+) -> impl Fn(&str, &TextConfig) -> Dimensions + 'static {
+    move |text, config| measure(fonts.as_ref(), text, config)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fontdue::FontSettings;
+
+    const TUFFY: &[u8] = include_bytes!("../../tests/fonts/Tuffy.ttf");
+    const HEBREW: &[u8] = include_bytes!("../../tests/fonts/NotoSansHebrew.ttf");
+
+    /// Horizontal extent of the pixels covered by the glyphs, placed like the tiny-skia renderer
+    /// places them.
+    fn ink_extent(font: &Font, text: &str, config: &TextConfig) -> (f32, f32) {
+        let font_size = config.font_size as f32;
+        let (mut left, mut right) = (f32::MAX, f32::MIN);
+        let mut x = 0.;
+        let mut previous = None;
+        for character in text.chars() {
+            if let Some(previous) = previous {
+                x += font
+                    .horizontal_kern(previous, character, font_size)
+                    .unwrap_or(0.);
+            }
+            previous = Some(character);
+
+            let (glyph, coverage) = font.rasterize(character, font_size);
+            let columns = (0..glyph.width).filter(|column| {
+                (0..glyph.height).any(|row| coverage[row * glyph.width + column] > 0)
+            });
+            for column in columns {
+                let column = x + glyph.xmin as f32 + column as f32;
+                left = left.min(column);
+                right = right.max(column + 1.);
+            }
+            x += glyph.advance_width + config.letter_spacing as f32;
+        }
+        (left, right)
+    }
+
+    #[test]
+    fn widths_cover_rasterized_glyphs() {
+        let font = Font::from_bytes(TUFFY, FontSettings::default()).unwrap();
+        let fonts: FontRegistry<_> = [("Tuffy", font.clone())].into_iter().collect();
+        let measure_text = fontdue_measure_text(fonts);
+
+        for font_size in [12, 16, 32] {
+            for letter_spacing in [0, 3] {
+                let mut config = TextConfig::new();
+                config.font_size(font_size).letter_spacing(letter_spacing);
+
+                let text = "Hello, World";
+                let size = measure_text(text, &config);
+                let (left, right) = ink_extent(&font, text, &config);
+                assert!(left >= -1., "{font_size} {letter_spacing}: {left}");
+                // The spacing after the last glyph and the side bearing of "d" are not inked
+                let trailing = right - size.width + letter_spacing as f32;
+                assert!(
+                    (-1. ..font_size as f32 * 0.2).contains(&-trailing),
+                    "{font_size} {letter_spacing}: {right} {}",
+                    size.width
+                );
+            }
+        }
+
+        let mut config = TextConfig::new();
+        config.font_size(16);
+        let unspaced = measure_text("abc", &config);
+        config.letter_spacing(2).line_height(40);
+        let spaced = measure_text("abc", &config);
+        assert_eq!(spaced.width, unspaced.width + 6.);
+        assert_eq!(spaced.height, 40.);
+
+        config.font_id(1);
+        assert_eq!(measure_text("abc", &config), Dimensions::default());
+    }
+
+    #[test]
+    fn missing_glyphs_fall_back() {
+        let mut fonts = FontRegistry::new();
+        let latin = Font::from_bytes(TUFFY, FontSettings::default()).unwrap();
+        let hebrew = Font::from_bytes(HEBREW, FontSettings::default()).unwrap();
+        let tuffy = fonts.add("Tuffy", latin.clone());
+        let noto = fonts.add("Noto Sans Hebrew", hebrew.clone());
+
+        let mut config = TextConfig::new();
+        config.font_id(tuffy).font_size(16).letter_spacing(1);
+        let text = "abc שלום";
+        let missing = measure(&fonts, text, &config);
+
+        fonts.set_fallbacks(tuffy, [noto]);
+        let runs: Vec<_> = fonts.runs(tuffy, text).map(|(_, run)| run).collect();
+        assert_eq!(runs, ["abc ", "שלום"]);
+        let size = measure(&fonts, text, &config);
+        let expected = run_width(&latin, "abc ", 16., 1.) + run_width(&hebrew, "שלום", 16., 1.);
+        assert_eq!(size.width, expected);
+        assert_ne!(size.width, missing.width);
+        // The line height is the one of the font of the text
+        assert_eq!(size.height, missing.height);
+    }
+}
@@ -0,0 +1,27 @@
+//! Ready-made text measurers for [`Clay::set_measure_text_function`](crate::Clay::set_measure_text_function),
+//! each measuring text the way the renderers built on the same font library draw it.
+
+#[cfg(feature = "measure-cache")]
+pub mod cache;
+#[cfg(feature = "measure-cache")]
+pub use self::cache::{CacheStats, MeasureCache};
+
+#[cfg(feature = "fontdue-measure")]
+pub mod fontdue;
+#[cfg(feature = "fontdue-measure")]
+pub use self::fontdue::fontdue_measure_text;
+
+#[cfg(feature = "ab-glyph-measure")]
+pub mod ab_glyph;
+#[cfg(feature = "ab-glyph-measure")]
+pub use self::ab_glyph::ab_glyph_measure_text;
+
+#[cfg(feature = "shaping")]
+pub mod rustybuzz;
+#[cfg(feature = "shaping")]
+pub use self::rustybuzz::rustybuzz_measure_text;
+
+#[cfg(feature = "cosmic-text-measure")]
+pub mod cosmic_text;
+#[cfg(feature = "cosmic-text-measure")]
+pub use self::cosmic_text::{cosmic_text_measure_text, CosmicTextMeasurer};
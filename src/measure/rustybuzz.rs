@@ -0,0 +1,64 @@
+//! Text measurement with [`rustybuzz`], measuring the lines of [`shape_line`] so complex scripts
+//! and bidirectional text are the size they are drawn.
+
+use rustybuzz::Face;
+
+use crate::fonts::FontRegistry;
+use crate::math::Dimensions;
+use crate::shaping::shape_line;
+use crate::text::TextConfig;
+
+/// Measures a shaped line of text. Text of a font id missing from `fonts` measures nothing.
+pub fn measure<'f>(
+    fonts: &'f FontRegistry<Face<'f>>,
+    text: &str,
+    config: &TextConfig,
+) -> Dimensions {
+    if fonts.get(config.font_id).is_none() {
+        return Dimensions::default();
+    }
+    let line = shape_line(fonts, text, config);
+    (line.width, line.height).into()
+}
+
+/// Measure function for [`Clay::set_measure_text_function`](crate::Clay::set_measure_text_function),
+/// measuring text with the fonts its shaped lines are drawn with. The font data must outlive the
+/// function, such as fonts included in the binary.
+pub fn rustybuzz_measure_text(
+    fonts: impl AsRef<FontRegistry<Face<'static>>> + 'static,
+) -> impl Fn(&str, &TextConfig) -> Dimensions + 'static {
+    move |text, config| measure(fonts.as_ref(), text, config)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TUFFY: &[u8] = include_bytes!("../../tests/fonts/Tuffy.ttf");
+    const HEBREW: &[u8] = include_bytes!("../../tests/fonts/NotoSansHebrew.ttf");
+
+    #[test]
+    fn words_measure_like_their_line() {
+        let mut fonts = FontRegistry::new();
+        let tuffy = fonts.add("Tuffy", Face::from_slice(TUFFY, 0).unwrap());
+        let hebrew = fonts.add("Noto Sans Hebrew", Face::from_slice(HEBREW, 0).unwrap());
+        fonts.set_fallbacks(tuffy, [hebrew]);
+        let line_fonts = fonts.clone();
+        let measure_text = rustybuzz_measure_text(fonts);
+
+        let mut config = TextConfig::new();
+        config.font_size(20);
+        // Clay measures the words and spaces of a line one by one
+        let text = "abc שלום def";
+        let words: f32 = ["abc", " ", "שלום", " ", "def"]
+            .iter()
+            .map(|word| measure_text(word, &config).width)
+            .sum();
+        let line = shape_line(&line_fonts, text, &config);
+        assert!((words - line.width).abs() < 0.01, "{words} {}", line.width);
+        assert_eq!(measure_text(text, &config).height, line.height);
+
+        config.font_id(2);
+        assert_eq!(measure_text(text, &config), Dimensions::default());
+    }
+}
@@ -1,7 +1,17 @@
-use crate::{bindings::*, color::Color, math::BoundingBox, OwnedData};
+use alloc::vec::Vec;
+
+use crate::{
+    bindings::*,
+    clip::{Clip, ClipStack},
+    color::Color,
+    fonts::FontId,
+    math::BoundingBox,
+    OwnedData,
+};
 
 /// Represents a rectangle with a specified color and corner radii.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rectangle {
     /// The fill color of the rectangle.
     pub color: Color,
@@ -10,14 +20,18 @@ pub struct Rectangle {
 }
 
 /// Represents a text element with styling attributes.
-#[derive(Debug, Clone)]
+///
+/// Text borrows its content, so it is only serialized. Serialized text is deserialized as a
+/// `serialization::FrameText`, which owns its content.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Text<'a> {
     /// The text content.
     pub text: &'a str,
     /// The color of the text.
     pub color: Color,
     /// The ID of the font used.
-    pub font_id: u16,
+    pub font_id: FontId,
     /// The font size.
     pub font_size: u16,
     /// The spacing between letters.
@@ -27,7 +41,8 @@ pub struct Text<'a> {
 }
 
 /// Defines individual corner radii for an element.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CornerRadii {
     /// The radius for the top-left corner.
     pub top_left: f32,
@@ -40,7 +55,8 @@ pub struct CornerRadii {
 }
 
 /// Defines the border width for each side of an element.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BorderWidth {
     /// Border width on the left side.
     pub left: u16,
@@ -55,7 +71,8 @@ pub struct BorderWidth {
 }
 
 /// Represents a border with a specified color, width, and corner radii.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Border {
     /// The border color.
     pub color: Color,
@@ -66,7 +83,7 @@ pub struct Border {
 }
 
 /// Represents an image with defined dimensions and data.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Image<'a, ImageElementData> {
     /// Background color
     pub background_color: Color,
@@ -77,7 +94,7 @@ pub struct Image<'a, ImageElementData> {
 }
 
 /// Represents a custom element with a background color, corner radii, and associated data.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Custom<'a, CustomElementData> {
     /// The background color of the custom element.
     pub background_color: Color,
@@ -88,7 +105,8 @@ pub struct Custom<'a, CustomElementData> {
 }
 
 /// Represents a clip element with corner radii, clipping axis and position.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Scissor {
     pub horizontal: bool,
     pub vertical: bool,
@@ -117,7 +135,7 @@ impl From<Clay_TextRenderData> for Text<'_> {
         Self {
             text,
             color: value.textColor.into(),
-            font_id: value.fontId,
+            font_id: FontId(value.fontId),
             font_size: value.fontSize,
             letter_spacing: value.letterSpacing,
             line_height: value.lineHeight,
@@ -173,6 +191,27 @@ impl<CustomElementData> Custom<'_, CustomElementData> {
     }
 }
 
+// Image and custom data are only referenced, so the commands can be cloned whatever the data is.
+impl<ImageElementData> Clone for Image<'_, ImageElementData> {
+    fn clone(&self) -> Self {
+        Self {
+            background_color: self.background_color,
+            corner_radii: self.corner_radii.clone(),
+            data: self.data,
+        }
+    }
+}
+
+impl<CustomElementData> Clone for Custom<'_, CustomElementData> {
+    fn clone(&self) -> Self {
+        Self {
+            background_color: self.background_color,
+            corner_radii: self.corner_radii.clone(),
+            data: self.data,
+        }
+    }
+}
+
 impl Scissor {
     pub(crate) unsafe fn from_clay_scissor_element_data(value: Clay_ScrollRenderData) -> Self {
         Self {
@@ -183,7 +222,7 @@ impl Scissor {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub enum RenderCommandConfig<'a, ImageElementData, CustomElementData> {
     None(),
     Rectangle(Rectangle),
@@ -226,8 +265,25 @@ impl<ImageElementData, CustomElementData>
     }
 }
 
+impl<ImageElementData, CustomElementData> Clone
+    for RenderCommandConfig<'_, ImageElementData, CustomElementData>
+{
+    fn clone(&self) -> Self {
+        match self {
+            Self::None() => Self::None(),
+            Self::Rectangle(rectangle) => Self::Rectangle(rectangle.clone()),
+            Self::Border(border) => Self::Border(border.clone()),
+            Self::Text(text) => Self::Text(text.clone()),
+            Self::Image(image) => Self::Image(image.clone()),
+            Self::ScissorStart(scissor) => Self::ScissorStart(scissor.clone()),
+            Self::ScissorEnd() => Self::ScissorEnd(),
+            Self::Custom(custom) => Self::Custom(custom.clone()),
+        }
+    }
+}
+
 /// Represents a render command for drawing an element on the screen.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct RenderCommand<'a, ImageElementData, CustomElementData> {
     /// The bounding box defining the area occupied by the element.
     pub bounding_box: BoundingBox,
@@ -243,8 +299,29 @@ pub struct RenderCommand<'a, ImageElementData, CustomElementData> {
     _owned_strings: OwnedData<'a>,
 }
 
-impl<'render,ImageElementData, CustomElementData> RenderCommand<'render, ImageElementData, CustomElementData> {
-    pub(crate) unsafe fn from_clay_render_command(value: Clay_RenderCommand, _owned_strings: OwnedData<'render>) -> Self {
+impl<'render, ImageElementData, CustomElementData>
+    RenderCommand<'render, ImageElementData, CustomElementData>
+{
+    #[allow(dead_code, clippy::arc_with_non_send_sync)]
+    pub(crate) fn new(
+        bounding_box: BoundingBox,
+        config: RenderCommandConfig<'render, ImageElementData, CustomElementData>,
+        id: u32,
+        z_index: i16,
+    ) -> Self {
+        Self {
+            bounding_box,
+            config,
+            id,
+            z_index,
+            _owned_strings: alloc::sync::Arc::new(crate::OwnedDataInner::new()),
+        }
+    }
+
+    pub(crate) unsafe fn from_clay_render_command(
+        value: Clay_RenderCommand,
+        _owned_strings: OwnedData<'render>,
+    ) -> Self {
         Self {
             id: value.id,
             z_index: value.zIndex,
@@ -254,3 +331,242 @@ impl<'render,ImageElementData, CustomElementData> RenderCommand<'render, ImageEl
         }
     }
 }
+
+impl<ImageElementData, CustomElementData> Clone
+    for RenderCommand<'_, ImageElementData, CustomElementData>
+{
+    fn clone(&self) -> Self {
+        Self {
+            bounding_box: self.bounding_box,
+            config: self.config.clone(),
+            id: self.id,
+            z_index: self.z_index,
+            _owned_strings: self._owned_strings.clone(),
+        }
+    }
+}
+
+/// Drawing backend the render commands are dispatched to by [`Renderer::render`], with one
+/// method per kind of command.
+///
+/// Clip regions are nested: [`push_clip`](Renderer::push_clip) receives the bounds of the clip
+/// element and its [`Clip`] resolved by a [`ClipStack`], and [`pop_clip`](Renderer::pop_clip)
+/// the clip that applies again, if any. Backends with a clip stack of their own, such as
+/// canvases with `save`/`restore`, can ignore them.
+pub trait Renderer<'a, ImageElementData: 'a, CustomElementData: 'a> {
+    fn rectangle(&mut self, bounds: BoundingBox, rectangle: &Rectangle);
+    fn border(&mut self, bounds: BoundingBox, border: &Border);
+    fn text(&mut self, bounds: BoundingBox, text: &Text<'a>);
+    fn image(&mut self, bounds: BoundingBox, image: &Image<'a, ImageElementData>);
+    fn push_clip(&mut self, bounds: BoundingBox, scissor: &Scissor, clip: &Clip);
+    fn pop_clip(&mut self, clip: Option<&Clip>);
+    fn custom(
+        &mut self,
+        command: &RenderCommand<'a, ImageElementData, CustomElementData>,
+        custom: &Custom<'a, CustomElementData>,
+    );
+
+    /// Draws the commands in order of z-index, keeping the order of the commands with the same
+    /// z-index. Unbalanced scissor ends are ignored and clips left open are popped at the end.
+    fn render(
+        &mut self,
+        render_commands: impl IntoIterator<
+            Item = RenderCommand<'a, ImageElementData, CustomElementData>,
+        >,
+    ) where
+        Self: Sized,
+    {
+        let mut commands: Vec<_> = render_commands.into_iter().collect();
+        // Clay already sorts its commands, command lists merged by hand may not be
+        if commands
+            .windows(2)
+            .any(|pair| pair[0].z_index > pair[1].z_index)
+        {
+            commands.sort_by_key(|command| command.z_index);
+        }
+
+        let mut clips = ClipStack::new();
+        for command in &commands {
+            let bounds = command.bounding_box;
+            match &command.config {
+                RenderCommandConfig::Rectangle(rectangle) => self.rectangle(bounds, rectangle),
+                RenderCommandConfig::Border(border) => self.border(bounds, border),
+                RenderCommandConfig::Text(text) => self.text(bounds, text),
+                RenderCommandConfig::Image(image) => self.image(bounds, image),
+                RenderCommandConfig::ScissorStart(scissor) => {
+                    let clip = clips.push(bounds, scissor);
+                    self.push_clip(bounds, scissor, clip);
+                }
+                RenderCommandConfig::ScissorEnd() => {
+                    if clips.pop().is_some() {
+                        self.pop_clip(clips.current());
+                    }
+                }
+                RenderCommandConfig::Custom(custom) => self.custom(command, custom),
+                RenderCommandConfig::None() => {}
+            }
+        }
+        while clips.pop().is_some() {
+            self.pop_clip(clips.current());
+        }
+    }
+}
+
+/// Converts the image and custom data referenced by render commands to handles, used to
+/// serialize or send render commands.
+pub trait HandleMapper<ImageElementData, CustomElementData> {
+    fn image_handle(&mut self, data: &ImageElementData) -> u64;
+    fn custom_handle(&mut self, data: &CustomElementData) -> u64;
+}
+
+/// Converts handles created by a [`HandleMapper`] back to the image and custom data.
+pub trait HandleResolver<'a, ImageElementData, CustomElementData> {
+    fn image(&self, handle: u64) -> Option<&'a ImageElementData>;
+    fn custom(&self, handle: u64) -> Option<&'a CustomElementData>;
+}
+
+/// Handles for layouts without image and custom data, every handle is `0`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoHandles;
+
+impl HandleMapper<(), ()> for NoHandles {
+    fn image_handle(&mut self, _data: &()) -> u64 {
+        0
+    }
+
+    fn custom_handle(&mut self, _data: &()) -> u64 {
+        0
+    }
+}
+
+impl<'a> HandleResolver<'a, (), ()> for NoHandles {
+    fn image(&self, _handle: u64) -> Option<&'a ()> {
+        Some(&())
+    }
+
+    fn custom(&self, _handle: u64) -> Option<&'a ()> {
+        Some(&())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Records the calls made by [`Renderer::render`].
+    #[derive(Default)]
+    struct Recorder {
+        calls: Vec<String>,
+    }
+
+    impl<'a> Renderer<'a, (), ()> for Recorder {
+        fn rectangle(&mut self, bounds: BoundingBox, _rectangle: &Rectangle) {
+            self.calls.push(format!("rectangle {}", bounds.x));
+        }
+
+        fn border(&mut self, bounds: BoundingBox, _border: &Border) {
+            self.calls.push(format!("border {}", bounds.x));
+        }
+
+        fn text(&mut self, _bounds: BoundingBox, text: &Text<'a>) {
+            self.calls.push(format!("text {}", text.text));
+        }
+
+        fn image(&mut self, bounds: BoundingBox, _image: &Image<'a, ()>) {
+            self.calls.push(format!("image {}", bounds.x));
+        }
+
+        fn push_clip(&mut self, _bounds: BoundingBox, _scissor: &Scissor, clip: &Clip) {
+            self.calls.push(format!("push {:?}", clip.bounds));
+        }
+
+        fn pop_clip(&mut self, clip: Option<&Clip>) {
+            self.calls
+                .push(format!("pop {:?}", clip.map(|clip| clip.bounds)));
+        }
+
+        fn custom(&mut self, command: &RenderCommand<'a, (), ()>, _custom: &Custom<'a, ()>) {
+            self.calls
+                .push(format!("custom {}", command.bounding_box.x));
+        }
+    }
+
+    fn radii() -> CornerRadii {
+        CornerRadii {
+            top_left: 0.,
+            top_right: 0.,
+            bottom_left: 0.,
+            bottom_right: 0.,
+        }
+    }
+
+    fn rectangle(x: f32, z_index: i16) -> RenderCommand<'static, (), ()> {
+        let config = RenderCommandConfig::Rectangle(Rectangle {
+            color: Color::rgb(255., 0., 0.),
+            corner_radii: radii(),
+        });
+        RenderCommand::new(BoundingBox::new(x, 0., 10., 10.), config, 0, z_index)
+    }
+
+    fn scissor_start(bounds: BoundingBox) -> RenderCommand<'static, (), ()> {
+        let config = RenderCommandConfig::ScissorStart(Scissor {
+            horizontal: true,
+            vertical: true,
+            corner_radii: radii(),
+        });
+        RenderCommand::new(bounds, config, 0, 0)
+    }
+
+    fn scissor_end() -> RenderCommand<'static, (), ()> {
+        RenderCommand::new(
+            BoundingBox::default(),
+            RenderCommandConfig::ScissorEnd(),
+            0,
+            0,
+        )
+    }
+
+    #[test]
+    fn render_sorts_by_z_index() {
+        let mut recorder = Recorder::default();
+        recorder.render([
+            rectangle(1., 2),
+            rectangle(2., 0),
+            rectangle(3., 2),
+            rectangle(4., 1),
+        ]);
+        assert_eq!(
+            recorder.calls,
+            ["rectangle 2", "rectangle 4", "rectangle 1", "rectangle 3"]
+        );
+    }
+
+    #[test]
+    fn render_intersects_nested_clips() {
+        let outer = BoundingBox::new(0., 0., 100., 100.);
+        let inner = BoundingBox::new(50., 50., 100., 100.);
+        let mut recorder = Recorder::default();
+        recorder.render([
+            scissor_start(outer),
+            scissor_start(inner),
+            rectangle(60., 0),
+            scissor_end(),
+            scissor_end(),
+            scissor_end(),
+            scissor_start(inner),
+        ]);
+        let shared = BoundingBox::new(50., 50., 50., 50.);
+        assert_eq!(
+            recorder.calls,
+            [
+                format!("push {outer:?}"),
+                format!("push {shared:?}"),
+                "rectangle 60".to_string(),
+                format!("pop {:?}", Some(outer)),
+                "pop None".to_string(),
+                format!("push {inner:?}"),
+                "pop None".to_string(),
+            ]
+        );
+    }
+}
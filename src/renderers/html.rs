@@ -0,0 +1,288 @@
+use std::fmt::Write;
+
+use crate::clip::Clip;
+use crate::fonts::FontRegistry;
+use crate::math::{BoundingBox, Dimensions};
+use crate::render_commands::{
+    Border, CornerRadii, Custom, Image, Rectangle, RenderCommand, Renderer, Scissor, Text,
+};
+use crate::Color;
+
+use super::{escape_xml, font_families};
+
+fn css_color(color: Color) -> String {
+    format!(
+        "rgba({},{},{},{})",
+        color.r.round(),
+        color.g.round(),
+        color.b.round(),
+        (color.a / 255.).clamp(0., 1.)
+    )
+}
+
+/// Formats the `border-radius` property, empty if every corner is square.
+fn border_radius(radii: &CornerRadii) -> String {
+    if radii.top_left <= 0.
+        && radii.top_right <= 0.
+        && radii.bottom_left <= 0.
+        && radii.bottom_right <= 0.
+    {
+        return String::new();
+    }
+    format!(
+        "border-radius:{}px {}px {}px {}px;",
+        radii.top_left, radii.top_right, radii.bottom_right, radii.bottom_left
+    )
+}
+
+/// Formats the position of `bounds` relative to the `origin` of the enclosing clip region.
+fn position(bounds: BoundingBox, origin: (f32, f32)) -> String {
+    format!(
+        "position:absolute;left:{}px;top:{}px;width:{}px;height:{}px;",
+        bounds.x - origin.0,
+        bounds.y - origin.1,
+        bounds.width,
+        bounds.height
+    )
+}
+
+struct HtmlRenderer<'f, F, G> {
+    output: String,
+    fonts: &'f FontRegistry<&'f str>,
+    image_src: F,
+    render_custom_element: G,
+    /// Children of a clip region are positioned relative to it
+    origins: Vec<(f32, f32)>,
+}
+
+impl<F, G> HtmlRenderer<'_, F, G> {
+    fn origin(&self) -> (f32, f32) {
+        self.origins.last().copied().unwrap_or((0., 0.))
+    }
+}
+
+impl<'a, ImageElementData: 'a, CustomElementData: 'a, F, G>
+    Renderer<'a, ImageElementData, CustomElementData> for HtmlRenderer<'_, F, G>
+where
+    F: FnMut(&ImageElementData) -> String,
+    G: FnMut(
+        &RenderCommand<'a, ImageElementData, CustomElementData>,
+        &Custom<'a, CustomElementData>,
+    ) -> String,
+{
+    fn rectangle(&mut self, bounds: BoundingBox, rect: &Rectangle) {
+        writeln!(
+            self.output,
+            r#"<div style="{}background-color:{};{}"></div>"#,
+            position(bounds, self.origin()),
+            css_color(rect.color),
+            border_radius(&rect.corner_radii)
+        )
+        .unwrap();
+    }
+
+    fn border(&mut self, bounds: BoundingBox, border: &Border) {
+        let width = &border.width;
+        writeln!(
+            self.output,
+            r#"<div style="{}box-sizing:border-box;border-style:solid;border-color:{};border-width:{}px {}px {}px {}px;{}"></div>"#,
+            position(bounds, self.origin()),
+            css_color(border.color),
+            width.top,
+            width.right,
+            width.bottom,
+            width.left,
+            border_radius(&border.corner_radii)
+        )
+        .unwrap();
+    }
+
+    fn text(&mut self, bounds: BoundingBox, text: &Text<'a>) {
+        let mut style = position(bounds, self.origin());
+        if let Some(font) = font_families(self.fonts, text.font_id) {
+            write!(style, "font-family:{font};").unwrap();
+        }
+        let line_height = if text.line_height > 0 {
+            text.line_height as f32
+        } else {
+            bounds.height
+        };
+        write!(
+            style,
+            "font-size:{}px;line-height:{}px;color:{};white-space:pre;",
+            text.font_size,
+            line_height,
+            css_color(text.color)
+        )
+        .unwrap();
+        if text.letter_spacing > 0 {
+            write!(style, "letter-spacing:{}px;", text.letter_spacing).unwrap();
+        }
+        writeln!(
+            self.output,
+            r#"<span style="{}">{}</span>"#,
+            escape_xml(&style),
+            escape_xml(text.text)
+        )
+        .unwrap();
+    }
+
+    fn image(&mut self, bounds: BoundingBox, image: &Image<'a, ImageElementData>) {
+        writeln!(
+            self.output,
+            r#"<img src="{}" alt="" style="{}object-fit:fill;{}">"#,
+            escape_xml(&(self.image_src)(image.data)),
+            position(bounds, self.origin()),
+            border_radius(&image.corner_radii)
+        )
+        .unwrap();
+    }
+
+    fn push_clip(&mut self, bounds: BoundingBox, scissor: &Scissor, _clip: &Clip) {
+        let overflow = match (scissor.horizontal, scissor.vertical) {
+            (true, false) => "overflow-x:hidden;",
+            (false, true) => "overflow-y:hidden;",
+            _ => "overflow:hidden;",
+        };
+        writeln!(
+            self.output,
+            r#"<div style="{}{overflow}{}">"#,
+            position(bounds, self.origin()),
+            border_radius(&scissor.corner_radii)
+        )
+        .unwrap();
+        self.origins.push((bounds.x, bounds.y));
+    }
+
+    fn pop_clip(&mut self, _clip: Option<&Clip>) {
+        self.origins.pop();
+        writeln!(self.output, "</div>").unwrap();
+    }
+
+    fn custom(
+        &mut self,
+        command: &RenderCommand<'a, ImageElementData, CustomElementData>,
+        custom: &Custom<'a, CustomElementData>,
+    ) {
+        writeln!(
+            self.output,
+            r#"<div style="{}background-color:{};{}">{}</div>"#,
+            position(command.bounding_box, self.origin()),
+            css_color(custom.background_color),
+            border_radius(&custom.corner_radii),
+            (self.render_custom_element)(command, custom)
+        )
+        .unwrap();
+    }
+}
+
+/// Converts render commands to HTML, positioning every command with an absolutely positioned
+/// `<div>` inside a container of the given dimensions.
+///
+/// `fonts` gives the CSS font family of each font id, followed by the families of its fallbacks,
+/// and `image_src` the URL or data URI of the image elements. The HTML returned by
+/// `render_custom_element` is inserted in the `<div>` of the custom elements.
+pub fn clay_html_render<'a, ImageElementData: 'a, CustomElementData: 'a>(
+    dimensions: Dimensions,
+    render_commands: impl Iterator<Item = RenderCommand<'a, ImageElementData, CustomElementData>>,
+    fonts: &FontRegistry<&str>,
+    image_src: impl FnMut(&ImageElementData) -> String,
+    render_custom_element: impl FnMut(
+        &RenderCommand<'a, ImageElementData, CustomElementData>,
+        &Custom<'a, CustomElementData>,
+    ) -> String,
+) -> String {
+    let mut renderer = HtmlRenderer {
+        output: String::new(),
+        fonts,
+        image_src,
+        render_custom_element,
+        origins: Vec::new(),
+    };
+    writeln!(
+        renderer.output,
+        r#"<div style="position:relative;width:{}px;height:{}px;overflow:hidden;">"#,
+        dimensions.width, dimensions.height
+    )
+    .unwrap();
+    renderer.render(render_commands);
+    writeln!(renderer.output, "</div>").unwrap();
+    renderer.output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::{examples, LayoutHarness};
+    use crate::{layout::Sizing, Declaration};
+
+    fn render(
+        harness: &mut LayoutHarness,
+        layout: impl FnOnce(&mut crate::ClayLayoutScope<'_, '_, (), ()>),
+    ) -> String {
+        let mut fonts = FontRegistry::new();
+        let inter = fonts.add("body", "\"Inter\"");
+        let sans_serif = fonts.add("sans-serif", "sans-serif");
+        fonts.set_fallbacks(inter, [sans_serif]);
+
+        let clay = harness.clay();
+        let mut scope = clay.begin::<(), ()>();
+        layout(&mut scope);
+        let html = clay_html_render(
+            (800., 600.).into(),
+            scope.end(),
+            &fonts,
+            |_| String::new(),
+            |_, _| String::new(),
+        );
+        assert_eq!(html.matches("<div").count(), html.matches("</div>").count());
+        html
+    }
+
+    #[test]
+    fn example_layouts() {
+        let mut harness = LayoutHarness::new();
+
+        let html = render(&mut harness, examples::basic_rectangle);
+        harness.assert_golden("basic_rectangle.html", &html);
+
+        let html = render(&mut harness, examples::dashboard);
+        harness.assert_golden("dashboard.html", &html);
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn custom_elements_and_escaping() {
+        let mut harness = LayoutHarness::new();
+        let clay = harness.clay();
+        let mut scope = clay.begin::<&str, &str>();
+        scope.with(Declaration::new()
+            .layout()
+                .width(Sizing::Fixed(10.))
+                .height(Sizing::Fixed(10.))
+                .end()
+            .custom_element(&"chart"), |_| {});
+        scope.with(Declaration::new()
+            .layout()
+                .width(Sizing::Fixed(10.))
+                .height(Sizing::Fixed(10.))
+                .end()
+            .image()
+                .data(&"a.png?x=1&y=2")
+                .end(), |_| {});
+        scope.text("<script>alert('x')</script>", crate::text::TextConfig::new().font_size(10).end());
+
+        let html = clay_html_render(
+            (100., 100.).into(),
+            scope.end(),
+            &FontRegistry::new(),
+            |src| src.to_string(),
+            |_, custom| format!("<canvas data-kind=\"{}\"></canvas>", custom.data),
+        );
+
+        assert!(html.contains("<canvas data-kind=\"chart\"></canvas></div>"));
+        assert!(html.contains("src=\"a.png?x=1&amp;y=2\""));
+        assert!(html.contains(">&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt;</span>"));
+        assert!(!html.contains("<script>"));
+    }
+}
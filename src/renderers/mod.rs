@@ -7,3 +7,160 @@ pub use raylib::clay_raylib_render;
 pub mod skia;
 #[cfg(feature = "skia-renderer")]
 pub use skia::clay_skia_render;
+
+#[cfg(feature = "tiny-skia-renderer")]
+pub mod tiny_skia;
+#[cfg(feature = "tiny-skia-renderer")]
+pub use self::tiny_skia::clay_tiny_skia_render;
+
+#[cfg(feature = "svg-renderer")]
+pub mod svg;
+#[cfg(feature = "svg-renderer")]
+pub use svg::clay_svg_render;
+
+#[cfg(feature = "html-renderer")]
+pub mod html;
+#[cfg(feature = "html-renderer")]
+pub use html::clay_html_render;
+
+#[cfg(feature = "tui-renderer")]
+pub mod tui;
+#[cfg(feature = "tui-renderer")]
+pub use tui::clay_tui_render;
+
+#[cfg(feature = "pdf-renderer")]
+pub mod pdf;
+#[cfg(feature = "pdf-renderer")]
+pub use pdf::clay_pdf_render;
+
+#[cfg(feature = "wgpu-renderer")]
+pub mod wgpu;
+#[cfg(feature = "wgpu-renderer")]
+pub use self::wgpu::WgpuRenderer;
+
+/// Font families of the font of `font_id` followed by its fallbacks, separated by commas like in
+/// CSS, or `None` when the font isn't registered.
+#[cfg(any(feature = "svg-renderer", feature = "html-renderer"))]
+pub(crate) fn font_families(
+    fonts: &crate::fonts::FontRegistry<&str>,
+    font_id: crate::fonts::FontId,
+) -> Option<String> {
+    fonts.get(font_id)?;
+    Some(fonts.chain(font_id).copied().collect::<Vec<_>>().join(", "))
+}
+
+/// Escapes the characters of `text` that can't appear as is in XML text and attributes.
+#[cfg(any(feature = "svg-renderer", feature = "html-renderer"))]
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Control point distance of the cubic approximating a quarter circle of radius 1.
+#[cfg(any(feature = "tiny-skia-renderer", feature = "pdf-renderer"))]
+const KAPPA: f32 = 0.552_284_8;
+
+/// Path of a renderer drawing with straight lines and cubic curves, built by [`rounded_rect`].
+#[cfg(any(feature = "tiny-skia-renderer", feature = "pdf-renderer"))]
+pub(crate) trait PathSink {
+    fn move_to(&mut self, x: f32, y: f32);
+    fn line_to(&mut self, x: f32, y: f32);
+    fn cubic_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32);
+    fn close(&mut self);
+}
+
+/// Appends a rectangle with rounded corners to `path`, the radii are clamped to half the size of
+/// the rectangle.
+#[cfg(any(feature = "tiny-skia-renderer", feature = "pdf-renderer"))]
+pub(crate) fn rounded_rect(
+    path: &mut impl PathSink,
+    rect: crate::math::BoundingBox,
+    radii: &crate::render_commands::CornerRadii,
+) {
+    let max = (rect.width.min(rect.height) / 2.).max(0.);
+    let [top_left, top_right, bottom_right, bottom_left] = [
+        radii.top_left,
+        radii.top_right,
+        radii.bottom_right,
+        radii.bottom_left,
+    ]
+    .map(|radius| radius.clamp(0., max));
+    let (left, top) = (rect.x, rect.y);
+    let (right, bottom) = (rect.x + rect.width, rect.y + rect.height);
+
+    path.move_to(left + top_left, top);
+    path.line_to(right - top_right, top);
+    path.cubic_to(
+        right - top_right * (1. - KAPPA),
+        top,
+        right,
+        top + top_right * (1. - KAPPA),
+        right,
+        top + top_right,
+    );
+    path.line_to(right, bottom - bottom_right);
+    path.cubic_to(
+        right,
+        bottom - bottom_right * (1. - KAPPA),
+        right - bottom_right * (1. - KAPPA),
+        bottom,
+        right - bottom_right,
+        bottom,
+    );
+    path.line_to(left + bottom_left, bottom);
+    path.cubic_to(
+        left + bottom_left * (1. - KAPPA),
+        bottom,
+        left,
+        bottom - bottom_left * (1. - KAPPA),
+        left,
+        bottom - bottom_left,
+    );
+    path.line_to(left, top + top_left);
+    path.cubic_to(
+        left,
+        top + top_left * (1. - KAPPA),
+        left + top_left * (1. - KAPPA),
+        top,
+        left + top_left,
+        top,
+    );
+    path.close();
+}
+
+/// Bounds and corner radii of the inside of a border drawn around `bounds`. The inner corners
+/// follow the outer ones, reduced by the width of the sides.
+#[cfg(any(feature = "tiny-skia-renderer", feature = "pdf-renderer"))]
+pub(crate) fn border_inner_rect(
+    bounds: crate::math::BoundingBox,
+    border: &crate::render_commands::Border,
+) -> (
+    crate::math::BoundingBox,
+    crate::render_commands::CornerRadii,
+) {
+    let width = &border.width;
+    let radii = &border.corner_radii;
+    let inner = crate::math::BoundingBox::new(
+        bounds.x + width.left as f32,
+        bounds.y + width.top as f32,
+        bounds.width - width.left as f32 - width.right as f32,
+        bounds.height - width.top as f32 - width.bottom as f32,
+    );
+    let inner_radii = crate::render_commands::CornerRadii {
+        top_left: (radii.top_left - width.left.max(width.top) as f32).max(0.),
+        top_right: (radii.top_right - width.right.max(width.top) as f32).max(0.),
+        bottom_left: (radii.bottom_left - width.left.max(width.bottom) as f32).max(0.),
+        bottom_right: (radii.bottom_right - width.right.max(width.bottom) as f32).max(0.),
+    };
+    (inner, inner_radii)
+}
@@ -0,0 +1,667 @@
+use pdf_writer::{Content, Name, Pdf, Rect, Ref, Str};
+
+use crate::math::{BoundingBox, Dimensions, Vector2};
+use crate::render_commands::{RenderCommand, RenderCommandConfig, Text};
+use crate::renderers::{border_inner_rect, rounded_rect, PathSink};
+use crate::text::TextConfig;
+use crate::tree::LayoutTree;
+use crate::{Clay, ClayLayoutScope, Color};
+
+/// Line height of the text measured by [`create_measure_text_function`], relative to the font
+/// size.
+const LINE_HEIGHT: f32 = 1.2;
+
+/// Ascender and descender of Helvetica, relative to the font size.
+const ASCENT: f32 = 0.718;
+const DESCENT: f32 = 0.207;
+
+/// Widths of the printable ASCII characters in Helvetica, in thousandths of the font size.
+#[rustfmt::skip]
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Height used to lay out the content of a [`Pagination`], large enough to never cull it.
+const MAX_CONTENT_HEIGHT: f32 = 1_000_000.;
+
+fn helvetica_width(character: char) -> f32 {
+    let index = (character as usize).wrapping_sub(32);
+    HELVETICA_WIDTHS.get(index).copied().unwrap_or(556) as f32 / 1000.
+}
+
+/// Encodes text with the WinAnsi encoding of the standard fonts. Characters outside of Latin-1
+/// are replaced with `?`.
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|character| match character as u32 {
+            code @ (0x20..=0x7e | 0xa0..=0xff) => code as u8,
+            _ => b'?',
+        })
+        .collect()
+}
+
+fn set_fill_color(content: &mut Content, color: Color) {
+    content.set_fill_rgb(color.r / 255., color.g / 255., color.b / 255.);
+}
+
+impl PathSink for Content {
+    fn move_to(&mut self, x: f32, y: f32) {
+        Content::move_to(self, x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        Content::line_to(self, x, y);
+    }
+
+    fn cubic_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        Content::cubic_to(self, x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.close_path();
+    }
+}
+
+/// Returns whether the two boxes share some area, unlike [`BoundingBox::touches`] boxes that
+/// only share an edge don't overlap.
+fn overlaps(a: &BoundingBox, b: &BoundingBox) -> bool {
+    a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+}
+
+/// Page of a [`PdfDocument`]. Its content uses the coordinates of the layout: the origin is the
+/// top left corner of the page and y grows downwards.
+pub struct PdfPage {
+    content: Content,
+    font_count: usize,
+}
+
+impl PdfPage {
+    fn new(page_size: Dimensions, font_count: usize) -> Self {
+        let mut content = Content::new();
+        content.transform([1., 0., 0., -1., 0., page_size.height]);
+        Self {
+            content,
+            font_count,
+        }
+    }
+
+    /// Draws the commands on the page.
+    pub fn render<'a, ImageElementData: 'a, CustomElementData: 'a>(
+        &mut self,
+        render_commands: impl Iterator<Item = RenderCommand<'a, ImageElementData, CustomElementData>>,
+    ) {
+        for command in render_commands {
+            self.draw(&command, Vector2::new(0., 0.));
+        }
+    }
+
+    /// Draws the part of the layout inside `source` with its top left corner at `position`.
+    /// Commands outside of `source` are skipped, the others are clipped to it.
+    pub fn render_region<'a, ImageElementData: 'a, CustomElementData: 'a>(
+        &mut self,
+        render_commands: impl Iterator<Item = RenderCommand<'a, ImageElementData, CustomElementData>>,
+        source: BoundingBox,
+        position: Vector2,
+    ) {
+        let offset = self.begin_region(source, position);
+        for command in render_commands {
+            self.draw_in_region(&command, &source, offset);
+        }
+        self.content.restore_state();
+    }
+
+    /// Clips the page to the region drawn at `position`, returning the offset of its commands.
+    fn begin_region(&mut self, source: BoundingBox, position: Vector2) -> Vector2 {
+        self.content.save_state();
+        self.content
+            .rect(position.x, position.y, source.width, source.height)
+            .clip_nonzero()
+            .end_path();
+        Vector2::new(position.x - source.x, position.y - source.y)
+    }
+
+    fn draw_in_region<ImageElementData, CustomElementData>(
+        &mut self,
+        command: &RenderCommand<'_, ImageElementData, CustomElementData>,
+        source: &BoundingBox,
+        offset: Vector2,
+    ) {
+        let scissor = matches!(
+            command.config,
+            RenderCommandConfig::ScissorStart(_) | RenderCommandConfig::ScissorEnd()
+        );
+        // Scissors are kept to balance the graphics state
+        if scissor || overlaps(&command.bounding_box, source) {
+            self.draw(command, offset);
+        }
+    }
+
+    fn draw<ImageElementData, CustomElementData>(
+        &mut self,
+        command: &RenderCommand<'_, ImageElementData, CustomElementData>,
+        offset: Vector2,
+    ) {
+        let bounds = BoundingBox::new(
+            command.bounding_box.x + offset.x,
+            command.bounding_box.y + offset.y,
+            command.bounding_box.width,
+            command.bounding_box.height,
+        );
+        let content = &mut self.content;
+
+        match &command.config {
+            RenderCommandConfig::Rectangle(rect) => {
+                if rect.color.a > 0. {
+                    set_fill_color(content, rect.color);
+                    rounded_rect(content, bounds, &rect.corner_radii);
+                    content.fill_nonzero();
+                }
+            }
+
+            RenderCommandConfig::Border(border) => {
+                let (inner, inner_radii) = border_inner_rect(bounds, border);
+                if bounds.is_empty() || border.color.a <= 0. {
+                    return;
+                }
+                set_fill_color(content, border.color);
+                rounded_rect(content, bounds, &border.corner_radii);
+                if !inner.is_empty() {
+                    rounded_rect(content, inner, &inner_radii);
+                }
+                content.fill_even_odd();
+            }
+
+            RenderCommandConfig::Text(text) => self.draw_text(bounds, text),
+
+            RenderCommandConfig::Image(image) => {
+                if image.background_color.a > 0. {
+                    set_fill_color(content, image.background_color);
+                    rounded_rect(content, bounds, &image.corner_radii);
+                    content.fill_nonzero();
+                }
+            }
+
+            RenderCommandConfig::ScissorStart(scissor) => {
+                content.save_state();
+                rounded_rect(content, bounds, &scissor.corner_radii);
+                content.clip_nonzero().end_path();
+            }
+
+            RenderCommandConfig::ScissorEnd() => {
+                content.restore_state();
+            }
+
+            RenderCommandConfig::Custom(custom) => {
+                if custom.background_color.a > 0. {
+                    set_fill_color(content, custom.background_color);
+                    rounded_rect(content, bounds, &custom.corner_radii);
+                    content.fill_nonzero();
+                }
+            }
+
+            RenderCommandConfig::None() => {}
+        }
+    }
+
+    fn draw_text(&mut self, bounds: BoundingBox, text: &Text) {
+        let font_id = if (text.font_id.0 as usize) < self.font_count {
+            text.font_id.0
+        } else {
+            0
+        };
+        let font_size = text.font_size as f32;
+        // Center the ascender and descender in the line
+        let baseline = bounds.y + bounds.height / 2. + (ASCENT - DESCENT) / 2. * font_size;
+        let font = format!("F{font_id}");
+
+        set_fill_color(&mut self.content, text.color);
+        self.content
+            .begin_text()
+            .set_font(Name(font.as_bytes()), font_size)
+            .set_char_spacing(text.letter_spacing as f32)
+            // Flip the glyphs back, the page is flipped to the layout coordinates
+            .set_text_matrix([1., 0., 0., -1., bounds.x, baseline])
+            .show(Str(&win_ansi(text.text)))
+            .end_text();
+    }
+}
+
+/// Multi-page PDF document the render commands are drawn to.
+///
+/// Text uses the standard PDF fonts, given by name to [`PdfDocument::new`] and indexed by the
+/// font id of the text elements, so no font file is embedded. Colors are opaque and images are
+/// drawn as their background color, like custom elements.
+pub struct PdfDocument {
+    page_size: Dimensions,
+    fonts: Vec<String>,
+    pages: Vec<PdfPage>,
+}
+
+impl PdfDocument {
+    /// Creates an empty document with pages of `page_size` points. `fonts` are names of standard
+    /// fonts, such as `Helvetica` or `Times-Bold`, defaulting to `Helvetica` if empty.
+    pub fn new(page_size: Dimensions, fonts: &[&str]) -> Self {
+        let fonts = if fonts.is_empty() {
+            vec!["Helvetica".to_owned()]
+        } else {
+            fonts.iter().map(|font| font.to_string()).collect()
+        };
+        Self {
+            page_size,
+            fonts,
+            pages: Vec::new(),
+        }
+    }
+
+    pub fn page_size(&self) -> Dimensions {
+        self.page_size
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Adds a blank page at the end of the document.
+    pub fn add_page(&mut self) -> &mut PdfPage {
+        self.pages
+            .push(PdfPage::new(self.page_size, self.fonts.len()));
+        self.pages.last_mut().unwrap()
+    }
+
+    /// Writes the document, returning the bytes of the PDF file.
+    pub fn finish(self) -> Vec<u8> {
+        let mut pdf = Pdf::new();
+        let catalog_id = Ref::new(1);
+        let tree_id = Ref::new(2);
+        let font_ids: Vec<Ref> = (0..self.fonts.len())
+            .map(|index| Ref::new(3 + index as i32))
+            .collect();
+        let first_page = 3 + self.fonts.len() as i32;
+        // Every page is followed by its content stream
+        let page_ids: Vec<Ref> = (0..self.pages.len())
+            .map(|index| Ref::new(first_page + 2 * index as i32))
+            .collect();
+
+        pdf.catalog(catalog_id).pages(tree_id);
+        pdf.pages(tree_id)
+            .kids(page_ids.iter().copied())
+            .count(page_ids.len() as i32);
+
+        for (font, id) in self.fonts.iter().zip(&font_ids) {
+            pdf.type1_font(*id)
+                .base_font(Name(font.as_bytes()))
+                .encoding_predefined(Name(b"WinAnsiEncoding"));
+        }
+
+        for (page, id) in self.pages.into_iter().zip(&page_ids) {
+            let content_id = Ref::new(id.get() + 1);
+            {
+                let mut page = pdf.page(*id);
+                page.media_box(Rect::new(
+                    0.,
+                    0.,
+                    self.page_size.width,
+                    self.page_size.height,
+                ))
+                .parent(tree_id)
+                .contents(content_id);
+                let mut resources = page.resources();
+                let mut fonts = resources.fonts();
+                for (index, font_id) in font_ids.iter().enumerate() {
+                    fonts.pair(Name(format!("F{index}").as_bytes()), *font_id);
+                }
+            }
+            pdf.stream(content_id, &page.content.finish());
+        }
+
+        pdf.finish()
+    }
+}
+
+/// Renders the commands to a PDF file of a single page of the given dimensions, see
+/// [`PdfDocument`].
+pub fn clay_pdf_render<'a, ImageElementData: 'a, CustomElementData: 'a>(
+    dimensions: Dimensions,
+    render_commands: impl Iterator<Item = RenderCommand<'a, ImageElementData, CustomElementData>>,
+    fonts: &[&str],
+) -> Vec<u8> {
+    let mut document = PdfDocument::new(dimensions, fonts);
+    document.add_page().render(render_commands);
+    document.finish()
+}
+
+/// Splits a layout taller than a page over several pages of a [`PdfDocument`], with a header and
+/// a footer repeated on every page.
+///
+/// The content must declare a single top to bottom container. It is laid out once with the width
+/// of the page and an unbounded height, so it should fit its children instead of growing. Pages
+/// are only broken between the children of that container, unless a child is taller than a page.
+/// ```no_run
+/// use clay_layout::{
+///     layout::{LayoutDirection, Sizing},
+///     renderers::pdf::{Pagination, PdfDocument},
+///     text::TextConfig,
+///     Clay, Declaration,
+/// };
+///
+/// let page_size = (595., 842.).into();
+/// let mut clay = Clay::new(page_size);
+/// let mut document = PdfDocument::new(page_size, &[]);
+/// Pagination::new(page_size).header(40.).footer(40.).render::<(), ()>(
+///     &mut clay,
+///     &mut document,
+///     |clay| {
+///         clay.with(Declaration::new()
+///             .layout()
+///                 .width(Sizing::Grow(0., 0.))
+///                 .direction(LayoutDirection::TopToBottom)
+///                 .end(), |clay|
+///         {
+///             for line in 0..200 {
+///                 clay.text_owned(format!("Line {line}"), TextConfig::new().font_size(12).end());
+///             }
+///         });
+///     },
+///     |clay, _, _| clay.text("Report", TextConfig::new().font_size(16).end()),
+///     |clay, page, count| {
+///         clay.text_owned(format!("{} / {count}", page + 1), TextConfig::new().font_size(10).end())
+///     },
+/// );
+/// std::fs::write("report.pdf", document.finish()).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Pagination {
+    page_size: Dimensions,
+    header_height: f32,
+    footer_height: f32,
+}
+
+impl Pagination {
+    pub fn new(page_size: Dimensions) -> Self {
+        Self {
+            page_size,
+            header_height: 0.,
+            footer_height: 0.,
+        }
+    }
+
+    /// Sets the height of the header at the top of every page.
+    pub fn header(&mut self, height: f32) -> &mut Self {
+        self.header_height = height;
+        self
+    }
+
+    /// Sets the height of the footer at the bottom of every page.
+    pub fn footer(&mut self, height: f32) -> &mut Self {
+        self.footer_height = height;
+        self
+    }
+
+    /// Height of the content on every page, between the header and the footer.
+    pub fn content_height(&self) -> f32 {
+        (self.page_size.height - self.header_height - self.footer_height).max(1.)
+    }
+
+    /// Returns the vertical ranges of the content laid out in `tree` that go on each page.
+    ///
+    /// Pages end at the bottom of the last child of the content container that fits, or are cut
+    /// at the content height if the first child doesn't fit.
+    pub fn page_ranges(&self, tree: &LayoutTree) -> Vec<(f32, f32)> {
+        let Some(column) = tree
+            .roots()
+            .first()
+            .and_then(|&root| tree.get(root)?.children.first().copied())
+            .and_then(|index| tree.get(index))
+        else {
+            return Vec::new();
+        };
+        let start = column.bounding_box.y;
+        let end = start + column.bounding_box.height;
+        let breaks: Vec<f32> = column
+            .children
+            .iter()
+            .filter_map(|&child| tree.get(child))
+            .map(|child| child.bounding_box.y)
+            .chain([end])
+            .collect();
+
+        let content_height = self.content_height();
+        let mut ranges = Vec::new();
+        let mut top = start;
+        while top < end {
+            let limit = top + content_height;
+            let bottom = breaks
+                .iter()
+                .copied()
+                .filter(|&position| position > top && position <= limit)
+                .max_by(f32::total_cmp)
+                .unwrap_or(limit.min(end));
+            ranges.push((top, bottom));
+            top = bottom;
+        }
+        ranges
+    }
+
+    /// Lays out the content and adds its pages to `document`. The header and footer are laid
+    /// out for every page and receive the index of the page and the number of pages.
+    ///
+    /// This changes the layout dimensions of `clay`.
+    pub fn render<'render, ImageElementData: 'render, CustomElementData: 'render>(
+        &self,
+        clay: &mut Clay,
+        document: &mut PdfDocument,
+        content: impl FnOnce(&mut ClayLayoutScope<'_, 'render, ImageElementData, CustomElementData>),
+        mut header: impl FnMut(
+            &mut ClayLayoutScope<'_, 'render, ImageElementData, CustomElementData>,
+            usize,
+            usize,
+        ),
+        mut footer: impl FnMut(
+            &mut ClayLayoutScope<'_, 'render, ImageElementData, CustomElementData>,
+            usize,
+            usize,
+        ),
+    ) {
+        let width = self.page_size.width;
+        clay.set_layout_dimensions(Dimensions::new(width, MAX_CONTENT_HEIGHT));
+        let mut scope = clay.begin::<ImageElementData, CustomElementData>();
+        content(&mut scope);
+        let (tree, commands) = scope.end_with_tree();
+        let commands: Vec<_> = commands.collect();
+
+        let ranges = self.page_ranges(&tree);
+        let page_count = ranges.len();
+        let footer_top = self.page_size.height - self.footer_height;
+
+        for (index, (top, bottom)) in ranges.into_iter().enumerate() {
+            let page = document.add_page();
+            let source = BoundingBox::new(0., top, width, bottom - top);
+            let offset = page.begin_region(source, Vector2::new(0., self.header_height));
+            for command in &commands {
+                page.draw_in_region(command, &source, offset);
+            }
+            page.content.restore_state();
+
+            if self.header_height > 0. {
+                Self::render_band(
+                    clay,
+                    page,
+                    Dimensions::new(width, self.header_height),
+                    0.,
+                    |clay| header(clay, index, page_count),
+                );
+            }
+            if self.footer_height > 0. {
+                Self::render_band(
+                    clay,
+                    page,
+                    Dimensions::new(width, self.footer_height),
+                    footer_top,
+                    |clay| footer(clay, index, page_count),
+                );
+            }
+        }
+    }
+
+    /// Lays out a header or footer of the given size and draws it at `top` on the page.
+    fn render_band<'render, ImageElementData: 'render, CustomElementData: 'render>(
+        clay: &mut Clay,
+        page: &mut PdfPage,
+        size: Dimensions,
+        top: f32,
+        band: impl FnOnce(&mut ClayLayoutScope<'_, 'render, ImageElementData, CustomElementData>),
+    ) {
+        clay.set_layout_dimensions(size);
+        let mut scope = clay.begin::<ImageElementData, CustomElementData>();
+        band(&mut scope);
+        page.render_region(
+            scope.end(),
+            BoundingBox::new(0., 0., size.width, size.height),
+            Vector2::new(0., top),
+        );
+    }
+}
+
+/// Creates a measure function using the widths of Helvetica, the default font of
+/// [`PdfDocument`]. Lines are 1.2 times the font size high.
+pub fn create_measure_text_function() -> impl Fn(&str, &TextConfig) -> Dimensions + 'static {
+    |text, text_config| {
+        let font_size = text_config.font_size as f32;
+        let width: f32 = text
+            .chars()
+            .map(|character| {
+                helvetica_width(character) * font_size + text_config.letter_spacing as f32
+            })
+            .sum();
+        let height = (font_size * LINE_HEIGHT).max(text_config.line_height as f32);
+        (width, height).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        layout::{LayoutDirection, Padding, Sizing},
+        Declaration,
+    };
+    use lopdf::Document;
+
+    #[rustfmt::skip]
+    #[test]
+    fn single_page() {
+        let mut clay = Clay::new((200., 100.).into());
+        clay.set_measure_text_function(create_measure_text_function());
+        let mut scope = clay.begin::<(), ()>();
+        scope.with(Declaration::new()
+            .layout()
+                .width(Sizing::Fixed(120.))
+                .padding(Padding::all(8))
+                .end()
+            .corner_radius().all(6.).end()
+            .border()
+                .all_directions(2)
+                .color(Color::rgb(0., 0., 0.))
+                .end()
+            .clip(true, true, (0., 0.).into())
+            .background_color(Color::rgb(240., 240., 240.)), |clay|
+        {
+            clay.text("Total: 42 €", TextConfig::new().font_size(12).end());
+        });
+        let pdf = clay_pdf_render((200., 100.).into(), scope.end(), &["Times-Roman"]);
+
+        let document = Document::load_mem(&pdf).unwrap();
+        assert_eq!(document.get_pages().len(), 1);
+        assert_eq!(document.extract_text(&[1]).unwrap(), "Total: 42 ?\n");
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn pagination() {
+        let page_size = Dimensions::new(400., 300.);
+        let mut clay = Clay::new(page_size);
+        clay.set_measure_text_function(create_measure_text_function());
+        let mut document = PdfDocument::new(page_size, &[]);
+
+        // Rows of 40 points, five fit between the header and the footer
+        Pagination::new(page_size).header(40.).footer(40.).render::<(), ()>(
+            &mut clay,
+            &mut document,
+            |clay| {
+                clay.with(Declaration::new()
+                    .layout()
+                        .width(Sizing::Grow(0., 0.))
+                        .direction(LayoutDirection::TopToBottom)
+                        .end(), |clay|
+                {
+                    for row in 0..23 {
+                        clay.with(Declaration::new()
+                            .layout()
+                                .width(Sizing::Grow(0., 0.))
+                                .height(Sizing::Fixed(40.))
+                                .end(), |clay|
+                        {
+                            clay.text_owned(format!("Row {row}"), TextConfig::new().font_size(12).end());
+                        });
+                    }
+                });
+            },
+            |clay, _, _| clay.text("Quarterly report", TextConfig::new().font_size(16).end()),
+            |clay, page, count| {
+                clay.text_owned(format!("Page {} of {count}", page + 1), TextConfig::new().font_size(10).end());
+            },
+        );
+        assert_eq!(document.page_count(), 5);
+
+        let document = Document::load_mem(&document.finish()).unwrap();
+        assert_eq!(document.get_pages().len(), 5);
+        assert_eq!(
+            document.extract_text(&[1]).unwrap(),
+            "Row 0\nRow 1\nRow 2\nRow 3\nRow 4\nQuarterly report\nPage 1 of 5\n"
+        );
+        assert_eq!(
+            document.extract_text(&[5]).unwrap(),
+            "Row 20\nRow 21\nRow 22\nQuarterly report\nPage 5 of 5\n"
+        );
+    }
+
+    #[test]
+    fn page_cut_inside_tall_element() {
+        let page_size = Dimensions::new(100., 100.);
+        let mut clay = Clay::new(page_size);
+        let mut document = PdfDocument::new(page_size, &[]);
+        Pagination::new(page_size).render::<(), ()>(
+            &mut clay,
+            &mut document,
+            |clay| {
+                clay.with(
+                    Declaration::new()
+                        .layout()
+                        .direction(LayoutDirection::TopToBottom)
+                        .end(),
+                    |clay| {
+                        for height in [60., 250., 30.] {
+                            clay.with(
+                                Declaration::new()
+                                    .layout()
+                                    .width(Sizing::Fixed(10.))
+                                    .height(Sizing::Fixed(height))
+                                    .end(),
+                                |_| {},
+                            );
+                        }
+                    },
+                );
+            },
+            |_, _, _| {},
+            |_, _, _| {},
+        );
+        // 60 | 100 | 100 | 50 + 30
+        assert_eq!(document.page_count(), 4);
+    }
+}
@@ -1,6 +1,18 @@
-use crate::render_commands::{RenderCommand, RenderCommandConfig};
+use std::ffi::CString;
+
+use crate::clip::Clip;
+use crate::fonts::{FontId, FontRegistry, HasGlyph};
+use crate::math::{BoundingBox, Dimensions};
+use crate::render_commands::{
+    Border, CornerRadii, Custom, Image, Rectangle, RenderCommand, Renderer, Scissor, Text,
+};
+use crate::tessellate::{Mesh, Tessellator};
+use crate::text::TextConfig;
 use raylib::{
-    ffi::{BeginScissorMode, EndScissorMode},
+    ffi::{
+        rlBegin, rlColor4ub, rlEnd, rlSetTexture, rlTexCoord2f, rlVertex2f, BeginScissorMode,
+        EndScissorMode, RL_TRIANGLES,
+    },
     prelude::*,
 };
 
@@ -26,220 +38,369 @@ macro_rules! clay_to_raylib_rect {
     };
 }
 
-#[doc = "This is a direct* port of Clay's raylib renderer. See [the C implementation](https://github.com/nicbarker/clay/blob/main/renderers/raylib/clay_renderer_raylib.c) for more info."]
-pub fn clay_raylib_render<'rl, 'a, CustomElementData: 'a>(
-    d: &mut RaylibDrawHandle<'rl>,
-    render_commands: impl Iterator<Item = RenderCommand<'a, Texture2D, CustomElementData>>,
-    fonts: &[Font],
-    mut handle_custom_element: impl FnMut(RenderCommand<'a, Texture2D, CustomElementData> , &mut RaylibDrawHandle<'rl>),
-) {
-    for command in render_commands {
-        match command.config {
-            RenderCommandConfig::Text(text) => {
-                let text_data = text.text;
-                if let Some(font) = fonts.get(text.font_id as usize) {
-                    d.draw_text_ex(
-                        font,
-                        text_data,
-                        Vector2 {
-                            x: command.bounding_box.x,
-                            y: command.bounding_box.y
-                        },
-                        text.font_size.into(),
-                        text.letter_spacing.into(),
-                        clay_to_raylib_color!(text.color),
-                    );
-                } else {
-                    d.draw_text(
-                        text_data,
-                        command.bounding_box.x as i32,
-                        command.bounding_box.y as i32,
-                        text.font_size.into(),
-                        clay_to_raylib_color!(text.color),
-                    );
-                }
+/// Raylib has no scissor stack, so `clip` must already be intersected with the enclosing clips.
+fn begin_scissor(clip: BoundingBox) {
+    // safety: raylib's BeginScissorMode is safe to call with any values.
+    // we need to use this here because the regular begin_scissor_mode
+    // ends the scissor mode on drop.
+    unsafe {
+        BeginScissorMode(
+            clip.x as i32,
+            clip.y as i32,
+            clip.width as i32,
+            clip.height as i32,
+        );
+    }
+}
+
+/// Draws the triangles of `mesh` with the texture `texture_id`, `0` being raylib's white texture.
+fn draw_mesh(mesh: &Mesh, texture_id: u32) {
+    // safety: rlgl batches the vertices, it only needs to be called between
+    // BeginDrawing and EndDrawing, which the draw handle guarantees.
+    unsafe {
+        rlSetTexture(texture_id);
+        rlBegin(RL_TRIANGLES as i32);
+        for triangle in mesh.indices.chunks_exact(3) {
+            let [a, b, c] =
+                [triangle[0], triangle[1], triangle[2]].map(|index| &mesh.vertices[index as usize]);
+            // Raylib culls the triangles that aren't counter-clockwise on screen
+            let cross = (b.position[0] - a.position[0]) * (c.position[1] - a.position[1])
+                - (b.position[1] - a.position[1]) * (c.position[0] - a.position[0]);
+            let vertices = if cross > 0. { [a, c, b] } else { [a, b, c] };
+            for vertex in vertices {
+                let [r, g, b, a] = vertex.color.map(|channel| (channel * 255.).round() as u8);
+                rlColor4ub(r, g, b, a);
+                rlTexCoord2f(vertex.uv[0], vertex.uv[1]);
+                rlVertex2f(vertex.position[0], vertex.position[1]);
             }
+        }
+        rlEnd();
+        rlSetTexture(0);
+    }
+}
+
+/// The radius of the corners if they are all the same.
+fn uniform_radius(radii: &CornerRadii) -> Option<f32> {
+    let radius = radii.top_left;
+    (radii.top_right == radius && radii.bottom_right == radius && radii.bottom_left == radius)
+        .then_some(radius)
+}
+
+/// The part of a texture covering `bounds` with the aspect ratio of the texture, centered and
+/// cropped on the sides that overflow, in texels.
+fn cover_source(texture: &Texture2D, bounds: BoundingBox) -> ::raylib::math::Rectangle {
+    let (texture_width, texture_height) = (texture.width as f32, texture.height as f32);
+    let scale = (bounds.width / texture_width).max(bounds.height / texture_height);
+    if !scale.is_finite() || scale <= 0. {
+        return ::raylib::math::Rectangle::new(0., 0., texture_width, texture_height);
+    }
+    let (width, height) = (bounds.width / scale, bounds.height / scale);
+    ::raylib::math::Rectangle::new(
+        (texture_width - width) / 2.,
+        (texture_height - height) / 2.,
+        width,
+        height,
+    )
+}
 
-            RenderCommandConfig::Image(image) => {
-                let texture = image.data;
+impl HasGlyph for Font {
+    fn has_glyph(&self, character: char) -> bool {
+        // Raylib gives the index of '?' for the characters it has no glyph for
+        let index = self.get_glyph_index(character);
+        RaylibFont::chars(self)
+            .get(index as usize)
+            .is_some_and(|glyph| glyph.value == character as i32)
+    }
+}
+
+/// Runs of text with the fonts drawing them, raylib's default font when the font id isn't
+/// registered.
+fn font_runs<'t>(
+    fonts: &FontRegistry<Font>,
+    font_id: FontId,
+    text: &'t str,
+) -> Vec<(::raylib::ffi::Font, &'t str)> {
+    if fonts.get(font_id).is_none() {
+        // safety: the default font is loaded with the window
+        return vec![(unsafe { ::raylib::ffi::GetFontDefault() }, text)];
+    }
+    fonts
+        .runs(font_id, text)
+        .map(|(font, run)| (*AsRef::<::raylib::ffi::Font>::as_ref(font), run))
+        .collect()
+}
+
+/// Size of a run of text drawn with `font`, without spacing after its last character.
+fn measure_run(font: ::raylib::ffi::Font, run: &CString, font_size: f32, spacing: f32) -> Vector2 {
+    // safety: the font and the text are valid for the duration of the call
+    unsafe { ::raylib::ffi::MeasureTextEx(font, run.as_ptr(), font_size, spacing).into() }
+}
+
+struct RaylibRenderer<'d, 'rl, 'f, F> {
+    d: &'d mut RaylibDrawHandle<'rl>,
+    fonts: &'f FontRegistry<Font>,
+    handle_custom_element: F,
+    tessellator: Tessellator,
+    mesh: Mesh,
+}
 
-                d.draw_texture_ex(
-                    texture,
-                    Vector2::new(command.bounding_box.x, command.bounding_box.y),
-                    0.,
-                    command.bounding_box.width / texture.width as f32,
-                    // TODO: backgrond color isnt in raylib bindings?
-                    clay_to_raylib_color!(Color::WHITE),
+impl<F> RaylibRenderer<'_, '_, '_, F> {
+    /// Draws a quarter of a ring from `start_angle`, clockwise, its inner radius shrunk by
+    /// `width`.
+    fn corner_ring(
+        &mut self,
+        center: Vector2,
+        radius: f32,
+        width: u16,
+        start_angle: f32,
+        color: Color,
+    ) {
+        if radius <= 0. || width == 0 {
+            return;
+        }
+        self.d.draw_ring(
+            Vector2::new(center.x.round(), center.y.round()),
+            (radius - width as f32).max(0.),
+            radius,
+            start_angle,
+            start_angle + 90.,
+            10,
+            color,
+        );
+    }
+}
+
+impl<'a, 'rl, CustomElementData: 'a, F> Renderer<'a, Texture2D, CustomElementData>
+    for RaylibRenderer<'_, 'rl, '_, F>
+where
+    F: FnMut(RenderCommand<'a, Texture2D, CustomElementData>, &mut RaylibDrawHandle<'rl>),
+{
+    fn rectangle(&mut self, bounds: BoundingBox, rect: &Rectangle) {
+        match uniform_radius(&rect.corner_radii) {
+            Some(radius) if radius > 0. => {
+                let roundness = (radius * 2.) / bounds.width.min(bounds.height);
+                self.d.draw_rectangle_rounded(
+                    clay_to_raylib_rect!(bounds),
+                    roundness,
+                    8,
+                    clay_to_raylib_color!(rect.color),
                 );
             }
+            Some(_) => {
+                self.d.draw_rectangle_rec(
+                    clay_to_raylib_rect!(bounds),
+                    clay_to_raylib_color!(rect.color),
+                );
+            }
+            // Raylib only rounds all the corners alike
+            None => {
+                self.mesh.clear();
+                self.tessellator.rectangle(&mut self.mesh, bounds, rect, 0);
+                draw_mesh(&self.mesh, 0);
+            }
+        }
+    }
+
+    fn border(&mut self, bounds: BoundingBox, border: &Border) {
+        let radii = &border.corner_radii;
+        let width = &border.width;
+        let color = clay_to_raylib_color!(border.color);
+        let (left, top) = (bounds.x, bounds.y);
+        let (right, bottom) = (bounds.x + bounds.width, bounds.y + bounds.height);
+
+        if width.left > 0 {
+            self.d.draw_rectangle_rec(
+                ::raylib::math::Rectangle::new(
+                    left,
+                    top + radii.top_left,
+                    width.left as f32,
+                    bounds.height - radii.top_left - radii.bottom_left,
+                ),
+                color,
+            );
+        }
+        if width.right > 0 {
+            self.d.draw_rectangle_rec(
+                ::raylib::math::Rectangle::new(
+                    right - width.right as f32,
+                    top + radii.top_right,
+                    width.right as f32,
+                    bounds.height - radii.top_right - radii.bottom_right,
+                ),
+                color,
+            );
+        }
+        if width.top > 0 {
+            self.d.draw_rectangle_rec(
+                ::raylib::math::Rectangle::new(
+                    left + radii.top_left,
+                    top,
+                    bounds.width - radii.top_left - radii.top_right,
+                    width.top as f32,
+                ),
+                color,
+            );
+        }
+        if width.bottom > 0 {
+            self.d.draw_rectangle_rec(
+                ::raylib::math::Rectangle::new(
+                    left + radii.bottom_left,
+                    bottom - width.bottom as f32,
+                    bounds.width - radii.bottom_left - radii.bottom_right,
+                    width.bottom as f32,
+                ),
+                color,
+            );
+        }
+
+        // Like Clay's renderer, the arcs take the width of the top and bottom sides
+        let (top_left, top_right) = (radii.top_left, radii.top_right);
+        let (bottom_left, bottom_right) = (radii.bottom_left, radii.bottom_right);
+        let center = Vector2::new(left + top_left, top + top_left);
+        self.corner_ring(center, top_left, width.top, 180., color);
+        let center = Vector2::new(right - top_right, top + top_right);
+        self.corner_ring(center, top_right, width.top, 270., color);
+        let center = Vector2::new(right - bottom_right, bottom - bottom_right);
+        self.corner_ring(center, bottom_right, width.bottom, 0., color);
+        let center = Vector2::new(left + bottom_left, bottom - bottom_left);
+        self.corner_ring(center, bottom_left, width.bottom, 90., color);
+    }
 
-            // safety: raylib's BeginScissorMode is safe to call with any values.
-            // we need to use this here because the regular begin_scissor_mode
-            // ends the scissor mode on drop.
-            RenderCommandConfig::ScissorStart(_) => unsafe {
-                BeginScissorMode(
-                    command.bounding_box.x as i32,
-                    command.bounding_box.y as i32,
-                    command.bounding_box.width as i32,
-                    command.bounding_box.height as i32,
+    fn text(&mut self, bounds: BoundingBox, text: &Text<'a>) {
+        let font_size = text.font_size as f32;
+        // Clay sizes the lines with the line height, the glyphs are centered in them
+        let line_offset = (text.line_height as f32 - font_size).max(0.) / 2.;
+        let mut position = Vector2::new(bounds.x, bounds.y + line_offset);
+        let spacing = text.letter_spacing as f32;
+        let color = clay_to_raylib_color!(text.color);
+
+        // The runs drawn with fallback fonts follow each other like the glyphs of a run
+        for (font, run) in font_runs(self.fonts, text.font_id, text.text) {
+            let Ok(run) = CString::new(run) else {
+                continue;
+            };
+            // safety: the font and the text are valid for the duration of the call, and the
+            // draw handle is borrowed while drawing
+            unsafe {
+                ::raylib::ffi::DrawTextEx(
+                    font,
+                    run.as_ptr(),
+                    position.into(),
+                    font_size,
+                    spacing,
+                    color.into(),
                 );
-            },
-
-            RenderCommandConfig::ScissorEnd() => unsafe {
-                EndScissorMode();
-            },
-
-            RenderCommandConfig::Rectangle(rect) => {
-                if rect.corner_radii.top_left > 0. {
-                    let radius = (rect.corner_radii.top_left * 2.)
-                        / if command.bounding_box.width > command.bounding_box.height {
-                        command.bounding_box.height
-                    } else {
-                        command.bounding_box.width
-                    };
-
-                    d.draw_rectangle_rounded(
-                        clay_to_raylib_rect!(command.bounding_box),
-                        radius,
-                        8,
-                        clay_to_raylib_color!(rect.color),
-                    );
-                } else {
-                    d.draw_rectangle(
-                        command.bounding_box.x as i32,
-                        command.bounding_box.y as i32,
-                        command.bounding_box.width as i32,
-                        command.bounding_box.height as i32,
-                        clay_to_raylib_color!(rect.color),
-                    );
-                }
             }
+            position.x += measure_run(font, &run, font_size, spacing).x + spacing;
+        }
+    }
+
+    fn image(&mut self, bounds: BoundingBox, image: &Image<'a, Texture2D>) {
+        let texture = image.data;
+
+        if uniform_radius(&image.corner_radii) == Some(0.) {
+            // Like Clay's renderers, a transparent background leaves the image untinted
+            let tint = if image.background_color.a > 0. {
+                clay_to_raylib_color!(image.background_color)
+            } else {
+                Color::WHITE
+            };
+            self.d.draw_texture_pro(
+                texture,
+                cover_source(texture, bounds),
+                clay_to_raylib_rect!(bounds),
+                Vector2::zero(),
+                0.,
+                tint,
+            );
+        } else {
+            self.mesh.clear();
+            self.tessellator.image(&mut self.mesh, bounds, image, 0);
+            // The uv of the mesh map the whole texture, narrow them to the covering part
+            let source = cover_source(texture, bounds);
+            let (width, height) = (texture.width as f32, texture.height as f32);
+            for vertex in &mut self.mesh.vertices {
+                let [u, v] = vertex.uv;
+                vertex.uv = [
+                    (source.x + u * source.width) / width,
+                    (source.y + v * source.height) / height,
+                ];
+            }
+            draw_mesh(&self.mesh, texture.id);
+        }
+    }
+
+    fn push_clip(&mut self, _bounds: BoundingBox, _scissor: &Scissor, clip: &Clip) {
+        begin_scissor(clip.bounds);
+    }
+
+    fn pop_clip(&mut self, clip: Option<&Clip>) {
+        match clip {
+            Some(clip) => begin_scissor(clip.bounds),
+            // safety: EndScissorMode only resets the scissor state
+            None => unsafe { EndScissorMode() },
+        }
+    }
+
+    fn custom(
+        &mut self,
+        command: &RenderCommand<'a, Texture2D, CustomElementData>,
+        _custom: &Custom<'a, CustomElementData>,
+    ) {
+        (self.handle_custom_element)(command.clone(), &mut *self.d);
+    }
+}
+
+/// Renders the commands with raylib, like Clay's C renderer: missing fonts fall back to raylib's
+/// default font and the characters missing from a font to its fallbacks. Images keep their
+/// aspect ratio, covering their bounds and cropped in the center when the bounds have another
+/// one. Raylib's scissors are rectangles, the corners of rounded scroll containers aren't
+/// clipped.
+///
+/// See [the C implementation](https://github.com/nicbarker/clay/blob/main/renderers/raylib/clay_renderer_raylib.c)
+/// for more info.
+pub fn clay_raylib_render<'rl, 'a, CustomElementData: 'a>(
+    d: &mut RaylibDrawHandle<'rl>,
+    render_commands: impl Iterator<Item = RenderCommand<'a, Texture2D, CustomElementData>>,
+    fonts: &FontRegistry<Font>,
+    handle_custom_element: impl FnMut(
+        RenderCommand<'a, Texture2D, CustomElementData>,
+        &mut RaylibDrawHandle<'rl>,
+    ),
+) {
+    RaylibRenderer {
+        d,
+        fonts,
+        handle_custom_element,
+        tessellator: Tessellator::new(),
+        mesh: Mesh::new(),
+    }
+    .render(render_commands);
+}
+
+/// Measures text like [`clay_raylib_render`] draws it, to be given to
+/// [`Clay::set_measure_text_function`](crate::Clay::set_measure_text_function). `fonts` are the
+/// ones given to the renderer, raylib's window must be open when the layout is computed.
+pub fn raylib_measure_text(
+    fonts: impl AsRef<FontRegistry<Font>> + 'static,
+) -> impl Fn(&str, &TextConfig) -> Dimensions + 'static {
+    move |text, text_config| {
+        let font_size = text_config.font_size as f32;
+        let spacing = text_config.letter_spacing as f32;
 
-            RenderCommandConfig::Border(border) => {
-                if border.width.left > 0 {
-                    d.draw_rectangle(
-                        command.bounding_box.x as i32,
-                        (command.bounding_box.y + border.corner_radii.top_left) as i32,
-                        border.width.left as i32,
-                        (command.bounding_box.height
-                            - border.corner_radii.top_left
-                            - border.corner_radii.bottom_left) as i32,
-                        clay_to_raylib_color!(border.color),
-                    );
-                }
-
-                if border.width.right > 0 {
-                    d.draw_rectangle(
-                        (command.bounding_box.x + command.bounding_box.width
-                            - border.width.right as f32) as i32,
-                        (command.bounding_box.y + border.corner_radii.top_right) as i32,
-                        border.width.right as i32,
-                        (command.bounding_box.height
-                            - border.corner_radii.top_right
-                            - border.corner_radii.bottom_right) as i32,
-                        clay_to_raylib_color!(border.color),
-                    );
-                }
-
-                if border.width.top > 0 {
-                    d.draw_rectangle(
-                        (command.bounding_box.x + border.corner_radii.top_left) as i32,
-                        command.bounding_box.y as i32,
-                        (command.bounding_box.width
-                            - border.corner_radii.top_left
-                            - border.corner_radii.top_right) as i32,
-                        border.width.top as i32,
-                        clay_to_raylib_color!(border.color),
-                    );
-                }
-
-                if border.width.bottom > 0 {
-                    d.draw_rectangle(
-                        (command.bounding_box.x + border.corner_radii.bottom_left) as i32,
-                        (command.bounding_box.y + command.bounding_box.height
-                            - border.width.bottom as f32) as i32,
-                        (command.bounding_box.width
-                            - border.corner_radii.bottom_left
-                            - border.corner_radii.bottom_right) as i32,
-                        border.width.bottom as i32,
-                        clay_to_raylib_color!(border.color),
-                    )
-                }
-
-                if border.corner_radii.top_left > 0. {
-                    let vec = Vector2::new(
-                        (command.bounding_box.x + border.corner_radii.top_left) as f32,
-                        (command.bounding_box.y + border.corner_radii.top_left) as f32,
-                    );
-
-                    d.draw_ring(
-                        vec,
-                        border.corner_radii.top_left - border.width.top as f32,
-                        border.corner_radii.top_left,
-                        180.,
-                        270.,
-                        10,
-                        clay_to_raylib_color!(border.color),
-                    );
-                }
-
-                if border.corner_radii.top_right > 0. {
-                    let vec = Vector2::new(
-                        (command.bounding_box.x + command.bounding_box.width
-                            - border.corner_radii.top_right) as f32,
-                        (command.bounding_box.y + border.corner_radii.top_right) as f32,
-                    );
-
-                    d.draw_ring(
-                        vec,
-                        border.corner_radii.top_right - border.width.top as f32,
-                        border.corner_radii.top_right,
-                        270.,
-                        360.,
-                        10,
-                        clay_to_raylib_color!(border.color),
-                    );
-                }
-
-                if border.corner_radii.bottom_left > 0. {
-                    let vec = Vector2::new(
-                        (command.bounding_box.x + border.corner_radii.bottom_left) as f32,
-                        (command.bounding_box.y + command.bounding_box.height
-                            - border.corner_radii.bottom_left) as f32,
-                    );
-
-                    d.draw_ring(
-                        vec,
-                        border.corner_radii.bottom_left - border.width.bottom as f32,
-                        border.corner_radii.bottom_left,
-                        90.,
-                        180.,
-                        10,
-                        clay_to_raylib_color!(border.color),
-                    );
-                }
-
-                if border.corner_radii.bottom_right > 0. {
-                    let vec = Vector2::new(
-                        (command.bounding_box.x + command.bounding_box.width
-                            - border.corner_radii.bottom_right) as f32,
-                        (command.bounding_box.y + command.bounding_box.height
-                            - border.corner_radii.bottom_right) as f32,
-                    );
-
-                    d.draw_ring(
-                        vec,
-                        border.corner_radii.bottom_right - border.width.bottom as f32,
-                        border.corner_radii.bottom_right,
-                        0.,
-                        90.,
-                        10,
-                        clay_to_raylib_color!(border.color),
-                    );
-                }
+        let (mut width, mut height) = (0f32, 0f32);
+        let runs = font_runs(fonts.as_ref(), text_config.font_id, text);
+        for (index, (font, run)) in runs.into_iter().enumerate() {
+            let Ok(run) = CString::new(run) else {
+                return Dimensions::default();
+            };
+            let size = measure_run(font, &run, font_size, spacing);
+            if index > 0 {
+                width += spacing;
             }
-            RenderCommandConfig::Custom(_) => handle_custom_element(command, &mut *d),
-            RenderCommandConfig::None() => {}
+            width += size.x;
+            height = height.max(size.y);
         }
+        (width, height.max(text_config.line_height as f32)).into()
     }
 }
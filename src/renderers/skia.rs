@@ -1,9 +1,15 @@
+use crate::clip::Clip;
+use crate::fonts::{FontId, FontRegistry, HasGlyph};
 use crate::math::{BoundingBox, Dimensions};
-use crate::render_commands::{Custom, RenderCommand, RenderCommandConfig};
+use crate::render_commands::{
+    Border, CornerRadii, Custom, Image as ClayImage, Rectangle, RenderCommand, Renderer, Scissor,
+    Text,
+};
 use crate::text::TextConfig;
 use crate::{ClayLayoutScope, Color as ClayColor};
 use skia_safe::{
-    Canvas, ClipOp, Color, Font, Image, Paint, Point, RRect, Rect, SamplingOptions, Typeface,
+    Canvas, ClipOp, Color, Font, GlyphId, Image, Paint, Point, RRect, Rect, SamplingOptions, Size,
+    TextBlob, Typeface,
 };
 
 pub fn clay_to_skia_color(color: ClayColor) -> Color {
@@ -18,270 +24,363 @@ pub fn clay_to_skia_color(color: ClayColor) -> Color {
 fn clay_to_skia_rect(rect: BoundingBox) -> Rect {
     Rect::from_xywh(rect.x, rect.y, rect.width, rect.height)
 }
-/// This is a direct* port of Clay's raylib renderer using skia_safe as the drawing API.
-pub fn clay_skia_render<'a, CustomElementData: 'a>(
-    canvas: &Canvas,
-    render_commands: impl Iterator<Item = RenderCommand<'a, Image, CustomElementData>>,
-    mut render_custom_element: impl FnMut(
-        &RenderCommand<'a, Image, CustomElementData>,
-        &Custom<'a, CustomElementData>,
-        &Canvas,
-    ),
-    fonts: &[&Typeface],
-) {
-    for command in render_commands {
-        match command.config {
-            RenderCommandConfig::Text(text) => {
-                let text_data = text.text;
-                let mut paint = Paint::default();
-                paint.set_color(clay_to_skia_color(text.color));
-                let font = Font::new(fonts[text.font_id as usize].clone(), text.font_size as f32);
-                let pos = Point::new(
-                    command.bounding_box.x,
-                    command.bounding_box.y + text.font_size as f32,
-                );
-                canvas.draw_str(&text_data, pos, &font, &paint);
-            }
 
-            RenderCommandConfig::Image(image) => {
-                let skia_image = image.data;
-                let mut paint = Paint::default();
-                paint.set_color(Color::WHITE);
-                paint.set_anti_alias(true);
-
-                let bounds = clay_to_skia_rect(command.bounding_box);
-                let has_border_radius = image.corner_radii.top_left > 0.
-                    || image.corner_radii.top_right > 0.
-                    || image.corner_radii.bottom_left > 0.
-                    || image.corner_radii.bottom_right > 0.;
-                if has_border_radius {
-                    canvas.save();
-                    let rrect = RRect::new_rect_radii(
-                        bounds,
-                        &[
-                            Point::new(image.corner_radii.top_left, image.corner_radii.top_left),
-                            Point::new(image.corner_radii.top_right, image.corner_radii.top_right),
-                            Point::new(
-                                image.corner_radii.bottom_left,
-                                image.corner_radii.bottom_left,
-                            ),
-                            Point::new(
-                                image.corner_radii.bottom_right,
-                                image.corner_radii.bottom_right,
-                            ),
-                        ],
-                    );
-                    canvas.clip_rrect(rrect, ClipOp::Intersect, true);
-                }
+fn skia_rrect(bounds: Rect, radii: &CornerRadii) -> RRect {
+    RRect::new_rect_radii(
+        bounds,
+        &[
+            Point::new(radii.top_left, radii.top_left),
+            Point::new(radii.top_right, radii.top_right),
+            Point::new(radii.bottom_right, radii.bottom_right),
+            Point::new(radii.bottom_left, radii.bottom_left),
+        ],
+    )
+}
 
-                canvas.draw_image_rect_with_sampling_options(
-                    skia_image,
-                    None,
-                    bounds,
-                    SamplingOptions::new(
-                        skia_safe::FilterMode::Linear,
-                        skia_safe::MipmapMode::Linear,
-                    ),
-                    &paint,
+fn has_corner_radius(radii: &CornerRadii) -> bool {
+    radii.top_left > 0. || radii.top_right > 0. || radii.bottom_left > 0. || radii.bottom_right > 0.
+}
+
+/// Font of `typeface` at `size`, or of skia's default typeface when there is none.
+fn skia_font(typeface: Option<&Typeface>, size: f32) -> Font {
+    match typeface {
+        Some(typeface) => Font::from_typeface(typeface.clone(), size),
+        None => {
+            let mut font = Font::default();
+            font.set_size(size);
+            font
+        }
+    }
+}
+
+/// Glyphs of `text` and their horizontal positions, with `letter_spacing` added after each glyph,
+/// and the total width.
+fn layout_glyphs(font: &Font, text: &str, letter_spacing: f32) -> (Vec<GlyphId>, Vec<f32>, f32) {
+    let glyphs = font.str_to_glyphs_vec(text);
+    let mut widths = vec![0.; glyphs.len()];
+    font.get_widths(&glyphs, &mut widths);
+
+    let mut x = 0.;
+    let positions = widths
+        .iter()
+        .map(|width| {
+            let position = x;
+            x += width + letter_spacing;
+            position
+        })
+        .collect();
+    (glyphs, positions, x)
+}
+
+/// Width of a run of text drawn with `font`, with `letter_spacing` added after each glyph.
+fn run_width(font: &Font, run: &str, letter_spacing: f32) -> f32 {
+    if letter_spacing == 0. {
+        font.measure_str(run, None).0
+    } else {
+        layout_glyphs(font, run, letter_spacing).2
+    }
+}
+
+impl HasGlyph for Typeface {
+    fn has_glyph(&self, character: char) -> bool {
+        self.unichar_to_glyph(character as i32) != 0
+    }
+}
+
+/// Runs of text with the fonts drawing them, of skia's default typeface when the font id isn't
+/// registered.
+fn font_runs<'t>(
+    fonts: &FontRegistry<Typeface>,
+    font_id: FontId,
+    size: f32,
+    text: &'t str,
+) -> Vec<(Font, &'t str)> {
+    if fonts.get(font_id).is_none() {
+        return vec![(skia_font(None, size), text)];
+    }
+    fonts
+        .runs(font_id, text)
+        .map(|(typeface, run)| (skia_font(Some(typeface), size), run))
+        .collect()
+}
+
+/// Distance from the top of a line to its baseline, the glyphs are centered in lines taller than
+/// the font.
+fn baseline_offset(font: &Font, line_height: u16) -> f32 {
+    let (_, metrics) = font.metrics();
+    let height = metrics.descent - metrics.ascent;
+    (line_height as f32 - height).max(0.) / 2. - metrics.ascent
+}
+
+struct SkiaRenderer<'c, 'f, F> {
+    canvas: &'c Canvas,
+    fonts: &'f FontRegistry<Typeface>,
+    render_custom_element: F,
+}
+
+impl<'a, CustomElementData: 'a, F> Renderer<'a, Image, CustomElementData>
+    for SkiaRenderer<'_, '_, F>
+where
+    F: FnMut(&RenderCommand<'a, Image, CustomElementData>, &Custom<'a, CustomElementData>, &Canvas),
+{
+    fn rectangle(&mut self, bounds: BoundingBox, rect: &Rectangle) {
+        let paint = {
+            let mut p = Paint::default();
+            p.set_color(clay_to_skia_color(rect.color));
+            p.set_anti_alias(true);
+            p.set_style(skia_safe::PaintStyle::Fill);
+            p
+        };
+        let bounds = clay_to_skia_rect(bounds);
+        if has_corner_radius(&rect.corner_radii) {
+            self.canvas
+                .draw_rrect(skia_rrect(bounds, &rect.corner_radii), &paint);
+        } else {
+            self.canvas.draw_rect(bounds, &paint);
+        }
+    }
+
+    fn border(&mut self, bb: BoundingBox, border: &Border) {
+        let canvas = self.canvas;
+        // Draw each border side using fill rectangles.
+        let paint = {
+            let mut p = Paint::default();
+            p.set_color(clay_to_skia_color(border.color));
+            p.set_anti_alias(true);
+            p
+        };
+
+        // Left border.
+        if border.width.left > 0 {
+            let rect = Rect::from_xywh(
+                bb.x,
+                bb.y + border.corner_radii.top_left,
+                border.width.left as f32,
+                bb.height - border.corner_radii.top_left - border.corner_radii.bottom_left,
+            );
+            canvas.draw_rect(rect, &paint);
+        }
+
+        // Right border.
+        if border.width.right > 0 {
+            let rect = Rect::from_xywh(
+                bb.x + bb.width - border.width.right as f32,
+                bb.y + border.corner_radii.top_right,
+                border.width.right as f32,
+                bb.height - border.corner_radii.top_right - border.corner_radii.bottom_right,
+            );
+            canvas.draw_rect(rect, &paint);
+        }
+
+        // Top border.
+        if border.width.top > 0 {
+            let rect = Rect::from_xywh(
+                bb.x + border.corner_radii.top_left,
+                bb.y,
+                bb.width - border.corner_radii.top_left - border.corner_radii.top_right,
+                border.width.top as f32,
+            );
+            canvas.draw_rect(rect, &paint);
+        }
+
+        // Bottom border.
+        if border.width.bottom > 0 {
+            let rect = Rect::from_xywh(
+                bb.x + border.corner_radii.bottom_left,
+                bb.y + bb.height - border.width.bottom as f32,
+                bb.width - border.corner_radii.bottom_left - border.corner_radii.bottom_right,
+                border.width.bottom as f32,
+            );
+            canvas.draw_rect(rect, &paint);
+        }
+
+        // For corner arcs, we draw strokes.
+        let mut stroke = Paint::default();
+        stroke.set_color(clay_to_skia_color(border.color));
+        stroke.set_stroke_width(1.0);
+        stroke.set_style(skia_safe::paint::Style::Stroke);
+        stroke.set_anti_alias(true);
+
+        // Helper to draw an arc.
+        let draw_corner_arc =
+            |center_x: f32, center_y: f32, radius: f32, start_angle: f32, sweep_angle: f32| {
+                let arc_rect = Rect::from_xywh(
+                    center_x - radius,
+                    center_y - radius,
+                    radius * 2.0,
+                    radius * 2.0,
                 );
+                canvas.draw_arc(arc_rect, start_angle, sweep_angle, false, &stroke);
+            };
 
-                // Restore canvas state if we applied a clip
-                if has_border_radius {
-                    canvas.restore();
-                }
-            }
+        if border.corner_radii.top_left > 0. {
+            // top-left: arc from 180 to 270 degrees.
+            let radius = border.corner_radii.top_left;
+            draw_corner_arc(bb.x + radius, bb.y + radius, radius, 180.0, 90.0);
+        }
 
-            RenderCommandConfig::ScissorStart() => {
-                // Save the current state then clip to the bounding box.
-                canvas.save();
-                let clip_rect = clay_to_skia_rect(command.bounding_box);
-                canvas.clip_rect(clip_rect, ClipOp::Intersect, false);
-            }
+        if border.corner_radii.top_right > 0. {
+            // top-right: arc from 270 to 360 degrees.
+            let radius = border.corner_radii.top_right;
+            draw_corner_arc(bb.x + bb.width - radius, bb.y + radius, radius, 270.0, 90.0);
+        }
 
-            RenderCommandConfig::ScissorEnd() => {
-                // Restore the previous state
-                canvas.restore();
-            }
+        if border.corner_radii.bottom_left > 0. {
+            // bottom-left: arc from 90 to 180 degrees.
+            let radius = border.corner_radii.bottom_left;
+            draw_corner_arc(bb.x + radius, bb.y + bb.height - radius, radius, 90.0, 90.0);
+        }
+
+        if border.corner_radii.bottom_right > 0. {
+            // bottom-right: arc from 0 to 90 degrees.
+            let radius = border.corner_radii.bottom_right;
+            draw_corner_arc(
+                bb.x + bb.width - radius,
+                bb.y + bb.height - radius,
+                radius,
+                0.0,
+                90.0,
+            );
+        }
+    }
 
-            RenderCommandConfig::Rectangle(rect) => {
-                let paint = {
-                    let mut p = Paint::default();
-                    p.set_color(clay_to_skia_color(rect.color));
-                    p.set_anti_alias(true);
-                    p.set_style(skia_safe::PaintStyle::Fill);
-                    p
-                };
-                let bounds = clay_to_skia_rect(command.bounding_box);
-                if rect.corner_radii.top_left > 0.
-                    || rect.corner_radii.top_right > 0.
-                    || rect.corner_radii.bottom_left > 0.
-                    || rect.corner_radii.bottom_right > 0.
+    fn text(&mut self, bounds: BoundingBox, text: &Text<'a>) {
+        let mut paint = Paint::default();
+        paint.set_color(clay_to_skia_color(text.color));
+        paint.set_anti_alias(true);
+        let font_size = text.font_size as f32;
+        // The runs drawn with fallback fonts share the baseline of the font of the text
+        let font = skia_font(self.fonts.get(text.font_id), font_size);
+        let mut origin = Point::new(
+            bounds.x,
+            bounds.y + baseline_offset(&font, text.line_height),
+        );
+
+        let letter_spacing = text.letter_spacing as f32;
+        for (font, run) in font_runs(self.fonts, text.font_id, font_size, text.text) {
+            if text.letter_spacing == 0 {
+                self.canvas.draw_str(run, origin, &font, &paint);
+            } else {
+                let (glyphs, positions, _) = layout_glyphs(&font, run, letter_spacing);
+                if let Some(blob) =
+                    TextBlob::from_pos_text_h(glyphs.as_slice(), &positions, 0., &font)
                 {
-                    let rrect = RRect::new_rect_radii(
-                        bounds,
-                        &[
-                            Point::new(rect.corner_radii.top_left, rect.corner_radii.top_left),
-                            Point::new(rect.corner_radii.top_right, rect.corner_radii.top_right),
-                            Point::new(
-                                rect.corner_radii.bottom_left,
-                                rect.corner_radii.bottom_left,
-                            ),
-                            Point::new(
-                                rect.corner_radii.bottom_right,
-                                rect.corner_radii.bottom_right,
-                            ),
-                        ],
-                    );
-                    canvas.draw_rrect(rrect, &paint);
-                } else {
-                    canvas.draw_rect(bounds, &paint);
+                    self.canvas.draw_text_blob(blob, origin, &paint);
                 }
             }
+            origin.x += run_width(&font, run, letter_spacing);
+        }
+    }
 
-            RenderCommandConfig::Border(border) => {
-                // Draw each border side using fill rectangles.
-                let paint = {
-                    let mut p = Paint::default();
-                    p.set_color(clay_to_skia_color(border.color));
-                    p.set_anti_alias(true);
-                    p
-                };
-
-                let bb = &command.bounding_box;
-
-                // Left border.
-                if border.width.left > 0 {
-                    let rect = Rect::from_xywh(
-                        bb.x,
-                        bb.y + border.corner_radii.top_left,
-                        border.width.left as f32,
-                        bb.height - border.corner_radii.top_left - border.corner_radii.bottom_left,
-                    );
-                    canvas.draw_rect(rect, &paint);
-                }
+    fn image(&mut self, bounds: BoundingBox, image: &ClayImage<'a, Image>) {
+        let mut paint = Paint::default();
+        paint.set_color(Color::WHITE);
+        paint.set_anti_alias(true);
 
-                // Right border.
-                if border.width.right > 0 {
-                    let rect = Rect::from_xywh(
-                        bb.x + bb.width - border.width.right as f32,
-                        bb.y + border.corner_radii.top_right,
-                        border.width.right as f32,
-                        bb.height
-                            - border.corner_radii.top_right
-                            - border.corner_radii.bottom_right,
-                    );
-                    canvas.draw_rect(rect, &paint);
-                }
+        let bounds = clay_to_skia_rect(bounds);
+        let has_border_radius = has_corner_radius(&image.corner_radii);
+        if has_border_radius {
+            self.canvas.save();
+            self.canvas.clip_rrect(
+                skia_rrect(bounds, &image.corner_radii),
+                ClipOp::Intersect,
+                true,
+            );
+        }
 
-                // Top border.
-                if border.width.top > 0 {
-                    let rect = Rect::from_xywh(
-                        bb.x + border.corner_radii.top_left,
-                        bb.y,
-                        bb.width - border.corner_radii.top_left - border.corner_radii.top_right,
-                        border.width.top as f32,
-                    );
-                    canvas.draw_rect(rect, &paint);
-                }
+        self.canvas.draw_image_rect_with_sampling_options(
+            image.data,
+            None,
+            bounds,
+            SamplingOptions::new(skia_safe::FilterMode::Linear, skia_safe::MipmapMode::Linear),
+            &paint,
+        );
 
-                // Bottom border.
-                if border.width.bottom > 0 {
-                    let rect = Rect::from_xywh(
-                        bb.x + border.corner_radii.bottom_left,
-                        bb.y + bb.height - border.width.bottom as f32,
-                        bb.width
-                            - border.corner_radii.bottom_left
-                            - border.corner_radii.bottom_right,
-                        border.width.bottom as f32,
-                    );
-                    canvas.draw_rect(rect, &paint);
-                }
+        // Restore canvas state if we applied a clip
+        if has_border_radius {
+            self.canvas.restore();
+        }
+    }
 
-                // For corner arcs, we draw strokes.
-                let mut stroke = Paint::default();
-                stroke.set_color(clay_to_skia_color(border.color));
-                stroke.set_stroke_width(1.0);
-                stroke.set_style(skia_safe::paint::Style::Stroke);
-                stroke.set_anti_alias(true);
-
-                // Helper to draw an arc.
-                let draw_corner_arc = |canvas: &Canvas,
-                                       center_x: f32,
-                                       center_y: f32,
-                                       radius: f32,
-                                       start_angle: f32,
-                                       sweep_angle: f32| {
-                    let arc_rect = Rect::from_xywh(
-                        center_x - radius,
-                        center_y - radius,
-                        radius * 2.0,
-                        radius * 2.0,
-                    );
-                    canvas.draw_arc(arc_rect, start_angle, sweep_angle, false, &stroke);
-                };
-
-                if border.corner_radii.top_left > 0. {
-                    // top-left: arc from 180 to 270 degrees.
-                    let center_x = bb.x + border.corner_radii.top_left;
-                    let center_y = bb.y + border.corner_radii.top_left;
-                    draw_corner_arc(
-                        canvas,
-                        center_x,
-                        center_y,
-                        border.corner_radii.top_left,
-                        180.0,
-                        90.0,
-                    );
-                }
+    fn push_clip(&mut self, bounds: BoundingBox, scissor: &Scissor, _clip: &Clip) {
+        // Skia intersects the saved clips itself
+        self.canvas.save();
+        let bounds = clay_to_skia_rect(bounds);
+        if has_corner_radius(&scissor.corner_radii) {
+            let rrect = skia_rrect(bounds, &scissor.corner_radii);
+            self.canvas.clip_rrect(rrect, ClipOp::Intersect, true);
+        } else {
+            self.canvas.clip_rect(bounds, ClipOp::Intersect, false);
+        }
+    }
 
-                if border.corner_radii.top_right > 0. {
-                    // top-right: arc from 270 to 360 degrees.
-                    let center_x = bb.x + bb.width - border.corner_radii.top_right;
-                    let center_y = bb.y + border.corner_radii.top_right;
-                    draw_corner_arc(
-                        canvas,
-                        center_x,
-                        center_y,
-                        border.corner_radii.top_right,
-                        270.0,
-                        90.0,
-                    );
-                }
+    fn pop_clip(&mut self, _clip: Option<&Clip>) {
+        self.canvas.restore();
+    }
 
-                if border.corner_radii.bottom_left > 0. {
-                    // bottom-left: arc from 90 to 180 degrees.
-                    let center_x = bb.x + border.corner_radii.bottom_left;
-                    let center_y = bb.y + bb.height - border.corner_radii.bottom_left;
-                    draw_corner_arc(
-                        canvas,
-                        center_x,
-                        center_y,
-                        border.corner_radii.bottom_left,
-                        90.0,
-                        90.0,
-                    );
-                }
+    fn custom(
+        &mut self,
+        command: &RenderCommand<'a, Image, CustomElementData>,
+        custom: &Custom<'a, CustomElementData>,
+    ) {
+        (self.render_custom_element)(command, custom, self.canvas)
+    }
+}
 
-                if border.corner_radii.bottom_right > 0. {
-                    // bottom-right: arc from 0 to 90 degrees.
-                    let center_x = bb.x + bb.width - border.corner_radii.bottom_right;
-                    let center_y = bb.y + bb.height - border.corner_radii.bottom_right;
-                    draw_corner_arc(
-                        canvas,
-                        center_x,
-                        center_y,
-                        border.corner_radii.bottom_right,
-                        0.0,
-                        90.0,
-                    );
-                }
-            }
-            RenderCommandConfig::Custom(ref custom) => {
-                render_custom_element(&command, custom, canvas)
-            }
-            RenderCommandConfig::None() => {}
-        }
+/// This is a direct* port of Clay's raylib renderer using skia_safe as the drawing API. Text is
+/// placed on the baseline given by the font metrics, the characters missing from a font are drawn
+/// with its fallbacks, and missing fonts fall back to skia's default typeface.
+pub fn clay_skia_render<'a, CustomElementData: 'a>(
+    canvas: &Canvas,
+    render_commands: impl Iterator<Item = RenderCommand<'a, Image, CustomElementData>>,
+    render_custom_element: impl FnMut(
+        &RenderCommand<'a, Image, CustomElementData>,
+        &Custom<'a, CustomElementData>,
+        &Canvas,
+    ),
+    fonts: &FontRegistry<Typeface>,
+) {
+    SkiaRenderer {
+        canvas,
+        fonts,
+        render_custom_element,
     }
+    .render(render_commands);
+}
+
+/// Renders the commands into a one page PDF document of `size` points.
+pub fn clay_skia_render_pdf<'a, CustomElementData: 'a>(
+    size: Dimensions,
+    render_commands: impl Iterator<Item = RenderCommand<'a, Image, CustomElementData>>,
+    render_custom_element: impl FnMut(
+        &RenderCommand<'a, Image, CustomElementData>,
+        &Custom<'a, CustomElementData>,
+        &Canvas,
+    ),
+    fonts: &FontRegistry<Typeface>,
+) -> Vec<u8> {
+    let mut pdf = Vec::new();
+    let document = skia_safe::pdf::new_document(&mut pdf, None);
+    let mut page = document.begin_page(Size::new(size.width, size.height), None);
+    clay_skia_render(page.canvas(), render_commands, render_custom_element, fonts);
+    page.end_page().close();
+    pdf
+}
+
+/// Renders the commands into an SVG document of `size` pixels.
+pub fn clay_skia_render_svg<'a, CustomElementData: 'a>(
+    size: Dimensions,
+    render_commands: impl Iterator<Item = RenderCommand<'a, Image, CustomElementData>>,
+    render_custom_element: impl FnMut(
+        &RenderCommand<'a, Image, CustomElementData>,
+        &Custom<'a, CustomElementData>,
+        &Canvas,
+    ),
+    fonts: &FontRegistry<Typeface>,
+) -> String {
+    let canvas = skia_safe::svg::Canvas::new(Rect::from_wh(size.width, size.height), None);
+    clay_skia_render(&canvas, render_commands, render_custom_element, fonts);
+    String::from_utf8_lossy(canvas.end().as_bytes()).into_owned()
 }
 
 pub type SkiaClayScope<'clay, 'render, CustomElements> =
@@ -292,14 +391,27 @@ pub fn get_source_dimensions_from_skia_image(image: &Image) -> Dimensions {
 }
 
 pub fn create_measure_text_function(
-    fonts: &'static [&Typeface],
+    fonts: &'static FontRegistry<Typeface>,
 ) -> impl Fn(&str, &TextConfig) -> Dimensions + 'static {
-    |text, text_config| {
-        let font = Font::new(
-            fonts[text_config.font_id as usize],
-            text_config.font_size as f32,
-        );
-        let width = font.measure_str(text, None).0;
-        (width, font.metrics().1.bottom - font.metrics().1.top).into()
+    skia_measure_text(fonts)
+}
+
+/// Measures text like [`clay_skia_render`] draws it, with the `fonts` given to the renderer.
+pub fn skia_measure_text(
+    fonts: impl AsRef<FontRegistry<Typeface>> + 'static,
+) -> impl Fn(&str, &TextConfig) -> Dimensions + 'static {
+    move |text, text_config| {
+        let fonts = fonts.as_ref();
+        let font_size = text_config.font_size as f32;
+        let letter_spacing = text_config.letter_spacing as f32;
+        let width = font_runs(fonts, text_config.font_id, font_size, text)
+            .iter()
+            .map(|(font, run)| run_width(font, run, letter_spacing))
+            .sum();
+
+        let font = skia_font(fonts.get(text_config.font_id), font_size);
+        let (_, metrics) = font.metrics();
+        let height = metrics.descent - metrics.ascent;
+        (width, height.max(text_config.line_height as f32)).into()
     }
 }
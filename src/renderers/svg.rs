@@ -0,0 +1,428 @@
+use std::fmt::Write;
+
+use crate::clip::Clip;
+use crate::fonts::FontRegistry;
+use crate::math::{BoundingBox, Dimensions};
+use crate::render_commands::{
+    Border, CornerRadii, Custom, Image, Rectangle, RenderCommand, Renderer, Scissor, Text,
+};
+use crate::Color;
+
+use super::{escape_xml, font_families};
+
+/// Formats a paint attribute, `fill` or `stroke`, with its opacity if the color isn't opaque.
+fn paint(attribute: &str, color: Color) -> String {
+    let mut paint = format!(
+        r#" {attribute}="rgb({},{},{})""#,
+        color.r.round(),
+        color.g.round(),
+        color.b.round()
+    );
+    if color.a < 255. {
+        write!(
+            paint,
+            r#" {attribute}-opacity="{}""#,
+            (color.a / 255.).max(0.)
+        )
+        .unwrap();
+    }
+    paint
+}
+
+fn is_uniform(radii: &CornerRadii) -> bool {
+    radii.top_left == radii.top_right
+        && radii.top_left == radii.bottom_left
+        && radii.top_left == radii.bottom_right
+}
+
+/// Returns a `<rect>` or `<path>` shape of the rounded rectangle, without closing the tag.
+fn rounded_rect(rect: BoundingBox, radii: &CornerRadii) -> String {
+    let max = rect.width.min(rect.height) / 2.;
+    let clamp = |radius: f32| radius.clamp(0., max.max(0.));
+
+    if is_uniform(radii) {
+        let radius = clamp(radii.top_left);
+        let mut shape = format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}""#,
+            rect.x, rect.y, rect.width, rect.height
+        );
+        if radius > 0. {
+            write!(shape, r#" rx="{radius}""#).unwrap();
+        }
+        return shape;
+    }
+
+    let (top_left, top_right) = (clamp(radii.top_left), clamp(radii.top_right));
+    let (bottom_left, bottom_right) = (clamp(radii.bottom_left), clamp(radii.bottom_right));
+    let (right, bottom) = (rect.x + rect.width, rect.y + rect.height);
+    format!(
+        r#"<path d="M{} {} H{} A{top_right} {top_right} 0 0 1 {right} {} V{} A{bottom_right} {bottom_right} 0 0 1 {} {bottom} H{} A{bottom_left} {bottom_left} 0 0 1 {} {} V{} A{top_left} {top_left} 0 0 1 {} {} Z""#,
+        rect.x + top_left,
+        rect.y,
+        right - top_right,
+        rect.y + top_right,
+        bottom - bottom_right,
+        right - bottom_right,
+        rect.x + bottom_left,
+        rect.x,
+        bottom - bottom_left,
+        rect.y + top_left,
+        rect.x + top_left,
+        rect.y,
+    )
+}
+
+/// Writes the sides of a border with different widths as separate stroked paths. Each side runs
+/// between the corner arcs, which take the width of the thickest adjacent side.
+fn write_sides(output: &mut String, rect: BoundingBox, border: &Border) {
+    let width = &border.width;
+    let radii = &border.corner_radii;
+    let stroke = paint("stroke", border.color);
+    let (left, top) = (rect.x, rect.y);
+    let (right, bottom) = (rect.x + rect.width, rect.y + rect.height);
+
+    let sides = [
+        (
+            width.top,
+            top + width.top as f32 / 2.,
+            true,
+            radii.top_left,
+            radii.top_right,
+        ),
+        (
+            width.bottom,
+            bottom - width.bottom as f32 / 2.,
+            true,
+            radii.bottom_left,
+            radii.bottom_right,
+        ),
+        (
+            width.left,
+            left + width.left as f32 / 2.,
+            false,
+            radii.top_left,
+            radii.bottom_left,
+        ),
+        (
+            width.right,
+            right - width.right as f32 / 2.,
+            false,
+            radii.top_right,
+            radii.bottom_right,
+        ),
+    ];
+    for (side_width, position, horizontal, start_radius, end_radius) in sides {
+        if side_width == 0 {
+            continue;
+        }
+        let d = if horizontal {
+            format!(
+                "M{} {position} H{}",
+                left + start_radius,
+                right - end_radius
+            )
+        } else {
+            format!(
+                "M{position} {} V{}",
+                top + start_radius,
+                bottom - end_radius
+            )
+        };
+        writeln!(
+            output,
+            r#"<path d="{d}" fill="none"{stroke} stroke-width="{side_width}"/>"#
+        )
+        .unwrap();
+    }
+
+    let corners = [
+        (radii.top_left, width.top.max(width.left), left, top, 1., 1.),
+        (
+            radii.top_right,
+            width.top.max(width.right),
+            right,
+            top,
+            -1.,
+            1.,
+        ),
+        (
+            radii.bottom_left,
+            width.bottom.max(width.left),
+            left,
+            bottom,
+            1.,
+            -1.,
+        ),
+        (
+            radii.bottom_right,
+            width.bottom.max(width.right),
+            right,
+            bottom,
+            -1.,
+            -1.,
+        ),
+    ];
+    for (radius, corner_width, x, y, dx, dy) in corners {
+        if radius <= 0. || corner_width == 0 {
+            continue;
+        }
+        // Arc centered on the stroke, from the vertical side to the horizontal side
+        let inset = corner_width as f32 / 2.;
+        let arc_radius = (radius - inset).max(0.);
+        let sweep = if dx * dy > 0. { 1 } else { 0 };
+        writeln!(
+            output,
+            r#"<path d="M{} {} A{arc_radius} {arc_radius} 0 0 {sweep} {} {}" fill="none"{stroke} stroke-width="{corner_width}"/>"#,
+            x + dx * inset,
+            y + dy * radius,
+            x + dx * radius,
+            y + dy * inset,
+        )
+        .unwrap();
+    }
+}
+
+struct SvgRenderer<'f, F> {
+    output: String,
+    fonts: &'f FontRegistry<&'f str>,
+    image_href: F,
+    clip_count: usize,
+}
+
+impl<'a, ImageElementData: 'a, CustomElementData: 'a, F>
+    Renderer<'a, ImageElementData, CustomElementData> for SvgRenderer<'_, F>
+where
+    F: FnMut(&ImageElementData) -> String,
+{
+    fn rectangle(&mut self, bounds: BoundingBox, rect: &Rectangle) {
+        writeln!(
+            self.output,
+            "{}{}/>",
+            rounded_rect(bounds, &rect.corner_radii),
+            paint("fill", rect.color)
+        )
+        .unwrap();
+    }
+
+    fn border(&mut self, bounds: BoundingBox, border: &Border) {
+        let width = &border.width;
+        let uniform =
+            width.left == width.right && width.left == width.top && width.left == width.bottom;
+        if !(uniform && is_uniform(&border.corner_radii)) {
+            write_sides(&mut self.output, bounds, border);
+            return;
+        }
+        if width.left == 0 {
+            return;
+        }
+        // Strokes are centered on the path, keep them inside the bounds
+        let inset = width.left as f32 / 2.;
+        let rect = BoundingBox::new(
+            bounds.x + inset,
+            bounds.y + inset,
+            bounds.width - width.left as f32,
+            bounds.height - width.left as f32,
+        );
+        let radius = (border.corner_radii.top_left - inset).max(0.);
+        let inner_radii = CornerRadii {
+            top_left: radius,
+            top_right: radius,
+            bottom_left: radius,
+            bottom_right: radius,
+        };
+        writeln!(
+            self.output,
+            r#"{} fill="none"{} stroke-width="{}"/>"#,
+            rounded_rect(rect, &inner_radii),
+            paint("stroke", border.color),
+            width.left
+        )
+        .unwrap();
+    }
+
+    fn text(&mut self, bounds: BoundingBox, text: &Text<'a>) {
+        let font = font_families(self.fonts, text.font_id);
+        let font = font.as_deref().unwrap_or("sans-serif");
+        write!(
+            self.output,
+            r#"<text x="{}" y="{}" dominant-baseline="central" font-family="{}" font-size="{}""#,
+            bounds.x,
+            bounds.y + bounds.height / 2.,
+            escape_xml(font),
+            text.font_size
+        )
+        .unwrap();
+        if text.letter_spacing > 0 {
+            write!(self.output, r#" letter-spacing="{}""#, text.letter_spacing).unwrap();
+        }
+        writeln!(
+            self.output,
+            r#"{} xml:space="preserve">{}</text>"#,
+            paint("fill", text.color),
+            escape_xml(text.text)
+        )
+        .unwrap();
+    }
+
+    fn image(&mut self, bounds: BoundingBox, image: &Image<'a, ImageElementData>) {
+        let radii = &image.corner_radii;
+        let rounded = radii.top_left > 0.
+            || radii.top_right > 0.
+            || radii.bottom_left > 0.
+            || radii.bottom_right > 0.;
+        if rounded {
+            self.clip_count += 1;
+            writeln!(
+                self.output,
+                r#"<clipPath id="clip{}">{}/></clipPath>"#,
+                self.clip_count,
+                rounded_rect(bounds, radii)
+            )
+            .unwrap();
+        }
+        write!(
+            self.output,
+            r#"<image x="{}" y="{}" width="{}" height="{}" preserveAspectRatio="none" href="{}""#,
+            bounds.x,
+            bounds.y,
+            bounds.width,
+            bounds.height,
+            escape_xml(&(self.image_href)(image.data))
+        )
+        .unwrap();
+        if rounded {
+            write!(self.output, r#" clip-path="url(#clip{})""#, self.clip_count).unwrap();
+        }
+        writeln!(self.output, "/>").unwrap();
+    }
+
+    fn push_clip(&mut self, bounds: BoundingBox, scissor: &Scissor, _clip: &Clip) {
+        // Nested groups intersect their clip paths
+        self.clip_count += 1;
+        writeln!(
+            self.output,
+            r#"<clipPath id="clip{}">{}/></clipPath>"#,
+            self.clip_count,
+            rounded_rect(bounds, &scissor.corner_radii)
+        )
+        .unwrap();
+        writeln!(
+            self.output,
+            r#"<g clip-path="url(#clip{})">"#,
+            self.clip_count
+        )
+        .unwrap();
+    }
+
+    fn pop_clip(&mut self, _clip: Option<&Clip>) {
+        writeln!(self.output, "</g>").unwrap();
+    }
+
+    fn custom(
+        &mut self,
+        command: &RenderCommand<'a, ImageElementData, CustomElementData>,
+        custom: &Custom<'a, CustomElementData>,
+    ) {
+        writeln!(
+            self.output,
+            "{}{}/>",
+            rounded_rect(command.bounding_box, &custom.corner_radii),
+            paint("fill", custom.background_color)
+        )
+        .unwrap();
+    }
+}
+
+/// Converts render commands to an SVG document of the given dimensions.
+///
+/// `fonts` gives the font family of each font id, followed by the families of its fallbacks, and
+/// `image_href` the URL or data URI of the image elements. Custom elements draw their background
+/// color only.
+pub fn clay_svg_render<'a, ImageElementData: 'a, CustomElementData: 'a>(
+    dimensions: Dimensions,
+    render_commands: impl Iterator<Item = RenderCommand<'a, ImageElementData, CustomElementData>>,
+    fonts: &FontRegistry<&str>,
+    image_href: impl FnMut(&ImageElementData) -> String,
+) -> String {
+    let mut renderer = SvgRenderer {
+        output: String::new(),
+        fonts,
+        image_href,
+        clip_count: 0,
+    };
+    writeln!(
+        renderer.output,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+        dimensions.width, dimensions.height
+    )
+    .unwrap();
+    renderer.render(render_commands);
+    writeln!(renderer.output, "</svg>").unwrap();
+    renderer.output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::{examples, LayoutHarness};
+
+    fn render(
+        harness: &mut LayoutHarness,
+        layout: impl FnOnce(&mut crate::ClayLayoutScope<'_, '_, (), ()>),
+    ) -> String {
+        let clay = harness.clay();
+        let mut scope = clay.begin::<(), ()>();
+        layout(&mut scope);
+        clay_svg_render(
+            (800., 600.).into(),
+            scope.end(),
+            &[("body", "Inter"), ("code", "Fira \"Code\"")]
+                .into_iter()
+                .collect(),
+            |_| "image.png".into(),
+        )
+    }
+
+    #[test]
+    fn example_layouts() {
+        let mut harness = LayoutHarness::new();
+
+        let svg = render(&mut harness, examples::basic_rectangle);
+        harness.assert_golden("basic_rectangle.svg", &svg);
+
+        let svg = render(&mut harness, examples::dashboard);
+        assert!(svg.contains("Edit &amp; &lt;View&gt;"));
+        assert_eq!(svg.matches("<g ").count(), svg.matches("</g>").count());
+        harness.assert_golden("dashboard.svg", &svg);
+    }
+
+    #[test]
+    fn per_side_border() {
+        let rect = BoundingBox::new(0., 0., 100., 50.);
+        let border = crate::render_commands::Border {
+            color: Color::rgba(0., 0., 0., 127.5),
+            corner_radii: CornerRadii {
+                top_left: 10.,
+                top_right: 0.,
+                bottom_left: 0.,
+                bottom_right: 0.,
+            },
+            width: crate::render_commands::BorderWidth {
+                left: 4,
+                right: 0,
+                top: 2,
+                bottom: 0,
+                between_children: 0,
+            },
+        };
+        let mut output = String::new();
+        write_sides(&mut output, rect, &border);
+
+        assert_eq!(
+            output,
+            "<path d=\"M10 1 H100\" fill=\"none\" stroke=\"rgb(0,0,0)\" stroke-opacity=\"0.5\" stroke-width=\"2\"/>\n\
+             <path d=\"M2 10 V50\" fill=\"none\" stroke=\"rgb(0,0,0)\" stroke-opacity=\"0.5\" stroke-width=\"4\"/>\n\
+             <path d=\"M2 10 A8 8 0 0 1 10 2\" fill=\"none\" stroke=\"rgb(0,0,0)\" stroke-opacity=\"0.5\" stroke-width=\"4\"/>\n"
+        );
+    }
+}
@@ -0,0 +1,497 @@
+use crate::clip::Clip;
+use crate::fonts::FontRegistry;
+use crate::math::{BoundingBox, Dimensions};
+use crate::measure::fontdue_measure_text;
+use crate::render_commands::{
+    Border, CornerRadii, Custom, Image, Rectangle, RenderCommand, Renderer, Scissor, Text,
+};
+use crate::renderers::{border_inner_rect, rounded_rect, PathSink};
+#[cfg(feature = "shaping")]
+use crate::shaping::shape_text;
+use crate::text::TextConfig;
+use crate::{ClayLayoutScope, Color as ClayColor};
+use fontdue::Font;
+#[cfg(feature = "shaping")]
+use rustybuzz::{ttf_parser::GlyphId, Face};
+use tiny_skia::{
+    FillRule, FilterQuality, Mask, Paint, Path, PathBuilder, Pattern, Pixmap, PixmapPaint,
+    PremultipliedColorU8, SpreadMode, Transform,
+};
+
+pub fn clay_to_tiny_skia_color(color: ClayColor) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba8(
+        color.r.round() as u8,
+        color.g.round() as u8,
+        color.b.round() as u8,
+        color.a.round() as u8,
+    )
+}
+
+fn solid_paint(color: ClayColor) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color(clay_to_tiny_skia_color(color));
+    paint.anti_alias = true;
+    paint
+}
+
+impl PathSink for PathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        PathBuilder::move_to(self, x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        PathBuilder::line_to(self, x, y);
+    }
+
+    fn cubic_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        PathBuilder::cubic_to(self, x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        PathBuilder::close(self);
+    }
+}
+
+/// Builds a rectangle path with rounded corners, the radii are clamped to half the size of the
+/// rectangle.
+fn rounded_rect_path(rect: BoundingBox, radii: &CornerRadii) -> Option<Path> {
+    if rect.width <= 0. || rect.height <= 0. {
+        return None;
+    }
+    let mut path = PathBuilder::new();
+    rounded_rect(&mut path, rect, radii);
+    path.finish()
+}
+
+/// Rasterizes text with fontdue, starting at the top left corner of `rect`. The characters
+/// missing from the font of the text are drawn with its fallbacks, on the same baseline.
+fn draw_text(
+    pixmap: &mut Pixmap,
+    rect: BoundingBox,
+    text: &Text,
+    fonts: &FontRegistry<Font>,
+    mask: Option<&Mask>,
+) {
+    let font_size = text.font_size as f32;
+    let Some(font) = fonts.get(text.font_id) else {
+        return;
+    };
+    let Some(metrics) = font.horizontal_line_metrics(font_size) else {
+        return;
+    };
+    // Center the line in the line height given by the layout
+    let baseline =
+        rect.y + metrics.ascent + ((text.line_height as f32 - metrics.new_line_size) / 2.).max(0.);
+
+    let color = text.color;
+    let alpha = color.a / 255.;
+    let mut x = rect.x;
+    for (font, run) in fonts.runs(text.font_id, text.text) {
+        let mut previous = None;
+        for character in run.chars() {
+            if let Some(previous) = previous {
+                x += font
+                    .horizontal_kern(previous, character, font_size)
+                    .unwrap_or(0.);
+            }
+            previous = Some(character);
+
+            let (glyph, coverage) = font.rasterize(character, font_size);
+            if glyph.width > 0 && glyph.height > 0 {
+                if let Some(mut bitmap) = Pixmap::new(glyph.width as u32, glyph.height as u32) {
+                    for (pixel, coverage) in bitmap.pixels_mut().iter_mut().zip(&coverage) {
+                        let a = alpha * *coverage as f32 / 255.;
+                        *pixel = PremultipliedColorU8::from_rgba(
+                            (color.r * a).round() as u8,
+                            (color.g * a).round() as u8,
+                            (color.b * a).round() as u8,
+                            (a * 255.).round() as u8,
+                        )
+                        .unwrap();
+                    }
+                    pixmap.draw_pixmap(
+                        (x + glyph.xmin as f32).round() as i32,
+                        (baseline - glyph.ymin as f32 - glyph.height as f32).round() as i32,
+                        bitmap.as_ref(),
+                        &PixmapPaint::default(),
+                        Transform::identity(),
+                        mask,
+                    );
+                }
+            }
+
+            x += glyph.advance_width + text.letter_spacing as f32;
+        }
+    }
+}
+
+/// Builds a tiny-skia path from the outline of a glyph, in font units.
+#[cfg(feature = "shaping")]
+struct GlyphOutline(PathBuilder);
+
+#[cfg(feature = "shaping")]
+impl rustybuzz::ttf_parser::OutlineBuilder for GlyphOutline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.0.quad_to(x1, y1, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.0.cubic_to(x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.0.close();
+    }
+}
+
+/// Fills the outlines of the glyphs of the shaped line of `text`, starting at the top left
+/// corner of `rect`, as measured by [`rustybuzz_measure_text`](crate::measure::rustybuzz_measure_text).
+#[cfg(feature = "shaping")]
+fn draw_shaped_text(
+    pixmap: &mut Pixmap,
+    rect: BoundingBox,
+    text: &Text,
+    fonts: &FontRegistry<Face>,
+    mask: Option<&Mask>,
+) {
+    let line = shape_text(fonts, text);
+    let paint = solid_paint(text.color);
+    let baseline = rect.y + line.baseline;
+    for run in &line.runs {
+        let scale = text.font_size as f32 / run.font.units_per_em() as f32;
+        for glyph in &run.glyphs {
+            let mut outline = GlyphOutline(PathBuilder::new());
+            let id = GlyphId(glyph.glyph_id);
+            if run.font.outline_glyph(id, &mut outline).is_none() {
+                continue;
+            }
+            let Some(path) = outline.0.finish() else {
+                continue;
+            };
+            // Font units go up from the baseline
+            let transform =
+                Transform::from_row(scale, 0., 0., -scale, rect.x + glyph.x, baseline + glyph.y);
+            pixmap.fill_path(&path, &paint, FillRule::Winding, transform, mask);
+        }
+    }
+}
+
+/// Fonts of the text, rasterized by fontdue or shaped by rustybuzz.
+enum TextFonts<'f> {
+    Fontdue(&'f FontRegistry<Font>),
+    #[cfg(feature = "shaping")]
+    Shaped(&'f FontRegistry<Face<'f>>),
+}
+
+struct TinySkiaRenderer<'p, 'f> {
+    pixmap: &'p mut Pixmap,
+    fonts: TextFonts<'f>,
+    /// Clip masks of the nested scissors, each one intersected with its parent
+    clips: Vec<Mask>,
+}
+
+impl TinySkiaRenderer<'_, '_> {
+    fn fill(&mut self, bounds: BoundingBox, radii: &CornerRadii, color: ClayColor) {
+        if let Some(path) = rounded_rect_path(bounds, radii) {
+            self.pixmap.fill_path(
+                &path,
+                &solid_paint(color),
+                FillRule::Winding,
+                Transform::identity(),
+                self.clips.last(),
+            );
+        }
+    }
+}
+
+impl<'a, CustomElementData: 'a> Renderer<'a, Pixmap, CustomElementData>
+    for TinySkiaRenderer<'_, '_>
+{
+    fn rectangle(&mut self, bounds: BoundingBox, rect: &Rectangle) {
+        self.fill(bounds, &rect.corner_radii, rect.color);
+    }
+
+    fn border(&mut self, bounds: BoundingBox, border: &Border) {
+        let (inner, inner_radii) = border_inner_rect(bounds, border);
+        let Some(outer) = rounded_rect_path(bounds, &border.corner_radii) else {
+            return;
+        };
+        let mut path = PathBuilder::new();
+        path.push_path(&outer);
+        if let Some(inner) = rounded_rect_path(inner, &inner_radii) {
+            path.push_path(&inner);
+        }
+        if let Some(path) = path.finish() {
+            self.pixmap.fill_path(
+                &path,
+                &solid_paint(border.color),
+                FillRule::EvenOdd,
+                Transform::identity(),
+                self.clips.last(),
+            );
+        }
+    }
+
+    fn text(&mut self, bounds: BoundingBox, text: &Text<'a>) {
+        let mask = self.clips.last();
+        match self.fonts {
+            TextFonts::Fontdue(fonts) => draw_text(self.pixmap, bounds, text, fonts, mask),
+            #[cfg(feature = "shaping")]
+            TextFonts::Shaped(fonts) => draw_shaped_text(self.pixmap, bounds, text, fonts, mask),
+        }
+    }
+
+    fn image(&mut self, bounds: BoundingBox, image: &Image<'a, Pixmap>) {
+        let source = image.data;
+        if source.width() == 0 || source.height() == 0 {
+            return;
+        }
+        let Some(path) = rounded_rect_path(bounds, &image.corner_radii) else {
+            return;
+        };
+        let transform = Transform::from_row(
+            bounds.width / source.width() as f32,
+            0.,
+            0.,
+            bounds.height / source.height() as f32,
+            bounds.x,
+            bounds.y,
+        );
+        let paint = Paint {
+            shader: Pattern::new(
+                source.as_ref(),
+                SpreadMode::Pad,
+                FilterQuality::Bilinear,
+                1.,
+                transform,
+            ),
+            anti_alias: true,
+            ..Default::default()
+        };
+        self.pixmap.fill_path(
+            &path,
+            &paint,
+            FillRule::Winding,
+            Transform::identity(),
+            self.clips.last(),
+        );
+    }
+
+    fn push_clip(&mut self, bounds: BoundingBox, scissor: &Scissor, _clip: &Clip) {
+        let path = rounded_rect_path(bounds, &scissor.corner_radii);
+        let clip = match (self.clips.last(), path) {
+            (Some(parent), Some(path)) => {
+                let mut clip = parent.clone();
+                clip.intersect_path(&path, FillRule::Winding, true, Transform::identity());
+                clip
+            }
+            (None, Some(path)) => {
+                let mut clip = Mask::new(self.pixmap.width(), self.pixmap.height()).unwrap();
+                clip.fill_path(&path, FillRule::Winding, true, Transform::identity());
+                clip
+            }
+            // An empty scissor hides everything until its end
+            (_, None) => Mask::new(self.pixmap.width(), self.pixmap.height()).unwrap(),
+        };
+        self.clips.push(clip);
+    }
+
+    fn pop_clip(&mut self, _clip: Option<&Clip>) {
+        self.clips.pop();
+    }
+
+    fn custom(
+        &mut self,
+        command: &RenderCommand<'a, Pixmap, CustomElementData>,
+        custom: &Custom<'a, CustomElementData>,
+    ) {
+        self.fill(
+            command.bounding_box,
+            &custom.corner_radii,
+            custom.background_color,
+        );
+    }
+}
+
+/// Renders the commands into `pixmap` without any GPU or windowing system, using the `fonts` of
+/// the font ids of the text elements, as measured by [`fontdue_measure_text`]. Custom elements
+/// draw their background color only.
+pub fn clay_tiny_skia_render<'a, CustomElementData: 'a>(
+    pixmap: &mut Pixmap,
+    render_commands: impl Iterator<Item = RenderCommand<'a, Pixmap, CustomElementData>>,
+    fonts: &FontRegistry<Font>,
+) {
+    TinySkiaRenderer {
+        pixmap,
+        fonts: TextFonts::Fontdue(fonts),
+        clips: Vec::new(),
+    }
+    .render(render_commands);
+}
+
+/// Renders the commands into `pixmap` like [`clay_tiny_skia_render`], shaping the text with
+/// rustybuzz so Arabic letters join and right-to-left runs are displayed in visual order. Text is
+/// measured by [`rustybuzz_measure_text`](crate::measure::rustybuzz_measure_text) with the same
+/// `fonts`.
+#[cfg(feature = "shaping")]
+pub fn clay_tiny_skia_render_shaped<'a, 'f, CustomElementData: 'a>(
+    pixmap: &mut Pixmap,
+    render_commands: impl Iterator<Item = RenderCommand<'a, Pixmap, CustomElementData>>,
+    fonts: &'f FontRegistry<Face<'f>>,
+) {
+    TinySkiaRenderer {
+        pixmap,
+        fonts: TextFonts::Shaped(fonts),
+        clips: Vec::new(),
+    }
+    .render(render_commands);
+}
+
+/// Renders the commands into a new pixmap filled with `background` and encodes it as PNG, to
+/// generate thumbnails on headless servers.
+pub fn clay_tiny_skia_render_png<'a, CustomElementData: 'a>(
+    dimensions: Dimensions,
+    background: ClayColor,
+    render_commands: impl Iterator<Item = RenderCommand<'a, Pixmap, CustomElementData>>,
+    fonts: &FontRegistry<Font>,
+) -> std::io::Result<Vec<u8>> {
+    let mut pixmap = Pixmap::new(dimensions.width as u32, dimensions.height as u32)
+        .ok_or_else(|| std::io::Error::other("empty dimensions"))?;
+    pixmap.fill(clay_to_tiny_skia_color(background));
+    clay_tiny_skia_render(&mut pixmap, render_commands, fonts);
+    pixmap.encode_png().map_err(std::io::Error::other)
+}
+
+pub type TinySkiaClayScope<'clay, 'render, CustomElements> =
+    ClayLayoutScope<'clay, 'render, Pixmap, CustomElements>;
+
+pub fn get_source_dimensions_from_pixmap(pixmap: &Pixmap) -> Dimensions {
+    (pixmap.width() as f32, pixmap.height() as f32).into()
+}
+
+pub fn create_measure_text_function(
+    fonts: impl AsRef<FontRegistry<Font>> + 'static,
+) -> impl Fn(&str, &TextConfig) -> Dimensions + 'static {
+    fontdue_measure_text(fonts)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{layout::Sizing, Clay, Declaration};
+
+    fn pixel(pixmap: &Pixmap, x: u32, y: u32) -> (u8, u8, u8, u8) {
+        let pixel = pixmap.pixel(x, y).unwrap().demultiply();
+        (pixel.red(), pixel.green(), pixel.blue(), pixel.alpha())
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn rectangles_borders_and_clips() {
+        let mut clay = Clay::new(Dimensions::new(100., 100.));
+        let mut scope = clay.begin::<Pixmap, ()>();
+        scope.with(Declaration::new()
+            .layout()
+                .width(Sizing::Fixed(60.))
+                .height(Sizing::Fixed(60.))
+                .end()
+            .corner_radius().all(20.).end()
+            .border()
+                .all_directions(4)
+                .color(ClayColor::rgb(0., 0., 255.))
+                .end()
+            .clip(true, true, (0., 0.).into())
+            .background_color(ClayColor::rgb(255., 0., 0.)), |clay|
+        {
+            clay.with(Declaration::new()
+                .layout()
+                    .width(Sizing::Fixed(100.))
+                    .height(Sizing::Fixed(100.))
+                    .end()
+                .background_color(ClayColor::rgb(0., 255., 0.)), |_| {});
+        });
+
+        let mut pixmap = Pixmap::new(100, 100).unwrap();
+        clay_tiny_skia_render(&mut pixmap, scope.end(), &FontRegistry::new());
+
+        // The child is clipped by the rounded scissor of its parent
+        assert_eq!(pixel(&pixmap, 30, 30), (0, 255, 0, 255));
+        assert_eq!(pixel(&pixmap, 1, 1).3, 0);
+        assert_eq!(pixel(&pixmap, 80, 80).3, 0);
+        // The border is drawn over the child
+        assert_eq!(pixel(&pixmap, 30, 1), (0, 0, 255, 255));
+        assert_eq!(pixel(&pixmap, 58, 30), (0, 0, 255, 255));
+    }
+
+    #[test]
+    fn png_export() {
+        let mut clay = Clay::new(Dimensions::new(8., 8.));
+        let scope = clay.begin::<Pixmap, ()>();
+        let png = clay_tiny_skia_render_png::<()>(
+            Dimensions::new(8., 8.),
+            ClayColor::rgb(255., 255., 255.),
+            scope.end(),
+            &FontRegistry::new(),
+        )
+        .unwrap();
+
+        let decoded = Pixmap::decode_png(&png).unwrap();
+        assert_eq!(
+            get_source_dimensions_from_pixmap(&decoded),
+            Dimensions::new(8., 8.)
+        );
+        assert_eq!(pixel(&decoded, 4, 4), (255, 255, 255, 255));
+    }
+
+    #[cfg(feature = "shaping")]
+    #[rustfmt::skip]
+    #[test]
+    fn shaped_text_stays_in_its_bounds() {
+        use crate::measure::rustybuzz_measure_text;
+
+        const TUFFY: &[u8] = include_bytes!("../../tests/fonts/Tuffy.ttf");
+        const HEBREW: &[u8] = include_bytes!("../../tests/fonts/NotoSansHebrew.ttf");
+        let mut fonts = FontRegistry::new();
+        let tuffy = fonts.add("Tuffy", Face::from_slice(TUFFY, 0).unwrap());
+        let hebrew = fonts.add("Noto Sans Hebrew", Face::from_slice(HEBREW, 0).unwrap());
+        fonts.set_fallbacks(tuffy, [hebrew]);
+
+        let mut clay = Clay::new(Dimensions::new(200., 40.));
+        clay.set_measure_text_function(rustybuzz_measure_text(fonts.clone()));
+        let mut scope = clay.begin::<Pixmap, ()>();
+        scope.with(Declaration::new()
+            .layout()
+                .padding(crate::layout::Padding::all(10))
+                .end(), |clay|
+        {
+            clay.text("abc שלום", TextConfig::new()
+                .font_size(16)
+                .color(ClayColor::rgb(0., 0., 0.))
+                .end());
+        });
+        let commands: Vec<_> = scope.end().collect();
+        let bounds = commands.last().unwrap().bounding_box;
+
+        let mut pixmap = Pixmap::new(200, 40).unwrap();
+        clay_tiny_skia_render_shaped(&mut pixmap, commands.into_iter(), &fonts);
+
+        let inked: Vec<_> = (0..40)
+            .flat_map(|y| (0..200).map(move |x| (x, y)))
+            .filter(|&(x, y)| pixel(&pixmap, x, y).3 > 0)
+            .collect();
+        assert!(!inked.is_empty());
+        for (x, y) in inked {
+            let (x, y) = (x as f32, y as f32);
+            assert!(x >= bounds.x - 1. && x < bounds.x + bounds.width + 1., "{x} {bounds:?}");
+            assert!(y >= bounds.y - 1. && y < bounds.y + bounds.height + 1., "{y} {bounds:?}");
+        }
+    }
+}
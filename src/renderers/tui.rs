@@ -0,0 +1,444 @@
+use std::fmt::Write;
+
+use crate::clip::Clip;
+use crate::math::{BoundingBox, Dimensions};
+use crate::render_commands::{
+    Border, Custom, Image, Rectangle, RenderCommand, Renderer, Scissor, Text,
+};
+use crate::text::TextConfig;
+use crate::Color;
+
+/// A character cell of a [`TuiGrid`]. Colors are `None` to keep the colors of the terminal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub character: char,
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            character: ' ',
+            foreground: None,
+            background: None,
+        }
+    }
+}
+
+/// Range of cells, the end column and row being excluded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CellRect {
+    left: usize,
+    top: usize,
+    right: usize,
+    bottom: usize,
+}
+
+impl CellRect {
+    fn intersect(&self, other: &Self) -> Self {
+        Self {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom),
+        }
+    }
+
+    fn contains(&self, column: usize, row: usize) -> bool {
+        (self.left..self.right).contains(&column) && (self.top..self.bottom).contains(&row)
+    }
+}
+
+/// Character cell grid the render commands are rasterized into by [`clay_tui_render`].
+///
+/// Every cell covers `cell_width` by `cell_height` layout units, which must match the measure
+/// function given by [`create_measure_text_function`].
+#[derive(Debug, Clone)]
+pub struct TuiGrid {
+    cells: Vec<Vec<Cell>>,
+    cell_width: f32,
+    cell_height: f32,
+}
+
+impl TuiGrid {
+    /// Creates an empty grid with cells of one by one layout unit.
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            cells: vec![vec![Cell::default(); columns]; rows],
+            cell_width: 1.,
+            cell_height: 1.,
+        }
+    }
+
+    /// Sets the size of a cell in layout units.
+    pub fn cell_size(&mut self, width: f32, height: f32) -> &mut Self {
+        self.cell_width = width;
+        self.cell_height = height;
+        self
+    }
+
+    /// Returns the layout dimensions covered by the grid, to pass to
+    /// [`Clay::set_layout_dimensions`](crate::Clay::set_layout_dimensions).
+    pub fn layout_dimensions(&self) -> Dimensions {
+        Dimensions::new(
+            self.columns() as f32 * self.cell_width,
+            self.rows() as f32 * self.cell_height,
+        )
+    }
+
+    pub fn columns(&self) -> usize {
+        self.cells.first().map_or(0, Vec::len)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn cells(&self) -> &[Vec<Cell>] {
+        &self.cells
+    }
+
+    pub fn into_cells(self) -> Vec<Vec<Cell>> {
+        self.cells
+    }
+
+    /// Resets every cell to a space without colors.
+    pub fn clear(&mut self) {
+        for row in &mut self.cells {
+            row.fill(Cell::default());
+        }
+    }
+
+    /// Returns the characters of the grid, one line per row.
+    pub fn to_text(&self) -> String {
+        let mut output = String::new();
+        for row in &self.cells {
+            output.extend(row.iter().map(|cell| cell.character));
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Returns the grid as lines of text with 24 bit ANSI color escape sequences, resetting the
+    /// colors at the end of every line.
+    pub fn to_ansi(&self) -> String {
+        let mut output = String::new();
+        for row in &self.cells {
+            let mut foreground = None;
+            let mut background = None;
+            for cell in row {
+                if cell.foreground != foreground {
+                    write_sgr(&mut output, cell.foreground, 38);
+                    foreground = cell.foreground;
+                }
+                if cell.background != background {
+                    write_sgr(&mut output, cell.background, 48);
+                    background = cell.background;
+                }
+                output.push(cell.character);
+            }
+            output.push_str("\x1b[0m\n");
+        }
+        output
+    }
+
+    /// Converts a bounding box to the cells it covers, rounding to the nearest cell edges.
+    fn cell_rect(&self, bounds: BoundingBox) -> CellRect {
+        let column = |x: f32| ((x / self.cell_width).round().max(0.) as usize).min(self.columns());
+        let row = |y: f32| ((y / self.cell_height).round().max(0.) as usize).min(self.rows());
+        CellRect {
+            left: column(bounds.x),
+            top: row(bounds.y),
+            right: column(bounds.x + bounds.width),
+            bottom: row(bounds.y + bounds.height),
+        }
+    }
+
+    fn full_rect(&self) -> CellRect {
+        CellRect {
+            left: 0,
+            top: 0,
+            right: self.columns(),
+            bottom: self.rows(),
+        }
+    }
+
+    fn fill(&mut self, rect: CellRect, color: Color) {
+        if color.a <= 0. {
+            return;
+        }
+        for row in &mut self.cells[rect.top..rect.bottom.max(rect.top)] {
+            for cell in &mut row[rect.left..rect.right.max(rect.left)] {
+                *cell = Cell {
+                    character: ' ',
+                    foreground: None,
+                    background: Some(color),
+                };
+            }
+        }
+    }
+
+    fn put(&mut self, clip: &CellRect, column: usize, row: usize, character: char, color: Color) {
+        if clip.contains(column, row) {
+            let cell = &mut self.cells[row][column];
+            cell.character = character;
+            cell.foreground = Some(color);
+        }
+    }
+
+    fn draw_border(&mut self, rect: CellRect, clip: &CellRect, border: &Border) {
+        if rect.right <= rect.left || rect.bottom <= rect.top {
+            return;
+        }
+        let width = &border.width;
+        let (left, top) = (rect.left, rect.top);
+        let (right, bottom) = (rect.right - 1, rect.bottom - 1);
+        let color = border.color;
+
+        if width.top > 0 {
+            for column in left..=right {
+                self.put(clip, column, top, '─', color);
+            }
+        }
+        if width.bottom > 0 {
+            for column in left..=right {
+                self.put(clip, column, bottom, '─', color);
+            }
+        }
+        if width.left > 0 {
+            for row in top..=bottom {
+                self.put(clip, left, row, '│', color);
+            }
+        }
+        if width.right > 0 {
+            for row in top..=bottom {
+                self.put(clip, right, row, '│', color);
+            }
+        }
+
+        let radii = &border.corner_radii;
+        let corners = [
+            (
+                width.top > 0 && width.left > 0,
+                radii.top_left > 0.,
+                left,
+                top,
+                '┌',
+                '╭',
+            ),
+            (
+                width.top > 0 && width.right > 0,
+                radii.top_right > 0.,
+                right,
+                top,
+                '┐',
+                '╮',
+            ),
+            (
+                width.bottom > 0 && width.left > 0,
+                radii.bottom_left > 0.,
+                left,
+                bottom,
+                '└',
+                '╰',
+            ),
+            (
+                width.bottom > 0 && width.right > 0,
+                radii.bottom_right > 0.,
+                right,
+                bottom,
+                '┘',
+                '╯',
+            ),
+        ];
+        for (present, rounded, column, row, square, round) in corners {
+            if present {
+                self.put(
+                    clip,
+                    column,
+                    row,
+                    if rounded { round } else { square },
+                    color,
+                );
+            }
+        }
+    }
+}
+
+fn write_sgr(output: &mut String, color: Option<Color>, code: u8) {
+    match color {
+        Some(color) => write!(
+            output,
+            "\x1b[{code};2;{};{};{}m",
+            color.r.round() as u8,
+            color.g.round() as u8,
+            color.b.round() as u8
+        )
+        .unwrap(),
+        None => write!(output, "\x1b[{}m", code + 1).unwrap(),
+    }
+}
+
+struct TuiRenderer<'g> {
+    grid: &'g mut TuiGrid,
+    /// Cells of the nested scissors, each one intersected with its parent
+    clips: Vec<CellRect>,
+}
+
+impl TuiRenderer<'_> {
+    fn clip(&self) -> CellRect {
+        self.clips
+            .last()
+            .copied()
+            .unwrap_or_else(|| self.grid.full_rect())
+    }
+
+    fn fill(&mut self, bounds: BoundingBox, color: Color) {
+        let rect = self.grid.cell_rect(bounds).intersect(&self.clip());
+        self.grid.fill(rect, color);
+    }
+}
+
+impl<'a, ImageElementData: 'a, CustomElementData: 'a>
+    Renderer<'a, ImageElementData, CustomElementData> for TuiRenderer<'_>
+{
+    fn rectangle(&mut self, bounds: BoundingBox, rectangle: &Rectangle) {
+        self.fill(bounds, rectangle.color);
+    }
+
+    fn border(&mut self, bounds: BoundingBox, border: &Border) {
+        let rect = self.grid.cell_rect(bounds);
+        let clip = self.clip();
+        self.grid.draw_border(rect, &clip, border);
+    }
+
+    fn text(&mut self, bounds: BoundingBox, text: &Text<'a>) {
+        let rect = self.grid.cell_rect(bounds);
+        let clip = self.clip();
+        for (offset, character) in text.text.chars().enumerate() {
+            self.grid
+                .put(&clip, rect.left + offset, rect.top, character, text.color);
+        }
+    }
+
+    fn image(&mut self, bounds: BoundingBox, image: &Image<'a, ImageElementData>) {
+        self.fill(bounds, image.background_color);
+    }
+
+    fn push_clip(&mut self, bounds: BoundingBox, _scissor: &Scissor, _clip: &Clip) {
+        // Rounded to cells before the intersection, like the commands drawn inside
+        let rect = self.grid.cell_rect(bounds).intersect(&self.clip());
+        self.clips.push(rect);
+    }
+
+    fn pop_clip(&mut self, _clip: Option<&Clip>) {
+        self.clips.pop();
+    }
+
+    fn custom(
+        &mut self,
+        command: &RenderCommand<'a, ImageElementData, CustomElementData>,
+        custom: &Custom<'a, CustomElementData>,
+    ) {
+        self.fill(command.bounding_box, custom.background_color);
+    }
+}
+
+/// Rasterizes the commands into the cells of `grid`: rectangles, images and custom elements
+/// set the background color, borders are drawn with box-drawing characters and text is written
+/// one character per cell, clipped to the scissor regions.
+pub fn clay_tui_render<'a, ImageElementData: 'a, CustomElementData: 'a>(
+    grid: &mut TuiGrid,
+    render_commands: impl Iterator<Item = RenderCommand<'a, ImageElementData, CustomElementData>>,
+) {
+    TuiRenderer {
+        grid,
+        clips: Vec::new(),
+    }
+    .render(render_commands);
+}
+
+/// Creates a measure function where every character takes one cell and every line one row,
+/// matching a [`TuiGrid`] with the same cell size. Font sizes and letter spacing are ignored.
+pub fn create_measure_text_function(
+    cell_width: f32,
+    cell_height: f32,
+) -> impl Fn(&str, &TextConfig) -> Dimensions + 'static {
+    move |text, _| Dimensions::new(text.chars().count() as f32 * cell_width, cell_height)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::{examples, LayoutHarness};
+    use crate::{
+        layout::{LayoutDirection, Padding, Sizing},
+        Clay, Declaration,
+    };
+
+    #[rustfmt::skip]
+    #[test]
+    fn borders_and_clipped_text() {
+        let mut grid = TuiGrid::new(12, 4);
+        grid.cell_size(8., 16.);
+        let mut clay = Clay::new(grid.layout_dimensions());
+        clay.set_measure_text_function(create_measure_text_function(8., 16.));
+
+        let mut scope = clay.begin::<(), ()>();
+        scope.with(Declaration::new()
+            .layout()
+                .width(Sizing::Fixed(80.))
+                .height(Sizing::Fixed(48.))
+                .padding(Padding::new(8, 8, 16, 16))
+                .direction(LayoutDirection::TopToBottom)
+                .end()
+            .corner_radius().all(4.).end()
+            .border()
+                .all_directions(1)
+                .color(Color::rgb(255., 255., 255.))
+                .end()
+            .clip(true, true, (0., 0.).into())
+            .background_color(Color::rgb(0., 0., 128.)), |clay|
+        {
+            clay.text("clipped text", TextConfig::new().font_size(16).end());
+        });
+        clay_tui_render(&mut grid, scope.end());
+
+        assert_eq!(
+            grid.to_text(),
+            "╭────────╮  \n\
+             │clipped │  \n\
+             ╰────────╯  \n\
+             \u{20}           \n"
+        );
+        let cells = grid.cells();
+        assert_eq!(cells[1][1].background, Some(Color::rgb(0., 0., 128.)));
+        assert_eq!(cells[1][10].background, None);
+
+        let ansi = grid.to_ansi();
+        assert!(ansi.starts_with("\x1b[38;2;255;255;255m\x1b[48;2;0;0;128m╭"));
+        assert_eq!(ansi.lines().count(), 4);
+        assert!(ansi.lines().all(|line| line.ends_with("\x1b[0m")));
+    }
+
+    #[test]
+    fn example_layout() {
+        let mut harness = LayoutHarness::new();
+        let mut grid = TuiGrid::new(100, 37);
+        grid.cell_size(8., 16.);
+        harness
+            .clay()
+            .set_measure_text_function(create_measure_text_function(8., 16.));
+        harness
+            .clay()
+            .set_layout_dimensions(grid.layout_dimensions());
+
+        let clay = harness.clay();
+        let mut scope = clay.begin::<(), ()>();
+        examples::dashboard(&mut scope);
+        clay_tui_render(&mut grid, scope.end());
+
+        harness.assert_golden("dashboard.txt", &grid.to_text());
+    }
+}
@@ -0,0 +1,800 @@
+//! GPU renderer built on [`wgpu`]: the [batches](crate::batch) of a frame are drawn as instanced
+//! rounded rectangles, and text runs with [`glyphon`].
+//!
+//! Rendering is split like [`glyphon`]'s: [`WgpuRenderer::prepare`] uploads the data of a frame
+//! before the render pass begins, and [`WgpuRenderer::render`] records its draws into the pass.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use glyphon::{
+    Buffer, Cache, Color as GlyphonColor, FontSystem, Metrics, PrepareError, RenderError,
+    Resolution, SwashCache, TextArea, TextAtlas, TextBounds, TextRenderer, Viewport,
+};
+use wgpu::util::DeviceExt;
+
+use crate::batch::{batch, BatchKind, BatchStats, Batches, Instance};
+use crate::clip::Clip;
+use crate::fonts::FontRegistry;
+use crate::math::{BoundingBox, Dimensions};
+use crate::measure::CosmicTextMeasurer;
+use crate::render_commands::{HandleMapper, RenderCommand, RenderCommandConfig};
+use crate::text::TextConfig;
+
+/// Size of an [`Instance`] in the instance buffer.
+const INSTANCE_SIZE: wgpu::BufferAddress = size_of::<Instance>() as wgpu::BufferAddress;
+
+/// Texture registered with [`WgpuRenderer::register_texture`], the `ImageElementData` of the
+/// image elements drawn by a [`WgpuRenderer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextureId(u64);
+
+/// Uses the registered textures as image handles, and the address of the custom data as custom
+/// handles.
+struct FrameHandles;
+
+impl<CustomElementData> HandleMapper<TextureId, CustomElementData> for FrameHandles {
+    fn image_handle(&mut self, data: &TextureId) -> u64 {
+        data.0
+    }
+
+    fn custom_handle(&mut self, data: &CustomElementData) -> u64 {
+        data as *const CustomElementData as usize as u64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum DrawKind {
+    /// Instances drawn with the texture of the handle, `None` for the white texture of shapes.
+    Instances {
+        texture: Option<u64>,
+        range: Range<u32>,
+    },
+    /// Text runs of the batch with the given index, drawn by the text renderer `renderer`.
+    Text {
+        renderer: usize,
+        batch: usize,
+    },
+    Custom {
+        handle: u64,
+        bounds: BoundingBox,
+    },
+}
+
+/// A draw of a frame, clipped to `scissor` (`x`, `y`, `width` and `height` in physical pixels).
+#[derive(Debug, Clone, PartialEq)]
+struct Draw {
+    scissor: [u32; 4],
+    kind: DrawKind,
+}
+
+/// Draws of a frame prepared by [`WgpuRenderer::prepare`].
+pub struct Frame<'a, CustomElementData> {
+    draws: Vec<Draw>,
+    custom: HashMap<u64, &'a CustomElementData>,
+    stats: BatchStats,
+}
+
+impl<CustomElementData> Frame<'_, CustomElementData> {
+    /// Counters of the batching of the frame.
+    pub fn stats(&self) -> BatchStats {
+        self.stats
+    }
+
+    /// Number of draw calls of the frame, custom elements included.
+    pub fn draw_count(&self) -> usize {
+        self.draws.len()
+    }
+}
+
+/// Renders Clay layouts into a [`wgpu::RenderPass`].
+///
+/// Colors and textures are expected in sRGB with straight alpha, the output is premultiplied and
+/// blended with [`wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING`]. Scissors are applied with
+/// [`wgpu::RenderPass::set_scissor_rect`], so the corners of rounded scroll containers are not
+/// clipped.
+pub struct WgpuRenderer {
+    pipeline: wgpu::RenderPipeline,
+    globals: wgpu::Buffer,
+    globals_bind_group: wgpu::BindGroup,
+    texture_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    white_texture: wgpu::BindGroup,
+    textures: HashMap<u64, wgpu::BindGroup>,
+    next_texture: u64,
+    instances: wgpu::Buffer,
+    srgb: bool,
+    size: [u32; 2],
+    scale: f32,
+
+    measurer: CosmicTextMeasurer,
+    swash_cache: SwashCache,
+    viewport: Viewport,
+    atlas: TextAtlas,
+    text_renderers: Vec<TextRenderer>,
+    text_buffers: Vec<Buffer>,
+}
+
+impl WgpuRenderer {
+    /// Creates a renderer drawing into targets of the given format, with the system fonts all
+    /// sans serif.
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue, format: wgpu::TextureFormat) -> Self {
+        let measurer = CosmicTextMeasurer::new(FontSystem::new(), FontRegistry::new());
+        Self::with_measurer(device, queue, format, measurer)
+    }
+
+    /// Creates a renderer drawing into targets of the given format, with the fonts of
+    /// `measurer`.
+    pub fn with_measurer(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        measurer: CosmicTextMeasurer,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("clay shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("wgpu_shader.wgsl").into()),
+        });
+
+        let globals = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("clay globals"),
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let globals_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("clay globals"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let globals_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("clay globals"),
+            layout: &globals_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: globals.as_entire_binding(),
+            }],
+        });
+
+        let texture_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("clay texture"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("clay sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("clay pipeline"),
+            bind_group_layouts: &[&globals_layout, &texture_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("clay pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: INSTANCE_SIZE,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![
+                        0 => Float32x4,
+                        1 => Float32x4,
+                        2 => Float32x4,
+                        3 => Float32x4,
+                    ],
+                }],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let white = device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some("clay white texture"),
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &[255; 4],
+        );
+        let white_texture = texture_bind_group(
+            device,
+            &texture_layout,
+            &sampler,
+            &white.create_view(&Default::default()),
+        );
+
+        let instances = instance_buffer(device, 256);
+
+        let cache = Cache::new(device);
+        let viewport = Viewport::new(device, &cache);
+        let atlas = TextAtlas::new(device, queue, &cache, format);
+
+        Self {
+            pipeline,
+            globals,
+            globals_bind_group,
+            texture_layout,
+            sampler,
+            white_texture,
+            textures: HashMap::new(),
+            next_texture: 0,
+            instances,
+            srgb: format.is_srgb(),
+            size: [1, 1],
+            scale: 1.,
+
+            measurer,
+            swash_cache: SwashCache::new(),
+            viewport,
+            atlas,
+            text_renderers: Vec::new(),
+            text_buffers: Vec::new(),
+        }
+    }
+
+    /// Sets the size of the render target in physical pixels, and the number of physical pixels
+    /// per layout unit.
+    pub fn resize(&mut self, width: u32, height: u32, scale: f32) {
+        self.size = [width.max(1), height.max(1)];
+        self.scale = scale;
+    }
+
+    /// Registers a texture for image elements. The texture must be filterable, and hold straight
+    /// alpha.
+    pub fn register_texture(
+        &mut self,
+        device: &wgpu::Device,
+        view: &wgpu::TextureView,
+    ) -> TextureId {
+        self.next_texture += 1;
+        let bind_group = texture_bind_group(device, &self.texture_layout, &self.sampler, view);
+        self.textures.insert(self.next_texture, bind_group);
+        TextureId(self.next_texture)
+    }
+
+    /// Unregisters a texture, the images still using it are skipped.
+    pub fn unregister_texture(&mut self, texture: TextureId) {
+        self.textures.remove(&texture.0);
+    }
+
+    /// The measurer of the text, sharing its fonts with the renderer.
+    pub fn measurer_mut(&mut self) -> &mut CosmicTextMeasurer {
+        &mut self.measurer
+    }
+
+    /// Measures text with the fonts of the renderer, in layout units.
+    pub fn measure_text(&mut self, text: &str, config: &TextConfig) -> Dimensions {
+        self.measurer.measure(text, config)
+    }
+
+    /// Uploads the instances and prepares the text of a frame. It must be rendered before the
+    /// next call.
+    pub fn prepare<'a, CustomElementData: 'a>(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        render_commands: impl IntoIterator<Item = RenderCommand<'a, TextureId, CustomElementData>>,
+    ) -> Result<Frame<'a, CustomElementData>, PrepareError> {
+        let mut custom = HashMap::new();
+        let commands = render_commands.into_iter().inspect(|command| {
+            if let RenderCommandConfig::Custom(element) = &command.config {
+                custom.insert(FrameHandles.custom_handle(element.data), element.data);
+            }
+        });
+        let batches = batch(commands, &mut FrameHandles);
+        let draws = plan(&batches, self.size, self.scale);
+
+        queue.write_buffer(
+            &self.globals,
+            0,
+            &globals_bytes(self.size, self.scale, self.srgb),
+        );
+        if !batches.instances.is_empty() {
+            let bytes = instance_bytes(&batches.instances);
+            if self.instances.size() < bytes.len() as wgpu::BufferAddress {
+                self.instances =
+                    instance_buffer(device, batches.instances.len().next_power_of_two());
+            }
+            queue.write_buffer(&self.instances, 0, &bytes);
+        }
+
+        self.prepare_text(device, queue, &batches, &draws)?;
+
+        Ok(Frame {
+            draws,
+            custom,
+            stats: batches.stats,
+        })
+    }
+
+    fn prepare_text(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        batches: &Batches,
+        draws: &[Draw],
+    ) -> Result<(), PrepareError> {
+        self.atlas.trim();
+        self.viewport.update(
+            queue,
+            Resolution {
+                width: self.size[0],
+                height: self.size[1],
+            },
+        );
+
+        let runs = &batches.text_runs;
+        while self.text_buffers.len() < runs.len() {
+            let buffer = Buffer::new(self.measurer.font_system_mut(), Metrics::new(16., 24.));
+            self.text_buffers.push(buffer);
+        }
+        for (run, buffer) in runs.iter().zip(&mut self.text_buffers) {
+            let text = &run.text;
+            let (font_id, font_size) = (text.font_id, text.font_size);
+            let line_height = text.line_height;
+            self.measurer
+                .shape(buffer, text.text, font_id, font_size, line_height);
+        }
+
+        for draw in draws {
+            let DrawKind::Text { renderer, batch } = draw.kind else {
+                continue;
+            };
+            let batch = &batches.batches[batch];
+            if self.text_renderers.len() <= renderer {
+                let text_renderer = TextRenderer::new(
+                    &mut self.atlas,
+                    device,
+                    wgpu::MultisampleState::default(),
+                    None,
+                );
+                self.text_renderers.push(text_renderer);
+            }
+
+            let [x, y, width, height] = draw.scissor.map(|value| value as i32);
+            let bounds = TextBounds {
+                left: x,
+                top: y,
+                right: x + width,
+                bottom: y + height,
+            };
+            let areas = batches
+                .text_runs(batch)
+                .iter()
+                .zip(&self.text_buffers[batch.range.clone()])
+                .map(|(run, buffer)| {
+                    let color = run.text.color;
+                    TextArea {
+                        buffer,
+                        left: run.bounds.x * self.scale,
+                        top: run.bounds.y * self.scale,
+                        scale: self.scale,
+                        bounds,
+                        default_color: GlyphonColor::rgba(
+                            color.r.round() as u8,
+                            color.g.round() as u8,
+                            color.b.round() as u8,
+                            color.a.round() as u8,
+                        ),
+                        custom_glyphs: &[],
+                    }
+                });
+            self.text_renderers[renderer].prepare(
+                device,
+                queue,
+                self.measurer.font_system_mut(),
+                &mut self.atlas,
+                &self.viewport,
+                areas,
+                &mut self.swash_cache,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Records the draws of a prepared frame. Custom elements are drawn by `custom`, which gets
+    /// the pass with the scissor of the element set, and their bounds in layout units.
+    pub fn render<'a, CustomElementData>(
+        &self,
+        frame: &Frame<'a, CustomElementData>,
+        pass: &mut wgpu::RenderPass<'_>,
+        mut custom: impl FnMut(&mut wgpu::RenderPass<'_>, BoundingBox, &'a CustomElementData),
+    ) -> Result<(), RenderError> {
+        // Text renderers and custom elements change the pipeline
+        let mut bound = false;
+        for draw in &frame.draws {
+            let [x, y, width, height] = draw.scissor;
+            match &draw.kind {
+                DrawKind::Instances { texture, range } => {
+                    let bind_group = match texture {
+                        None => &self.white_texture,
+                        Some(texture) => match self.textures.get(texture) {
+                            Some(bind_group) => bind_group,
+                            None => continue,
+                        },
+                    };
+                    if !bound {
+                        pass.set_pipeline(&self.pipeline);
+                        pass.set_bind_group(0, &self.globals_bind_group, &[]);
+                        pass.set_vertex_buffer(0, self.instances.slice(..));
+                        bound = true;
+                    }
+                    pass.set_scissor_rect(x, y, width, height);
+                    pass.set_bind_group(1, bind_group, &[]);
+                    pass.draw(0..4, range.clone());
+                }
+                DrawKind::Text { renderer, .. } => {
+                    pass.set_scissor_rect(x, y, width, height);
+                    self.text_renderers[*renderer].render(&self.atlas, &self.viewport, pass)?;
+                    bound = false;
+                }
+                DrawKind::Custom { handle, bounds } => {
+                    if let Some(data) = frame.custom.get(handle) {
+                        pass.set_scissor_rect(x, y, width, height);
+                        custom(pass, *bounds, data);
+                        bound = false;
+                    }
+                }
+            }
+        }
+
+        let [width, height] = self.size;
+        pass.set_scissor_rect(0, 0, width, height);
+        Ok(())
+    }
+}
+
+fn texture_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    sampler: &wgpu::Sampler,
+    view: &wgpu::TextureView,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("clay texture"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+    })
+}
+
+fn instance_buffer(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("clay instances"),
+        size: capacity as wgpu::BufferAddress * INSTANCE_SIZE,
+        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+/// Contents of the globals uniform: the viewport, the scale and whether the target is sRGB.
+fn globals_bytes(size: [u32; 2], scale: f32, srgb: bool) -> [u8; 16] {
+    let mut bytes = [0; 16];
+    bytes[0..4].copy_from_slice(&(size[0] as f32).to_ne_bytes());
+    bytes[4..8].copy_from_slice(&(size[1] as f32).to_ne_bytes());
+    bytes[8..12].copy_from_slice(&scale.to_ne_bytes());
+    bytes[12..16].copy_from_slice(&(srgb as u32).to_ne_bytes());
+    bytes
+}
+
+fn instance_bytes(instances: &[Instance]) -> Vec<u8> {
+    instances
+        .iter()
+        .flat_map(|instance| {
+            [
+                instance.rect,
+                instance.corner_radii,
+                instance.border_widths,
+                instance.color,
+            ]
+        })
+        .flatten()
+        .flat_map(f32::to_ne_bytes)
+        .collect()
+}
+
+/// Scissor of a clip in physical pixels, rounded outwards and limited to the target. `None` when
+/// nothing of the clip is on the target.
+fn scissor_rect(clip: Option<&Clip>, size: [u32; 2], scale: f32) -> Option<[u32; 4]> {
+    let [width, height] = size;
+    let Some(clip) = clip else {
+        return Some([0, 0, width, height]);
+    };
+    let bounds = clip.bounds;
+    let left = (bounds.x * scale).floor().clamp(0., width as f32) as u32;
+    let top = (bounds.y * scale).floor().clamp(0., height as f32) as u32;
+    let right = ((bounds.x + bounds.width) * scale)
+        .ceil()
+        .clamp(0., width as f32) as u32;
+    let bottom = ((bounds.y + bounds.height) * scale)
+        .ceil()
+        .clamp(0., height as f32) as u32;
+    (right > left && bottom > top).then(|| [left, top, right - left, bottom - top])
+}
+
+/// Turns the batches of a frame into draws, skipping the ones clipped out of the target.
+fn plan(batches: &Batches, size: [u32; 2], scale: f32) -> Vec<Draw> {
+    let mut draws = Vec::new();
+    let mut text_renderers = 0;
+    for (index, batch) in batches.batches.iter().enumerate() {
+        let Some(scissor) = scissor_rect(batch.clip.as_ref(), size, scale) else {
+            continue;
+        };
+        let range = batch.range.start as u32..batch.range.end as u32;
+        match batch.kind {
+            BatchKind::Shapes => draws.push(Draw {
+                scissor,
+                kind: DrawKind::Instances {
+                    texture: None,
+                    range,
+                },
+            }),
+            BatchKind::Image { texture } => draws.push(Draw {
+                scissor,
+                kind: DrawKind::Instances {
+                    texture: Some(texture),
+                    range,
+                },
+            }),
+            BatchKind::Text { .. } => {
+                draws.push(Draw {
+                    scissor,
+                    kind: DrawKind::Text {
+                        renderer: text_renderers,
+                        batch: index,
+                    },
+                });
+                text_renderers += 1;
+            }
+            BatchKind::Custom { handle } => {
+                draws.extend(batches.instances(batch).iter().map(|instance| {
+                    let [x, y, width, height] = instance.rect;
+                    Draw {
+                        scissor,
+                        kind: DrawKind::Custom {
+                            handle,
+                            bounds: BoundingBox::new(x, y, width, height),
+                        },
+                    }
+                }))
+            }
+        }
+    }
+    draws
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        color::Color,
+        render_commands::{CornerRadii, Custom, Image, Rectangle, Scissor},
+    };
+
+    fn scissor_start<'a>(bounds: BoundingBox) -> RenderCommand<'a, TextureId, u32> {
+        let scissor = Scissor {
+            horizontal: true,
+            vertical: true,
+            corner_radii: CornerRadii::default(),
+        };
+        RenderCommand::new(bounds, RenderCommandConfig::ScissorStart(scissor), 1, 0)
+    }
+
+    #[test]
+    fn shader_is_valid() {
+        use wgpu::naga::{front::wgsl, valid};
+
+        let module = wgsl::parse_str(include_str!("wgpu_shader.wgsl")).unwrap();
+        valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::empty())
+            .validate(&module)
+            .unwrap();
+    }
+
+    #[test]
+    fn instance_layout() {
+        let instance = Instance {
+            rect: [1., 2., 3., 4.],
+            corner_radii: [5., 6., 7., 8.],
+            border_widths: [9., 10., 11., 12.],
+            color: [0.25, 0.5, 0.75, 1.],
+        };
+        let bytes = instance_bytes(&[instance, Instance::default()]);
+        assert_eq!(bytes.len(), 2 * INSTANCE_SIZE as usize);
+        let floats: Vec<f32> = bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_ne_bytes(chunk.try_into().unwrap()))
+            .collect();
+        assert_eq!(
+            floats[..16],
+            [1., 2., 3., 4., 5., 6., 7., 8., 9., 10., 11., 12., 0.25, 0.5, 0.75, 1.]
+        );
+        assert!(floats[16..].iter().all(|value| *value == 0.));
+
+        let globals = globals_bytes([800, 600], 2., true);
+        assert_eq!(globals[0..4], 800f32.to_ne_bytes());
+        assert_eq!(globals[8..12], 2f32.to_ne_bytes());
+        assert_eq!(globals[12..16], 1u32.to_ne_bytes());
+    }
+
+    #[test]
+    fn scissors_scale_and_clamp() {
+        let clip = |x, y, width, height| Clip {
+            bounds: BoundingBox::new(x, y, width, height),
+            corner_radii: CornerRadii::default(),
+        };
+
+        assert_eq!(scissor_rect(None, [800, 600], 2.), Some([0, 0, 800, 600]));
+        assert_eq!(
+            scissor_rect(Some(&clip(10.25, 20., 100., 50.5)), [800, 600], 2.),
+            Some([20, 40, 201, 101])
+        );
+        assert_eq!(
+            scissor_rect(Some(&clip(-10., 250., 100., 100.)), [800, 600], 2.),
+            Some([0, 500, 180, 100])
+        );
+        assert_eq!(
+            scissor_rect(Some(&clip(500., 0., 100., 100.)), [800, 600], 2.),
+            None
+        );
+        assert_eq!(
+            scissor_rect(Some(&clip(10., 10., 0., 100.)), [800, 600], 1.),
+            None
+        );
+    }
+
+    #[test]
+    fn draws_follow_batches() {
+        let corner_radii = CornerRadii::default();
+        let texture = TextureId(3);
+        let data = [7u32, 8];
+        let rectangle = RenderCommandConfig::Rectangle(Rectangle {
+            color: Color::rgba(255., 0., 0., 128.),
+            corner_radii: corner_radii.clone(),
+        });
+        let image = RenderCommandConfig::Image(Image {
+            background_color: Color::rgba(0., 0., 0., 0.),
+            corner_radii: corner_radii.clone(),
+            data: &texture,
+        });
+        let custom = |data| {
+            RenderCommandConfig::Custom(Custom {
+                background_color: Color::rgba(0., 0., 0., 0.),
+                corner_radii: corner_radii.clone(),
+                data,
+            })
+        };
+        let commands = [
+            RenderCommand::new(
+                BoundingBox::new(0., 0., 100., 100.),
+                rectangle.clone(),
+                1,
+                0,
+            ),
+            scissor_start(BoundingBox::new(10., 10., 50., 50.)),
+            RenderCommand::new(BoundingBox::new(10., 10., 20., 20.), image, 2, 0),
+            RenderCommand::new(BoundingBox::new(30., 10., 20., 20.), custom(&data[0]), 3, 0),
+            RenderCommand::new(BoundingBox::new(30., 30., 20., 20.), custom(&data[1]), 4, 0),
+            RenderCommand::new(
+                BoundingBox::default(),
+                RenderCommandConfig::ScissorEnd(),
+                1,
+                0,
+            ),
+            scissor_start(BoundingBox::new(500., 500., 50., 50.)),
+            RenderCommand::new(BoundingBox::new(500., 500., 20., 20.), rectangle, 5, 0),
+            RenderCommand::new(
+                BoundingBox::default(),
+                RenderCommandConfig::ScissorEnd(),
+                1,
+                0,
+            ),
+        ];
+
+        let batches = batch(commands, &mut FrameHandles);
+        assert_eq!(batches.instances[0].color, [1., 0., 0., 128. / 255.]);
+        // Images with a transparent background are drawn untinted
+        assert_eq!(batches.instances[1].color, [1.; 4]);
+
+        let draws = plan(&batches, [400, 300], 2.);
+        let custom_handle = |data| FrameHandles.custom_handle(data);
+        assert_eq!(
+            draws,
+            [
+                Draw {
+                    scissor: [0, 0, 400, 300],
+                    kind: DrawKind::Instances {
+                        texture: None,
+                        range: 0..1
+                    },
+                },
+                Draw {
+                    scissor: [20, 20, 100, 100],
+                    kind: DrawKind::Instances {
+                        texture: Some(3),
+                        range: 1..2
+                    },
+                },
+                Draw {
+                    scissor: [20, 20, 100, 100],
+                    kind: DrawKind::Custom {
+                        handle: custom_handle(&data[0]),
+                        bounds: BoundingBox::new(30., 10., 20., 20.),
+                    },
+                },
+                Draw {
+                    scissor: [20, 20, 100, 100],
+                    kind: DrawKind::Custom {
+                        handle: custom_handle(&data[1]),
+                        bounds: BoundingBox::new(30., 30., 20., 20.),
+                    },
+                },
+            ]
+        );
+    }
+}
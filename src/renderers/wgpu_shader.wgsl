@@ -0,0 +1,99 @@
+// Instanced rounded rectangles, borders and images of `crate::batch::Instance`.
+
+struct Globals {
+    // Size of the render target in physical pixels
+    viewport: vec2<f32>,
+    // Physical pixels per layout unit
+    scale: f32,
+    // Non zero when the target is sRGB and the colors have to be converted to linear
+    srgb: u32,
+};
+
+@group(0) @binding(0) var<uniform> globals: Globals;
+@group(1) @binding(0) var image: texture_2d<f32>;
+@group(1) @binding(1) var image_sampler: sampler;
+
+struct Instance {
+    @location(0) rect: vec4<f32>,
+    @location(1) corner_radii: vec4<f32>,
+    @location(2) border_widths: vec4<f32>,
+    @location(3) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    // Position relative to the center of the rectangle, in layout units
+    @location(0) local: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) @interpolate(flat) half_size: vec2<f32>,
+    @location(3) @interpolate(flat) corner_radii: vec4<f32>,
+    @location(4) @interpolate(flat) border_widths: vec4<f32>,
+    @location(5) @interpolate(flat) color: vec4<f32>,
+};
+
+fn to_linear(color: vec3<f32>) -> vec3<f32> {
+    let low = color / 12.92;
+    let high = pow((color + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, color <= vec3<f32>(0.04045));
+}
+
+// Signed distance to a rectangle centered on the origin with top left, top right, bottom right
+// and bottom left radii.
+fn rounded_box(position: vec2<f32>, half_size: vec2<f32>, corner_radii: vec4<f32>) -> f32 {
+    let top = select(corner_radii.wz, corner_radii.xy, position.y < 0.0);
+    let radius = min(select(top.y, top.x, position.x < 0.0), min(half_size.x, half_size.y));
+    let q = abs(position) - half_size + radius;
+    return min(max(q.x, q.y), 0.0) + length(max(q, vec2<f32>(0.0))) - radius;
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32, instance: Instance) -> VertexOutput {
+    let corner = vec2<f32>(f32(index & 1u), f32(index >> 1u));
+    // One physical pixel of margin around the rectangle for the antialiased edges
+    let margin = 1.0 / globals.scale;
+    let origin = instance.rect.xy - margin;
+    let position = origin + corner * (instance.rect.zw + 2.0 * margin);
+    let physical = position * globals.scale / globals.viewport;
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(physical.x * 2.0 - 1.0, 1.0 - physical.y * 2.0, 0.0, 1.0);
+    out.local = position - instance.rect.xy - instance.rect.zw * 0.5;
+    out.uv = (position - instance.rect.xy) / max(instance.rect.zw, vec2<f32>(1e-6));
+    out.half_size = instance.rect.zw * 0.5;
+    out.corner_radii = instance.corner_radii;
+    out.border_widths = instance.border_widths;
+    out.color = instance.color;
+    if globals.srgb != 0u {
+        out.color = vec4<f32>(to_linear(instance.color.rgb), instance.color.a);
+    }
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texel = textureSample(image, image_sampler, in.uv);
+
+    let outer = rounded_box(in.local, in.half_size, in.corner_radii);
+    var coverage = clamp(0.5 - outer * globals.scale, 0.0, 1.0);
+
+    let widths = in.border_widths;
+    if any(widths != vec4<f32>(0.0)) {
+        // The inside of a border is a rectangle inset by the widths of the sides, its radii
+        // shrink by the widest side meeting at each corner.
+        let inner_center = vec2<f32>(widths.x - widths.z, widths.y - widths.w) * 0.5;
+        let inner_half_size = in.half_size - vec2<f32>(widths.x + widths.z, widths.y + widths.w) * 0.5;
+        let inner_radii = max(
+            in.corner_radii - max(widths.xzzx, widths.yyww),
+            vec4<f32>(0.0),
+        );
+        if all(inner_half_size > vec2<f32>(0.0)) {
+            let inner = rounded_box(in.local - inner_center, inner_half_size, inner_radii);
+            coverage *= clamp(0.5 + inner * globals.scale, 0.0, 1.0);
+        }
+    }
+
+    // Textures hold straight alpha, the output is premultiplied
+    let color = texel * in.color;
+    let alpha = color.a * coverage;
+    return vec4<f32>(color.rgb * alpha, alpha);
+}
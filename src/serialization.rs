@@ -0,0 +1,356 @@
+//! Serde support for render commands, enabled with the `serde` feature.
+//!
+//! The plain render data types ([`Rectangle`], [`Border`], [`Scissor`], [`BoundingBox`],
+//! [`Color`](crate::Color), [`TextConfig`](crate::text::TextConfig), ...) derive `Serialize` and
+//! `Deserialize` directly. [`Text`] borrows its content from the layout and is deserialized as a
+//! [`FrameText`] owning it instead, as a borrowed string can't hold the escapes of JSON text.
+//!
+//! Render commands borrow their text and reference the image and custom data of the
+//! application, so they don't implement serde themselves: they are converted to a [`Frame`]
+//! first, replacing that data by handles given by a [`HandleMapper`]. [`Frame::replay`] turns a
+//! frame back into render commands borrowing the frame, through a [`HandleResolver`].
+//! ```
+//! use clay_layout::{serialization::{Frame, NoHandles}, Clay, Declaration};
+//!
+//! let mut clay = Clay::new((800., 600.).into());
+//! let mut scope = clay.begin::<(), ()>();
+//! scope.with(Declaration::new().background_color((255., 0., 0.).into()), |_| {});
+//!
+//! let frame = Frame::capture(scope.end(), &mut NoHandles);
+//! let json = serde_json::to_string(&frame).unwrap();
+//!
+//! let frame: Frame = serde_json::from_str(&json).unwrap();
+//! let commands = frame.replay(&NoHandles).unwrap();
+//! assert_eq!(commands.len(), 1);
+//! ```
+
+use alloc::{string::String, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    color::Color,
+    fonts::FontId,
+    math::BoundingBox,
+    render_commands::{
+        Border, CornerRadii, Custom, Image, Rectangle, RenderCommand, RenderCommandConfig, Scissor,
+        Text,
+    },
+};
+
+pub use crate::render_commands::{HandleMapper, HandleResolver, NoHandles};
+
+/// A [`Text`] owning its content, serialized the same way.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameText {
+    pub text: String,
+    pub color: Color,
+    pub font_id: FontId,
+    pub font_size: u16,
+    pub letter_spacing: u16,
+    pub line_height: u16,
+}
+
+impl FrameText {
+    /// The text borrowing the content of `self`.
+    pub fn as_text(&self) -> Text<'_> {
+        Text {
+            text: &self.text,
+            color: self.color,
+            font_id: self.font_id,
+            font_size: self.font_size,
+            letter_spacing: self.letter_spacing,
+            line_height: self.line_height,
+        }
+    }
+}
+
+impl From<&Text<'_>> for FrameText {
+    fn from(text: &Text) -> Self {
+        Self {
+            text: text.text.into(),
+            color: text.color,
+            font_id: text.font_id,
+            font_size: text.font_size,
+            letter_spacing: text.letter_spacing,
+            line_height: text.line_height,
+        }
+    }
+}
+
+/// Serializable counterpart of [`RenderCommandConfig`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FrameCommandConfig {
+    None,
+    Rectangle(Rectangle),
+    Border(Border),
+    Text(FrameText),
+    Image {
+        background_color: Color,
+        corner_radii: CornerRadii,
+        handle: u64,
+    },
+    ScissorStart(Scissor),
+    ScissorEnd,
+    Custom {
+        background_color: Color,
+        corner_radii: CornerRadii,
+        handle: u64,
+    },
+}
+
+/// Serializable counterpart of [`RenderCommand`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameCommand {
+    pub bounding_box: BoundingBox,
+    pub config: FrameCommandConfig,
+    pub id: u32,
+    pub z_index: i16,
+}
+
+impl FrameCommand {
+    /// Converts a render command, mapping its image and custom data with `handles`.
+    pub fn from_render_command<ImageElementData, CustomElementData>(
+        command: &RenderCommand<'_, ImageElementData, CustomElementData>,
+        handles: &mut impl HandleMapper<ImageElementData, CustomElementData>,
+    ) -> Self {
+        let config = match &command.config {
+            RenderCommandConfig::None() => FrameCommandConfig::None,
+            RenderCommandConfig::Rectangle(rectangle) => {
+                FrameCommandConfig::Rectangle(rectangle.clone())
+            }
+            RenderCommandConfig::Border(border) => FrameCommandConfig::Border(border.clone()),
+            RenderCommandConfig::Text(text) => FrameCommandConfig::Text(text.into()),
+            RenderCommandConfig::Image(image) => FrameCommandConfig::Image {
+                background_color: image.background_color,
+                corner_radii: image.corner_radii.clone(),
+                handle: handles.image_handle(image.data),
+            },
+            RenderCommandConfig::ScissorStart(scissor) => {
+                FrameCommandConfig::ScissorStart(scissor.clone())
+            }
+            RenderCommandConfig::ScissorEnd() => FrameCommandConfig::ScissorEnd,
+            RenderCommandConfig::Custom(custom) => FrameCommandConfig::Custom {
+                background_color: custom.background_color,
+                corner_radii: custom.corner_radii.clone(),
+                handle: handles.custom_handle(custom.data),
+            },
+        };
+
+        Self {
+            bounding_box: command.bounding_box,
+            config,
+            id: command.id,
+            z_index: command.z_index,
+        }
+    }
+
+    /// Converts back to a render command borrowing the text of this command, resolving the image
+    /// and custom data with `handles`. Returns `None` if a handle can't be resolved.
+    pub fn to_render_command<'a, ImageElementData, CustomElementData>(
+        &'a self,
+        handles: &impl HandleResolver<'a, ImageElementData, CustomElementData>,
+    ) -> Option<RenderCommand<'a, ImageElementData, CustomElementData>> {
+        let config = match &self.config {
+            FrameCommandConfig::None => RenderCommandConfig::None(),
+            FrameCommandConfig::Rectangle(rectangle) => {
+                RenderCommandConfig::Rectangle(rectangle.clone())
+            }
+            FrameCommandConfig::Border(border) => RenderCommandConfig::Border(border.clone()),
+            FrameCommandConfig::Text(text) => RenderCommandConfig::Text(text.as_text()),
+            FrameCommandConfig::Image {
+                background_color,
+                corner_radii,
+                handle,
+            } => RenderCommandConfig::Image(Image {
+                background_color: *background_color,
+                corner_radii: corner_radii.clone(),
+                data: handles.image(*handle)?,
+            }),
+            FrameCommandConfig::ScissorStart(scissor) => {
+                RenderCommandConfig::ScissorStart(scissor.clone())
+            }
+            FrameCommandConfig::ScissorEnd => RenderCommandConfig::ScissorEnd(),
+            FrameCommandConfig::Custom {
+                background_color,
+                corner_radii,
+                handle,
+            } => RenderCommandConfig::Custom(Custom {
+                background_color: *background_color,
+                corner_radii: corner_radii.clone(),
+                data: handles.custom(*handle)?,
+            }),
+        };
+
+        Some(RenderCommand::new(
+            self.bounding_box,
+            config,
+            self.id,
+            self.z_index,
+        ))
+    }
+}
+
+/// The render commands of a frame, in a serializable form.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Frame {
+    pub commands: Vec<FrameCommand>,
+}
+
+impl Frame {
+    /// Collects the render commands of a frame, typically the output of
+    /// [`ClayLayoutScope::end`](crate::ClayLayoutScope::end).
+    pub fn capture<'render, ImageElementData: 'render, CustomElementData: 'render>(
+        commands: impl IntoIterator<Item = RenderCommand<'render, ImageElementData, CustomElementData>>,
+        handles: &mut impl HandleMapper<ImageElementData, CustomElementData>,
+    ) -> Self {
+        Self {
+            commands: commands
+                .into_iter()
+                .map(|command| FrameCommand::from_render_command(&command, handles))
+                .collect(),
+        }
+    }
+
+    /// Converts the frame back to render commands. Returns the first handle that couldn't be
+    /// resolved as error.
+    pub fn replay<'a, ImageElementData, CustomElementData>(
+        &'a self,
+        handles: &impl HandleResolver<'a, ImageElementData, CustomElementData>,
+    ) -> Result<Vec<RenderCommand<'a, ImageElementData, CustomElementData>>, u64> {
+        self.commands
+            .iter()
+            .map(|command| {
+                command
+                    .to_render_command(handles)
+                    .ok_or(match command.config {
+                        FrameCommandConfig::Image { handle, .. }
+                        | FrameCommandConfig::Custom { handle, .. } => handle,
+                        _ => 0,
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        layout::Sizing, math::Dimensions, testing::monospace_measure, text::TextConfig, Clay,
+        ClayLayoutScope, Declaration,
+    };
+
+    struct Images<'a>(&'a [&'a str]);
+
+    impl HandleMapper<&str, ()> for Images<'_> {
+        fn image_handle(&mut self, data: &&str) -> u64 {
+            self.0.iter().position(|image| image == data).unwrap() as u64
+        }
+
+        fn custom_handle(&mut self, _data: &()) -> u64 {
+            0
+        }
+    }
+
+    impl<'a> HandleResolver<'a, &'a str, ()> for Images<'a> {
+        fn image(&self, handle: u64) -> Option<&'a &'a str> {
+            self.0.get(handle as usize)
+        }
+
+        fn custom(&self, _handle: u64) -> Option<&'a ()> {
+            Some(&())
+        }
+    }
+
+    #[rustfmt::skip]
+    fn layout<'render>(clay: &mut ClayLayoutScope<'_, 'render, &'render str, ()>, image: &'render &'render str) {
+        clay.with(Declaration::new()
+            .layout()
+                .width(Sizing::Fixed(100.))
+                .height(Sizing::Fixed(50.))
+                .end()
+            .image()
+                .data(image)
+                .end(), |_| {});
+        clay.with(Declaration::new()
+            .clip(false, true, (0., 0.).into())
+            .border()
+                .all_directions(2)
+                .color(Color::rgb(0., 0., 255.))
+                .end()
+            .background_color(Color::rgb(255., 0., 0.)), |clay|
+        {
+            clay.text("quoted \"text\" שלום", TextConfig::new().font_size(16).end());
+        });
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let images = ["first.png", "second.png"];
+        let mut clay = Clay::new(Dimensions::new(800.0, 600.0));
+        clay.set_measure_text_function(monospace_measure);
+
+        let mut scope = clay.begin::<&str, ()>();
+        layout(&mut scope, &images[1]);
+        let frame = Frame::capture(scope.end(), &mut Images(&images));
+
+        let json = serde_json::to_string(&frame).unwrap();
+        let decoded: Frame = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, frame);
+
+        let commands = decoded.replay(&Images(&images)).unwrap();
+        assert_eq!(commands.len(), frame.commands.len());
+        assert!(commands.iter().any(|command| matches!(
+            command.config,
+            RenderCommandConfig::Image(Image {
+                data: &"second.png",
+                ..
+            })
+        )));
+        assert!(commands.iter().any(|command| matches!(
+            command.config,
+            RenderCommandConfig::Text(Text {
+                text: "quoted \"text\" שלום",
+                ..
+            })
+        )));
+
+        assert_eq!(decoded.replay(&Images(&images[..1])).unwrap_err(), 1);
+    }
+
+    #[test]
+    fn escaped_text() {
+        // Escaped and non-ASCII characters can't be borrowed from the JSON
+        for content in ["borrowed", "quoted \"text\"\nשלום", "\u{5e9}\t\\"] {
+            let text = Text {
+                text: content,
+                color: Color::rgb(1., 2., 3.),
+                font_id: FontId(1),
+                font_size: 12,
+                letter_spacing: 0,
+                line_height: 0,
+            };
+            let json = serde_json::to_string(&text).unwrap();
+            let decoded: FrameText = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, FrameText::from(&text));
+            assert_eq!(decoded.as_text(), text);
+        }
+        let escaped: FrameText = serde_json::from_str(
+            r#"{"text":"\u05e9\n","color":{"r":0,"g":0,"b":0,"a":0},"font_id":0,"font_size":0,"letter_spacing":0,"line_height":0}"#,
+        )
+        .unwrap();
+        assert_eq!(escaped.text, "ש\n");
+
+        let config: TextConfig = serde_json::from_str(
+            &serde_json::to_string(
+                TextConfig::new()
+                    .font_size(20)
+                    .color(Color::rgb(1., 2., 3.)),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(config.font_size, 20);
+    }
+}
@@ -0,0 +1,346 @@
+//! Shaping of complex scripts and bidirectional text with [`rustybuzz`] and [`unicode_bidi`].
+//!
+//! Clay measures the words of a text and breaks it into lines in logical order. Shaping a line
+//! gives the glyphs of scripts whose letters change with their neighbours, such as Arabic, and
+//! reorders its right-to-left runs for display. Renderers draw the [`ShapedLine`] of the text of
+//! a render command with [`shape_text`], and measure it with
+//! [`rustybuzz_measure_text`](crate::measure::rustybuzz_measure_text).
+
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use rustybuzz::{Direction, Face, UnicodeBuffer};
+use unicode_bidi::ParagraphBidiInfo;
+
+use crate::fonts::{FontRegistry, HasGlyph};
+use crate::render_commands::Text;
+use crate::text::TextConfig;
+
+impl HasGlyph for Face<'_> {
+    fn has_glyph(&self, character: char) -> bool {
+        self.glyph_index(character).is_some()
+    }
+}
+
+/// A glyph placed on a line, in layout units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapedGlyph {
+    pub glyph_id: u16,
+    /// Byte offset in the text of the line of the first character the glyph draws.
+    pub cluster: usize,
+    /// Horizontal position of the origin of the glyph from the left of the line.
+    pub x: f32,
+    /// Vertical position of the origin of the glyph below the baseline.
+    pub y: f32,
+    pub advance: f32,
+}
+
+/// Glyphs of a part of a line in a single direction, drawn with the same font.
+#[derive(Clone)]
+pub struct GlyphRun<'f> {
+    pub font: &'f Face<'f>,
+    pub rtl: bool,
+    /// Bytes of the text of the line the run draws.
+    pub range: Range<usize>,
+    /// Glyphs in visual order, left to right.
+    pub glyphs: Vec<ShapedGlyph>,
+}
+
+impl core::fmt::Debug for GlyphRun<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("GlyphRun")
+            .field("rtl", &self.rtl)
+            .field("range", &self.range)
+            .field("glyphs", &self.glyphs)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A line of text shaped for display, its runs in visual order from left to right.
+#[derive(Debug, Clone, Default)]
+pub struct ShapedLine<'f> {
+    pub runs: Vec<GlyphRun<'f>>,
+    pub width: f32,
+    /// Height of the line, the line height of its font or the `line_height` of its config if
+    /// larger.
+    pub height: f32,
+    /// Distance from the top of the line to the baseline, the glyphs centered in the line height.
+    pub baseline: f32,
+    /// Whether the paragraph direction is right to left, given by its first strong character.
+    pub rtl: bool,
+}
+
+/// Shapes a line of text with the font of `config` and its fallbacks, with `letter_spacing` after
+/// each cluster. Text of a font id missing from `fonts` is empty.
+pub fn shape_line<'f>(
+    fonts: &'f FontRegistry<Face<'f>>,
+    text: &str,
+    config: &TextConfig,
+) -> ShapedLine<'f> {
+    let Some(face) = fonts.get(config.font_id) else {
+        return ShapedLine::default();
+    };
+    let font_size = config.font_size as f32;
+    let scale = font_size / face.units_per_em() as f32;
+    let natural_height = (face.ascender() - face.descender() + face.line_gap()) as f32 * scale;
+    let height = natural_height.max(config.line_height as f32);
+    let baseline = (height - natural_height) / 2. + face.ascender() as f32 * scale;
+
+    let mut line = ShapedLine {
+        height,
+        baseline,
+        ..Default::default()
+    };
+    if text.is_empty() {
+        return line;
+    }
+
+    let bidi = ParagraphBidiInfo::new(text, None);
+    line.rtl = bidi.paragraph_level.is_rtl();
+    let (levels, level_runs) = bidi.visual_runs(0..text.len());
+    for level_run in level_runs {
+        let rtl = levels[level_run.start].is_rtl();
+        let mut font_runs = Vec::new();
+        let mut start = level_run.start;
+        for (font, run) in fonts.runs(config.font_id, &text[level_run]) {
+            font_runs.push((font, start..start + run.len()));
+            start += run.len();
+        }
+        // The runs of a right to left level are displayed from its end
+        if rtl {
+            font_runs.reverse();
+        }
+
+        for (font, range) in font_runs {
+            let (run, end) = shape_run(font, text, range, rtl, config, line.width);
+            line.width = end;
+            line.runs.push(run);
+        }
+    }
+    line
+}
+
+/// Shapes the `range` of `text` in a single direction, starting at `x`. Returns the run and the
+/// position after its last glyph.
+fn shape_run<'f>(
+    font: &'f Face<'f>,
+    text: &str,
+    range: Range<usize>,
+    rtl: bool,
+    config: &TextConfig,
+    mut x: f32,
+) -> (GlyphRun<'f>, f32) {
+    let scale = config.font_size as f32 / font.units_per_em() as f32;
+    let letter_spacing = config.letter_spacing as f32;
+
+    let mut buffer = UnicodeBuffer::new();
+    buffer.push_str(&text[range.clone()]);
+    buffer.set_direction(match rtl {
+        true => Direction::RightToLeft,
+        false => Direction::LeftToRight,
+    });
+    buffer.guess_segment_properties();
+    let shaped = rustybuzz::shape(font, &[], buffer);
+
+    let infos = shaped.glyph_infos();
+    let positions = shaped.glyph_positions();
+    let mut glyphs = Vec::with_capacity(infos.len());
+    for (index, (info, position)) in infos.iter().zip(positions).enumerate() {
+        // The spacing goes after the last glyph of a cluster
+        let cluster_end = infos
+            .get(index + 1)
+            .is_none_or(|next| next.cluster != info.cluster);
+        let spacing = if cluster_end { letter_spacing } else { 0. };
+        let advance = position.x_advance as f32 * scale + spacing;
+        glyphs.push(ShapedGlyph {
+            glyph_id: info.glyph_id as u16,
+            cluster: range.start + info.cluster as usize,
+            x: x + position.x_offset as f32 * scale,
+            y: -position.y_offset as f32 * scale,
+            advance,
+        });
+        x += advance;
+    }
+
+    let run = GlyphRun {
+        font,
+        rtl,
+        range,
+        glyphs,
+    };
+    (run, x)
+}
+
+/// Shapes the text of a render command, see [`shape_line`].
+pub fn shape_text<'f>(fonts: &'f FontRegistry<Face<'f>>, text: &Text) -> ShapedLine<'f> {
+    let mut config = TextConfig::new();
+    config
+        .font_id(text.font_id)
+        .font_size(text.font_size)
+        .letter_spacing(text.letter_spacing)
+        .line_height(text.line_height);
+    shape_line(fonts, text.text, &config)
+}
+
+/// The characters of a line in display order, for renderers drawing strings without reordering
+/// them. Mirrored characters, such as brackets, aren't mirrored.
+/// ```
+/// use clay_layout::shaping::visual_order;
+///
+/// assert_eq!(visual_order("abc שלום def"), "abc םולש def");
+/// assert_eq!(visual_order("plain"), "plain");
+/// ```
+pub fn visual_order(text: &str) -> Cow<'_, str> {
+    ParagraphBidiInfo::new(text, None).reorder_line(0..text.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fonts::FontId;
+
+    const TUFFY: &[u8] = include_bytes!("../tests/fonts/Tuffy.ttf");
+    const HEBREW: &[u8] = include_bytes!("../tests/fonts/NotoSansHebrew.ttf");
+    const ARABIC: &[u8] = include_bytes!("../tests/fonts/NotoSansArabic.ttf");
+
+    /// Tuffy falling back to the Hebrew and Arabic fonts.
+    fn fonts() -> FontRegistry<Face<'static>> {
+        let mut fonts = FontRegistry::new();
+        let tuffy = fonts.add("Tuffy", Face::from_slice(TUFFY, 0).unwrap());
+        let hebrew = fonts.add("Noto Sans Hebrew", Face::from_slice(HEBREW, 0).unwrap());
+        let arabic = fonts.add("Noto Sans Arabic", Face::from_slice(ARABIC, 0).unwrap());
+        fonts.set_fallbacks(tuffy, [hebrew, arabic]);
+        fonts
+    }
+
+    fn config(font_size: u16) -> TextConfig {
+        let mut config = TextConfig::new();
+        config.font_size(font_size);
+        config
+    }
+
+    /// The ranges and directions of the runs of a line, with the clusters of their glyphs.
+    fn runs(line: &ShapedLine) -> Vec<(Range<usize>, bool, Vec<usize>)> {
+        let runs = line.runs.iter().map(|run| {
+            let clusters = run.glyphs.iter().map(|glyph| glyph.cluster).collect();
+            (run.range.clone(), run.rtl, clusters)
+        });
+        runs.collect()
+    }
+
+    fn assert_glyphs_follow(line: &ShapedLine) {
+        let glyphs = line.runs.iter().flat_map(|run| &run.glyphs);
+        let mut x = 0.;
+        for glyph in glyphs {
+            // Marks are placed by their offset from the pen
+            if glyph.advance != 0. {
+                assert_eq!(glyph.x, x, "{line:?}");
+            }
+            x += glyph.advance;
+        }
+        assert_eq!(x, line.width);
+    }
+
+    #[test]
+    fn mixed_directions_are_in_visual_order() {
+        let fonts = fonts();
+        let text = "abc שלום def";
+        let line = shape_line(&fonts, text, &config(16));
+
+        assert!(!line.rtl);
+        assert_eq!(
+            runs(&line),
+            [
+                (0..4, false, vec![0, 1, 2, 3]),
+                // Hebrew glyphs are displayed from the last letter
+                (4..12, true, vec![10, 8, 6, 4]),
+                (12..16, false, vec![12, 13, 14, 15]),
+            ]
+        );
+        let hebrew = fonts.get(FontId(1)).unwrap();
+        assert!(core::ptr::eq(line.runs[1].font, hebrew));
+        assert_glyphs_follow(&line);
+
+        // A paragraph starting with Hebrew is right to left, the Latin word goes on its left
+        let text = "שלום abc";
+        let line = shape_line(&fonts, text, &config(16));
+        assert!(line.rtl);
+        assert_eq!(
+            runs(&line),
+            [
+                (9..12, false, vec![9, 10, 11]),
+                // The space has a glyph in Tuffy and is drawn with it, right of the Latin word
+                (8..9, true, vec![8]),
+                (0..8, true, vec![6, 4, 2, 0]),
+            ]
+        );
+        assert_glyphs_follow(&line);
+        assert_eq!(visual_order(text), "abc םולש");
+    }
+
+    #[test]
+    fn arabic_letters_join() {
+        let fonts = fonts();
+        let arabic = fonts.get(FontId(2)).unwrap();
+        let text = "سلام";
+        let line = shape_line(&fonts, text, &config(32));
+
+        let [run] = line.runs.as_slice() else {
+            panic!("{line:?}");
+        };
+        assert!(run.rtl);
+        assert!(core::ptr::eq(run.font, arabic));
+        // Lam and alef are a single ligature joined to the initial form of seen. Alef doesn't
+        // join the letter after it, meem stays isolated
+        let clusters: Vec<_> = run.glyphs.iter().map(|glyph| glyph.cluster).collect();
+        assert_eq!(clusters, [6, 2, 0]);
+        let isolated = |character| arabic.glyph_index(character).unwrap().0;
+        assert_eq!(run.glyphs[0].glyph_id, isolated('م'));
+        assert_ne!(run.glyphs[1].glyph_id, isolated('ل'));
+        assert_ne!(run.glyphs[2].glyph_id, isolated('س'));
+        assert_glyphs_follow(&line);
+    }
+
+    #[test]
+    fn marks_keep_the_line_width() {
+        let fonts = fonts();
+        // Hebrew points are drawn over or under their letters, without advancing
+        let pointed = shape_line(&fonts, "שָׁלוֹם", &config(32));
+        assert_eq!(pointed.width, shape_line(&fonts, "שלום", &config(32)).width);
+        assert_glyphs_follow(&pointed);
+
+        // The accent ending the line is offset to the right of the pen, past the end of the line
+        let accented = shape_line(&fonts, "x\u{301}", &config(32));
+        let [x, accent] = accented.runs[0].glyphs.as_slice() else {
+            panic!("{accented:?}");
+        };
+        assert_eq!(accent.advance, 0.);
+        assert!(accent.x > x.advance, "{accented:?}");
+        assert_eq!(accented.width, shape_line(&fonts, "x", &config(32)).width);
+        assert_glyphs_follow(&accented);
+    }
+
+    #[test]
+    fn letter_spacing_follows_clusters() {
+        let fonts = fonts();
+        let text = "abc שלום";
+        let mut spaced = config(16);
+        spaced.letter_spacing(3);
+
+        let line = shape_line(&fonts, text, &config(16));
+        let spaced_line = shape_line(&fonts, text, &spaced);
+        assert_eq!(spaced_line.width, line.width + 3. * 8.);
+        assert_glyphs_follow(&spaced_line);
+
+        let mut tall = config(16);
+        tall.line_height(60);
+        let tall_line = shape_line(&fonts, text, &tall);
+        assert_eq!(tall_line.height, 60.);
+        assert_eq!(tall_line.baseline - line.baseline, (60. - line.height) / 2.);
+
+        let mut missing = config(16);
+        missing.font_id(7);
+        assert!(shape_line(&fonts, text, &missing).runs.is_empty());
+    }
+}
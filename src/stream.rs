@@ -0,0 +1,818 @@
+//! Compact binary encoding of render commands, to send frames to another process or record them.
+//!
+//! A frame is encoded as a little-endian header, a table of interned strings and one record per
+//! render command:
+//!
+//! ```text
+//! frame   := "CLRF" version:u16 string_count:u32 command_count:u32 string* command*
+//! string  := length:u32 utf8_bytes
+//! command := type:u8 z_index:i16 id:u32 bounding_box:4*f32 payload
+//! ```
+//!
+//! Image and custom data are replaced by handles given by a [`HandleMapper`]. [`FrameView`]
+//! reads a frame without copying, [`FrameRecorder`] and [`Replay`] write and read a sequence of
+//! frames.
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    io::{Read, Write},
+};
+
+use crate::{
+    color::Color,
+    fonts::FontId,
+    math::BoundingBox,
+    render_commands::{
+        Border, BorderWidth, CornerRadii, Custom, HandleMapper, HandleResolver, Image, Rectangle,
+        RenderCommand, RenderCommandConfig, Scissor, Text,
+    },
+};
+
+/// Version of the frame encoding, increased on every incompatible change.
+pub const VERSION: u16 = 1;
+
+const FRAME_MAGIC: &[u8; 4] = b"CLRF";
+const RECORDING_MAGIC: &[u8; 4] = b"CLRR";
+
+const FRAME_HEADER_SIZE: usize = 4 + 2 + 4 + 4;
+const COMMAND_HEADER_SIZE: usize = 1 + 2 + 4 + 16;
+
+const TYPE_NONE: u8 = 0;
+const TYPE_RECTANGLE: u8 = 1;
+const TYPE_BORDER: u8 = 2;
+const TYPE_TEXT: u8 = 3;
+const TYPE_IMAGE: u8 = 4;
+const TYPE_SCISSOR_START: u8 = 5;
+const TYPE_SCISSOR_END: u8 = 6;
+const TYPE_CUSTOM: u8 = 7;
+
+/// Errors returned while decoding frames or reading recordings.
+#[derive(Debug)]
+pub enum StreamError {
+    Io(std::io::Error),
+    /// The data doesn't start with the expected magic bytes.
+    BadMagic,
+    /// The data was encoded with another version of the format.
+    UnsupportedVersion(u16),
+    /// The data ends in the middle of the frame.
+    Truncated,
+    InvalidUtf8,
+    InvalidCommandType(u8),
+    InvalidStringIndex(u32),
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::BadMagic => write!(f, "not a clay render stream"),
+            Self::UnsupportedVersion(version) => {
+                write!(
+                    f,
+                    "unsupported stream version {version}, expected {VERSION}"
+                )
+            }
+            Self::Truncated => write!(f, "truncated frame"),
+            Self::InvalidUtf8 => write!(f, "string is not valid UTF-8"),
+            Self::InvalidCommandType(command_type) => {
+                write!(f, "invalid render command type {command_type}")
+            }
+            Self::InvalidStringIndex(index) => write!(f, "invalid string index {index}"),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+impl From<std::io::Error> for StreamError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+/// Encodes the render commands of a frame, typically the output of
+/// [`ClayLayoutScope::end`](crate::ClayLayoutScope::end).
+pub fn encode_frame<'render, ImageElementData: 'render, CustomElementData: 'render>(
+    commands: impl IntoIterator<Item = RenderCommand<'render, ImageElementData, CustomElementData>>,
+    handles: &mut impl HandleMapper<ImageElementData, CustomElementData>,
+) -> Vec<u8> {
+    let mut strings: Vec<&str> = Vec::new();
+    let mut string_indices: HashMap<&str, u32> = HashMap::new();
+    let mut records = Vec::new();
+    let mut command_count = 0u32;
+
+    for command in commands {
+        command_count += 1;
+        let command_type = match command.config {
+            RenderCommandConfig::None() => TYPE_NONE,
+            RenderCommandConfig::Rectangle(_) => TYPE_RECTANGLE,
+            RenderCommandConfig::Border(_) => TYPE_BORDER,
+            RenderCommandConfig::Text(_) => TYPE_TEXT,
+            RenderCommandConfig::Image(_) => TYPE_IMAGE,
+            RenderCommandConfig::ScissorStart(_) => TYPE_SCISSOR_START,
+            RenderCommandConfig::ScissorEnd() => TYPE_SCISSOR_END,
+            RenderCommandConfig::Custom(_) => TYPE_CUSTOM,
+        };
+        records.push(command_type);
+        records.extend_from_slice(&command.z_index.to_le_bytes());
+        records.extend_from_slice(&command.id.to_le_bytes());
+        put_bounding_box(&mut records, command.bounding_box);
+
+        match command.config {
+            RenderCommandConfig::None() | RenderCommandConfig::ScissorEnd() => {}
+            RenderCommandConfig::Rectangle(rectangle) => {
+                put_color(&mut records, rectangle.color);
+                put_radii(&mut records, &rectangle.corner_radii);
+            }
+            RenderCommandConfig::Border(border) => {
+                put_color(&mut records, border.color);
+                put_radii(&mut records, &border.corner_radii);
+                for width in [
+                    border.width.left,
+                    border.width.right,
+                    border.width.top,
+                    border.width.bottom,
+                    border.width.between_children,
+                ] {
+                    records.extend_from_slice(&width.to_le_bytes());
+                }
+            }
+            RenderCommandConfig::Text(text) => {
+                // The strings live as long as the render commands, not just this iteration
+                let content: &'render str = text.text;
+                let index = *string_indices.entry(content).or_insert_with(|| {
+                    strings.push(content);
+                    strings.len() as u32 - 1
+                });
+                records.extend_from_slice(&index.to_le_bytes());
+                put_color(&mut records, text.color);
+                for value in [
+                    text.font_id.0,
+                    text.font_size,
+                    text.letter_spacing,
+                    text.line_height,
+                ] {
+                    records.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            RenderCommandConfig::Image(image) => {
+                put_color(&mut records, image.background_color);
+                put_radii(&mut records, &image.corner_radii);
+                records.extend_from_slice(&handles.image_handle(image.data).to_le_bytes());
+            }
+            RenderCommandConfig::ScissorStart(scissor) => {
+                records.push(scissor.horizontal as u8);
+                records.push(scissor.vertical as u8);
+                put_radii(&mut records, &scissor.corner_radii);
+            }
+            RenderCommandConfig::Custom(custom) => {
+                put_color(&mut records, custom.background_color);
+                put_radii(&mut records, &custom.corner_radii);
+                records.extend_from_slice(&handles.custom_handle(custom.data).to_le_bytes());
+            }
+        }
+    }
+
+    let strings_size: usize = strings.iter().map(|string| 4 + string.len()).sum();
+    let mut frame = Vec::with_capacity(FRAME_HEADER_SIZE + strings_size + records.len());
+    frame.extend_from_slice(FRAME_MAGIC);
+    frame.extend_from_slice(&VERSION.to_le_bytes());
+    frame.extend_from_slice(&(strings.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&command_count.to_le_bytes());
+    for string in strings {
+        frame.extend_from_slice(&(string.len() as u32).to_le_bytes());
+        frame.extend_from_slice(string.as_bytes());
+    }
+    frame.extend_from_slice(&records);
+    frame
+}
+
+fn put_f32s(output: &mut Vec<u8>, values: [f32; 4]) {
+    for value in values {
+        output.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+fn put_bounding_box(output: &mut Vec<u8>, value: BoundingBox) {
+    put_f32s(output, [value.x, value.y, value.width, value.height]);
+}
+
+fn put_color(output: &mut Vec<u8>, value: Color) {
+    put_f32s(output, [value.r, value.g, value.b, value.a]);
+}
+
+fn put_radii(output: &mut Vec<u8>, value: &CornerRadii) {
+    put_f32s(
+        output,
+        [
+            value.top_left,
+            value.top_right,
+            value.bottom_left,
+            value.bottom_right,
+        ],
+    );
+}
+
+/// Size of the payload of each command type, `None` for unknown types.
+fn payload_size(command_type: u8) -> Option<usize> {
+    Some(match command_type {
+        TYPE_NONE | TYPE_SCISSOR_END => 0,
+        TYPE_RECTANGLE => 32,
+        TYPE_BORDER => 32 + 10,
+        TYPE_TEXT => 4 + 16 + 8,
+        TYPE_IMAGE | TYPE_CUSTOM => 32 + 8,
+        TYPE_SCISSOR_START => 2 + 16,
+        _ => return None,
+    })
+}
+
+/// Little-endian reader over a byte slice.
+#[derive(Clone, Copy)]
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8], StreamError> {
+        if self.data.len() < count {
+            return Err(StreamError::Truncated);
+        }
+        let (bytes, rest) = self.data.split_at(count);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], StreamError> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, StreamError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, StreamError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn i16(&mut self) -> Result<i16, StreamError> {
+        Ok(i16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, StreamError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, StreamError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn f32s(&mut self) -> Result<[f32; 4], StreamError> {
+        let mut values = [0.; 4];
+        for value in &mut values {
+            *value = f32::from_le_bytes(self.array()?);
+        }
+        Ok(values)
+    }
+
+    fn color(&mut self) -> Result<Color, StreamError> {
+        let [r, g, b, a] = self.f32s()?;
+        Ok(Color::rgba(r, g, b, a))
+    }
+
+    fn radii(&mut self) -> Result<CornerRadii, StreamError> {
+        let [top_left, top_right, bottom_left, bottom_right] = self.f32s()?;
+        Ok(CornerRadii {
+            top_left,
+            top_right,
+            bottom_left,
+            bottom_right,
+        })
+    }
+}
+
+/// Configuration of a [`CommandView`], like [`RenderCommandConfig`] with handles in place of
+/// the image and custom data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandViewConfig<'a> {
+    None,
+    Rectangle(Rectangle),
+    Border(Border),
+    Text(Text<'a>),
+    Image {
+        background_color: Color,
+        corner_radii: CornerRadii,
+        handle: u64,
+    },
+    ScissorStart(Scissor),
+    ScissorEnd,
+    Custom {
+        background_color: Color,
+        corner_radii: CornerRadii,
+        handle: u64,
+    },
+}
+
+/// A render command read from a [`FrameView`], borrowing its text from the frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandView<'a> {
+    pub bounding_box: BoundingBox,
+    pub config: CommandViewConfig<'a>,
+    pub id: u32,
+    pub z_index: i16,
+}
+
+impl<'a> CommandView<'a> {
+    /// Converts to a render command, resolving the image and custom data with `handles`.
+    /// Returns `None` if a handle can't be resolved.
+    pub fn to_render_command<ImageElementData, CustomElementData>(
+        &self,
+        handles: &impl HandleResolver<'a, ImageElementData, CustomElementData>,
+    ) -> Option<RenderCommand<'a, ImageElementData, CustomElementData>> {
+        let config = match &self.config {
+            CommandViewConfig::None => RenderCommandConfig::None(),
+            CommandViewConfig::Rectangle(rectangle) => {
+                RenderCommandConfig::Rectangle(rectangle.clone())
+            }
+            CommandViewConfig::Border(border) => RenderCommandConfig::Border(border.clone()),
+            CommandViewConfig::Text(text) => RenderCommandConfig::Text(text.clone()),
+            CommandViewConfig::Image {
+                background_color,
+                corner_radii,
+                handle,
+            } => RenderCommandConfig::Image(Image {
+                background_color: *background_color,
+                corner_radii: corner_radii.clone(),
+                data: handles.image(*handle)?,
+            }),
+            CommandViewConfig::ScissorStart(scissor) => {
+                RenderCommandConfig::ScissorStart(scissor.clone())
+            }
+            CommandViewConfig::ScissorEnd => RenderCommandConfig::ScissorEnd(),
+            CommandViewConfig::Custom {
+                background_color,
+                corner_radii,
+                handle,
+            } => RenderCommandConfig::Custom(Custom {
+                background_color: *background_color,
+                corner_radii: corner_radii.clone(),
+                data: handles.custom(*handle)?,
+            }),
+        };
+
+        Some(RenderCommand::new(
+            self.bounding_box,
+            config,
+            self.id,
+            self.z_index,
+        ))
+    }
+}
+
+/// A decoded frame borrowing the encoded bytes.
+///
+/// The whole frame is validated by [`parse`](Self::parse), reading the commands can't fail.
+#[derive(Debug, Clone)]
+pub struct FrameView<'a> {
+    strings: Vec<&'a str>,
+    commands: &'a [u8],
+    command_count: u32,
+}
+
+impl<'a> FrameView<'a> {
+    /// Validates an encoded frame.
+    pub fn parse(data: &'a [u8]) -> Result<Self, StreamError> {
+        let mut reader = Reader { data };
+        if reader.bytes(4)? != FRAME_MAGIC {
+            return Err(StreamError::BadMagic);
+        }
+        let version = reader.u16()?;
+        if version != VERSION {
+            return Err(StreamError::UnsupportedVersion(version));
+        }
+        let string_count = reader.u32()?;
+        let command_count = reader.u32()?;
+        // The counts aren't trusted before the data they announce is known to fit
+        let records = string_count as u64 * 4 + command_count as u64 * COMMAND_HEADER_SIZE as u64;
+        if records > reader.data.len() as u64 {
+            return Err(StreamError::Truncated);
+        }
+
+        let mut strings = Vec::with_capacity(string_count as usize);
+        for _ in 0..string_count {
+            let length = reader.u32()? as usize;
+            let string = core::str::from_utf8(reader.bytes(length)?)
+                .map_err(|_| StreamError::InvalidUtf8)?;
+            strings.push(string);
+        }
+
+        let commands = reader.data;
+        for _ in 0..command_count {
+            let command_type = reader.u8()?;
+            let size =
+                payload_size(command_type).ok_or(StreamError::InvalidCommandType(command_type))?;
+            let mut payload = reader;
+            reader.bytes(COMMAND_HEADER_SIZE - 1 + size)?;
+            if command_type == TYPE_TEXT {
+                payload.bytes(COMMAND_HEADER_SIZE - 1)?;
+                let index = payload.u32()?;
+                if index >= string_count {
+                    return Err(StreamError::InvalidStringIndex(index));
+                }
+            }
+        }
+
+        Ok(Self {
+            strings,
+            commands,
+            command_count,
+        })
+    }
+
+    /// Number of render commands in the frame.
+    pub fn len(&self) -> usize {
+        self.command_count as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.command_count == 0
+    }
+
+    /// The interned strings of the frame.
+    pub fn strings(&self) -> &[&'a str] {
+        &self.strings
+    }
+
+    /// Iterates over the render commands of the frame.
+    pub fn commands(&self) -> impl Iterator<Item = CommandView<'a>> + '_ {
+        let mut reader = Reader {
+            data: self.commands,
+        };
+        (0..self.command_count).map(move |_| {
+            Self::read_command(&mut reader, &self.strings).expect("frame validated by parse")
+        })
+    }
+
+    fn read_command(
+        reader: &mut Reader<'a>,
+        strings: &[&'a str],
+    ) -> Result<CommandView<'a>, StreamError> {
+        let command_type = reader.u8()?;
+        let z_index = reader.i16()?;
+        let id = reader.u32()?;
+        let [x, y, width, height] = reader.f32s()?;
+
+        let config = match command_type {
+            TYPE_NONE => CommandViewConfig::None,
+            TYPE_RECTANGLE => CommandViewConfig::Rectangle(Rectangle {
+                color: reader.color()?,
+                corner_radii: reader.radii()?,
+            }),
+            TYPE_BORDER => {
+                let color = reader.color()?;
+                let corner_radii = reader.radii()?;
+                CommandViewConfig::Border(Border {
+                    color,
+                    corner_radii,
+                    width: BorderWidth {
+                        left: reader.u16()?,
+                        right: reader.u16()?,
+                        top: reader.u16()?,
+                        bottom: reader.u16()?,
+                        between_children: reader.u16()?,
+                    },
+                })
+            }
+            TYPE_TEXT => {
+                let index = reader.u32()?;
+                CommandViewConfig::Text(Text {
+                    text: strings
+                        .get(index as usize)
+                        .ok_or(StreamError::InvalidStringIndex(index))?,
+                    color: reader.color()?,
+                    font_id: FontId(reader.u16()?),
+                    font_size: reader.u16()?,
+                    letter_spacing: reader.u16()?,
+                    line_height: reader.u16()?,
+                })
+            }
+            TYPE_IMAGE => CommandViewConfig::Image {
+                background_color: reader.color()?,
+                corner_radii: reader.radii()?,
+                handle: reader.u64()?,
+            },
+            TYPE_SCISSOR_START => CommandViewConfig::ScissorStart(Scissor {
+                horizontal: reader.u8()? != 0,
+                vertical: reader.u8()? != 0,
+                corner_radii: reader.radii()?,
+            }),
+            TYPE_SCISSOR_END => CommandViewConfig::ScissorEnd,
+            TYPE_CUSTOM => CommandViewConfig::Custom {
+                background_color: reader.color()?,
+                corner_radii: reader.radii()?,
+                handle: reader.u64()?,
+            },
+            _ => return Err(StreamError::InvalidCommandType(command_type)),
+        };
+
+        Ok(CommandView {
+            bounding_box: BoundingBox::new(x, y, width, height),
+            config,
+            id,
+            z_index,
+        })
+    }
+}
+
+/// Writes a sequence of frames, each prefixed by its time in seconds and its length.
+/// ```
+/// use clay_layout::{render_commands::NoHandles, stream::{FrameRecorder, Replay}, Clay};
+///
+/// let mut clay = Clay::new((800., 600.).into());
+/// let mut recording = Vec::new();
+/// let mut recorder = FrameRecorder::new(&mut recording).unwrap();
+/// recorder.record(0.0, clay.begin::<(), ()>().end(), &mut NoHandles).unwrap();
+///
+/// let frames: Vec<_> = Replay::new(recording.as_slice()).unwrap().collect();
+/// assert_eq!(frames.len(), 1);
+/// ```
+pub struct FrameRecorder<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> FrameRecorder<W> {
+    /// Writes the recording header to `writer`.
+    pub fn new(mut writer: W) -> std::io::Result<Self> {
+        writer.write_all(RECORDING_MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        Ok(Self { writer })
+    }
+
+    /// Encodes and writes the render commands of a frame, `time` is the time of the frame in
+    /// seconds since the start of the recording.
+    pub fn record<'render, ImageElementData: 'render, CustomElementData: 'render>(
+        &mut self,
+        time: f64,
+        commands: impl IntoIterator<Item = RenderCommand<'render, ImageElementData, CustomElementData>>,
+        handles: &mut impl HandleMapper<ImageElementData, CustomElementData>,
+    ) -> std::io::Result<()> {
+        self.write_frame(time, &encode_frame(commands, handles))
+    }
+
+    /// Writes an already encoded frame.
+    pub fn write_frame(&mut self, time: f64, frame: &[u8]) -> std::io::Result<()> {
+        self.writer.write_all(&time.to_le_bytes())?;
+        self.writer.write_all(&(frame.len() as u32).to_le_bytes())?;
+        self.writer.write_all(frame)
+    }
+
+    /// Flushes and returns the writer.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// A frame read by [`Replay`].
+#[derive(Debug, Clone)]
+pub struct RecordedFrame {
+    /// Time of the frame in seconds since the start of the recording.
+    pub time: f64,
+    /// The encoded frame, decoded with [`view`](Self::view).
+    pub data: Vec<u8>,
+}
+
+impl RecordedFrame {
+    pub fn view(&self) -> Result<FrameView<'_>, StreamError> {
+        FrameView::parse(&self.data)
+    }
+}
+
+/// Iterator over the frames written by a [`FrameRecorder`].
+pub struct Replay<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> Replay<R> {
+    /// Reads and checks the recording header.
+    pub fn new(mut reader: R) -> Result<Self, StreamError> {
+        let mut header = [0; 6];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != RECORDING_MAGIC {
+            return Err(StreamError::BadMagic);
+        }
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != VERSION {
+            return Err(StreamError::UnsupportedVersion(version));
+        }
+        Ok(Self { reader })
+    }
+
+    fn read_frame(&mut self) -> Result<Option<RecordedFrame>, StreamError> {
+        let mut time = [0; 8];
+        // A clean end of the recording is only possible between frames
+        match self.reader.read(&mut time[..1])? {
+            0 => return Ok(None),
+            _ => self.reader.read_exact(&mut time[1..])?,
+        }
+        let mut length = [0; 4];
+        self.reader.read_exact(&mut length)?;
+        let length = u32::from_le_bytes(length) as usize;
+        if length < FRAME_HEADER_SIZE {
+            return Err(StreamError::Truncated);
+        }
+        // The buffer grows with the data actually read, not with the length of the stream
+        let mut data = Vec::new();
+        (&mut self.reader)
+            .take(length as u64)
+            .read_to_end(&mut data)?;
+        if data.len() != length {
+            return Err(StreamError::Truncated);
+        }
+        Ok(Some(RecordedFrame {
+            time: f64::from_le_bytes(time),
+            data,
+        }))
+    }
+}
+
+impl<R: Read> Iterator for Replay<R> {
+    type Item = Result<RecordedFrame, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_frame().transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        layout::Sizing, testing::LayoutHarness, text::TextConfig, ClayLayoutScope, Declaration,
+    };
+
+    struct Images;
+
+    impl HandleMapper<u64, ()> for Images {
+        fn image_handle(&mut self, data: &u64) -> u64 {
+            *data
+        }
+
+        fn custom_handle(&mut self, _data: &()) -> u64 {
+            0
+        }
+    }
+
+    impl<'a> HandleResolver<'a, u64, ()> for Images {
+        fn image(&self, handle: u64) -> Option<&'a u64> {
+            (handle == 7).then_some(&7)
+        }
+
+        fn custom(&self, _handle: u64) -> Option<&'a ()> {
+            Some(&())
+        }
+    }
+
+    #[rustfmt::skip]
+    fn layout(clay: &mut ClayLayoutScope<'_, '_, u64, ()>) {
+        clay.with(Declaration::new()
+            .layout()
+                .width(Sizing::Fixed(20.))
+                .height(Sizing::Fixed(20.))
+                .end()
+            .image()
+                .data(&7)
+                .end(), |_| {});
+        clay.with(Declaration::new()
+            .clip(true, false, (0., 0.).into())
+            .corner_radius().all(4.).end()
+            .border()
+                .all_directions(1)
+                .color(Color::rgb(0., 0., 255.))
+                .end()
+            .background_color(Color::rgb(255., 0., 0.)), |clay|
+        {
+            clay.text("repeated", TextConfig::new().font_size(10).end());
+            clay.text("repeated", TextConfig::new().font_size(20).end());
+        });
+    }
+
+    #[test]
+    fn frame_round_trip() {
+        let mut harness = LayoutHarness::new();
+        let clay = harness.clay();
+        let mut scope = clay.begin::<u64, ()>();
+        layout(&mut scope);
+        let commands: Vec<_> = scope.end().collect();
+
+        let encoded = encode_frame(commands.iter().cloned(), &mut Images);
+        let frame = FrameView::parse(&encoded).unwrap();
+        assert_eq!(frame.len(), commands.len());
+        assert_eq!(frame.strings(), ["repeated"]);
+
+        for (view, command) in frame.commands().zip(&commands) {
+            let decoded = view.to_render_command(&Images).unwrap();
+            assert_eq!(decoded.id, command.id);
+            assert_eq!(decoded.z_index, command.z_index);
+            assert_eq!(decoded.bounding_box, command.bounding_box);
+            match (&decoded.config, &command.config) {
+                (RenderCommandConfig::Image(decoded), RenderCommandConfig::Image(command)) => {
+                    assert_eq!(decoded.data, command.data);
+                    assert_eq!(decoded.corner_radii, command.corner_radii);
+                }
+                (
+                    RenderCommandConfig::Rectangle(decoded),
+                    RenderCommandConfig::Rectangle(command),
+                ) => assert_eq!(decoded, command),
+                (RenderCommandConfig::Border(decoded), RenderCommandConfig::Border(command)) => {
+                    assert_eq!(decoded, command)
+                }
+                (RenderCommandConfig::Text(decoded), RenderCommandConfig::Text(command)) => {
+                    assert_eq!(decoded, command)
+                }
+                (
+                    RenderCommandConfig::ScissorStart(decoded),
+                    RenderCommandConfig::ScissorStart(command),
+                ) => assert_eq!(decoded, command),
+                (RenderCommandConfig::ScissorEnd(), RenderCommandConfig::ScissorEnd()) => {}
+                (decoded, command) => panic!("{decoded:?} decoded as {command:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_frames() {
+        let mut harness = LayoutHarness::new();
+        let clay = harness.clay();
+        let mut scope = clay.begin::<u64, ()>();
+        layout(&mut scope);
+        let encoded = encode_frame(scope.end(), &mut Images);
+
+        assert!(matches!(
+            FrameView::parse(&encoded[..encoded.len() - 1]),
+            Err(StreamError::Truncated)
+        ));
+        assert!(matches!(
+            FrameView::parse(b"JUNKJUNKJUNKJUNK"),
+            Err(StreamError::BadMagic)
+        ));
+
+        let mut future = encoded.clone();
+        future[4] = 2;
+        assert!(matches!(
+            FrameView::parse(&future),
+            Err(StreamError::UnsupportedVersion(2))
+        ));
+
+        // Counts larger than the data are rejected before anything is allocated for them
+        let mut header = encoded[..FRAME_HEADER_SIZE].to_vec();
+        header[6..10].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            FrameView::parse(&header),
+            Err(StreamError::Truncated)
+        ));
+        let mut header = encoded[..FRAME_HEADER_SIZE].to_vec();
+        header[10..14].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            FrameView::parse(&header),
+            Err(StreamError::Truncated)
+        ));
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let mut harness = LayoutHarness::new();
+        let clay = harness.clay();
+        let mut recorder = FrameRecorder::new(Vec::new()).unwrap();
+        for frame in 0..3 {
+            let mut scope = clay.begin::<u64, ()>();
+            layout(&mut scope);
+            recorder
+                .record(frame as f64 / 60., scope.end(), &mut Images)
+                .unwrap();
+        }
+        let recording = recorder.finish().unwrap();
+
+        let frames = Replay::new(recording.as_slice())
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[2].time, 2. / 60.);
+        assert_eq!(frames[0].data, frames[2].data);
+        assert!(frames[1].view().unwrap().commands().count() > 0);
+
+        let truncated = Replay::new(&recording[..recording.len() - 1])
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>();
+        assert!(truncated.is_err());
+
+        // A frame announcing gigabytes of data ends with the recording
+        let mut huge = recording[..6].to_vec();
+        huge.extend_from_slice(&0f64.to_le_bytes());
+        huge.extend_from_slice(&u32::MAX.to_le_bytes());
+        huge.extend_from_slice(&recording[18..64]);
+        let mut replay = Replay::new(huge.as_slice()).unwrap();
+        assert!(matches!(replay.next(), Some(Err(StreamError::Truncated))));
+    }
+}
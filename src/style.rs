@@ -0,0 +1,395 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::{bindings::*, color::Color, Declaration};
+
+/// Interaction state of an element, computed from the pointer data of the previous frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InteractionState {
+    /// The pointer is over the element.
+    pub hovered: bool,
+    /// The pointer is over the element and pressed.
+    pub active: bool,
+    /// The element has the focus, see [`Clay::set_focus`](crate::Clay::set_focus).
+    pub focused: bool,
+    /// The element was declared as disabled.
+    pub disabled: bool,
+}
+
+/// Properties of a [`StateStyle`] replaced while the element is in a given state.
+///
+/// Fields left to `None` keep the value of the base declaration.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StyleOverride {
+    pub background_color: Option<Color>,
+    pub border_color: Option<Color>,
+    pub border_width: Option<u16>,
+    pub corner_radius: Option<f32>,
+}
+
+impl StyleOverride {
+    /// Creates an override that keeps every property of the base declaration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the background color.
+    #[inline]
+    pub fn background_color(&mut self, color: Color) -> &mut Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// Sets the border color.
+    #[inline]
+    pub fn border_color(&mut self, color: Color) -> &mut Self {
+        self.border_color = Some(color);
+        self
+    }
+
+    /// Sets the same border width for all sides.
+    #[inline]
+    pub fn border_width(&mut self, width: u16) -> &mut Self {
+        self.border_width = Some(width);
+        self
+    }
+
+    /// Sets all four corner radii to the same value.
+    #[inline]
+    pub fn corner_radius(&mut self, radius: f32) -> &mut Self {
+        self.corner_radius = Some(radius);
+        self
+    }
+
+    fn apply<ImageElementData, CustomElementData>(
+        &self,
+        declaration: &mut Declaration<'_, ImageElementData, CustomElementData>,
+    ) {
+        if let Some(color) = self.background_color {
+            declaration.background_color(color);
+        }
+        if let Some(color) = self.border_color {
+            declaration.border().color(color);
+        }
+        if let Some(width) = self.border_width {
+            declaration.border().all_directions(width);
+        }
+        if let Some(radius) = self.corner_radius {
+            declaration.corner_radius().all(radius);
+        }
+    }
+}
+
+/// A declaration with overrides for the hover, active, focus and disabled states.
+///
+/// Used with [`ClayLayoutScope::with_state_style`](crate::ClayLayoutScope::with_state_style),
+/// which picks the overrides matching the state of the element. Overrides are layered in the
+/// order focus, hover, active. A disabled element only uses the disabled override.
+pub struct StateStyle<'render, ImageElementData, CustomElementData> {
+    pub base: Declaration<'render, ImageElementData, CustomElementData>,
+    pub hover: StyleOverride,
+    pub active: StyleOverride,
+    pub focus: StyleOverride,
+    pub disabled: StyleOverride,
+    /// Duration in seconds of the color and corner radius transition when the state changes,
+    /// `0.0` switches instantly.
+    pub transition: f32,
+}
+
+impl<'render, ImageElementData: 'render, CustomElementData: 'render>
+    StateStyle<'render, ImageElementData, CustomElementData>
+{
+    /// Creates a style using `base` in every state.
+    pub fn new(base: &Declaration<'render, ImageElementData, CustomElementData>) -> Self {
+        Self {
+            base: copy_declaration(base),
+            hover: StyleOverride::default(),
+            active: StyleOverride::default(),
+            focus: StyleOverride::default(),
+            disabled: StyleOverride::default(),
+            transition: 0.,
+        }
+    }
+
+    /// Sets the override used while the element is hovered.
+    #[inline]
+    pub fn hover(&mut self, style: StyleOverride) -> &mut Self {
+        self.hover = style;
+        self
+    }
+
+    /// Sets the override used while the element is hovered and the pointer is pressed.
+    #[inline]
+    pub fn active(&mut self, style: StyleOverride) -> &mut Self {
+        self.active = style;
+        self
+    }
+
+    /// Sets the override used while the element has the focus.
+    #[inline]
+    pub fn focus(&mut self, style: StyleOverride) -> &mut Self {
+        self.focus = style;
+        self
+    }
+
+    /// Sets the override used while the element is disabled.
+    #[inline]
+    pub fn disabled(&mut self, style: StyleOverride) -> &mut Self {
+        self.disabled = style;
+        self
+    }
+
+    /// Sets the transition duration in seconds.
+    #[inline]
+    pub fn transition(&mut self, duration: f32) -> &mut Self {
+        self.transition = duration;
+        self
+    }
+
+    /// Returns the declaration matching `state`.
+    pub fn resolve(
+        &self,
+        state: InteractionState,
+    ) -> Declaration<'render, ImageElementData, CustomElementData> {
+        let mut declaration = copy_declaration(&self.base);
+
+        if state.disabled {
+            self.disabled.apply(&mut declaration);
+            return declaration;
+        }
+        if state.focused {
+            self.focus.apply(&mut declaration);
+        }
+        if state.hovered {
+            self.hover.apply(&mut declaration);
+        }
+        if state.active {
+            self.active.apply(&mut declaration);
+        }
+
+        declaration
+    }
+}
+
+fn copy_declaration<'render, ImageElementData, CustomElementData>(
+    declaration: &Declaration<'render, ImageElementData, CustomElementData>,
+) -> Declaration<'render, ImageElementData, CustomElementData> {
+    Declaration {
+        inner: declaration.inner,
+        _phantom: PhantomData,
+    }
+}
+
+/// Animated properties of an element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Visuals {
+    background_color: Color,
+    border_color: Color,
+    corner_radius: [f32; 4],
+}
+
+impl Visuals {
+    fn from_declaration<ImageElementData, CustomElementData>(
+        declaration: &Declaration<'_, ImageElementData, CustomElementData>,
+    ) -> Self {
+        let radius = declaration.inner.cornerRadius;
+        Self {
+            background_color: declaration.inner.backgroundColor.into(),
+            border_color: declaration.inner.border.color.into(),
+            corner_radius: [
+                radius.topLeft,
+                radius.topRight,
+                radius.bottomLeft,
+                radius.bottomRight,
+            ],
+        }
+    }
+
+    fn apply<ImageElementData, CustomElementData>(
+        &self,
+        declaration: &mut Declaration<'_, ImageElementData, CustomElementData>,
+    ) {
+        declaration.inner.backgroundColor = self.background_color.into();
+        declaration.inner.border.color = self.border_color.into();
+        declaration.inner.cornerRadius = Clay_CornerRadius {
+            topLeft: self.corner_radius[0],
+            topRight: self.corner_radius[1],
+            bottomLeft: self.corner_radius[2],
+            bottomRight: self.corner_radius[3],
+        };
+    }
+
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        let lerp = |from: f32, to: f32| from + (to - from) * t;
+        let lerp_color = |from: Color, to: Color| {
+            Color::rgba(
+                lerp(from.r, to.r),
+                lerp(from.g, to.g),
+                lerp(from.b, to.b),
+                lerp(from.a, to.a),
+            )
+        };
+        Self {
+            background_color: lerp_color(self.background_color, to.background_color),
+            border_color: lerp_color(self.border_color, to.border_color),
+            corner_radius: core::array::from_fn(|i| {
+                lerp(self.corner_radius[i], to.corner_radius[i])
+            }),
+        }
+    }
+}
+
+/// A running transition of a [`StateStyle`], stored by [`Clay`](crate::Clay) between frames.
+#[derive(Debug, Clone)]
+pub(crate) struct Transition {
+    id: u32,
+    from: Visuals,
+    to: Visuals,
+    elapsed: f32,
+    duration: f32,
+    /// Whether the element was declared since the last call to `Clay::update_transitions`
+    seen: bool,
+}
+
+impl Transition {
+    fn current(&self) -> Visuals {
+        if self.elapsed >= self.duration {
+            self.to
+        } else {
+            self.from.lerp(&self.to, self.elapsed / self.duration)
+        }
+    }
+}
+
+/// Replaces the animated properties of `declaration` with the current value of the transition
+/// of element `id`, starting a new transition if the target changed.
+pub(crate) fn animate<ImageElementData, CustomElementData>(
+    transitions: &mut Vec<Transition>,
+    id: u32,
+    duration: f32,
+    declaration: &mut Declaration<'_, ImageElementData, CustomElementData>,
+) {
+    let target = Visuals::from_declaration(declaration);
+
+    let transition = match transitions
+        .iter_mut()
+        .find(|transition| transition.id == id)
+    {
+        Some(transition) => transition,
+        None => {
+            transitions.push(Transition {
+                id,
+                from: target,
+                to: target,
+                elapsed: duration,
+                duration,
+                seen: true,
+            });
+            return;
+        }
+    };
+
+    transition.seen = true;
+    if transition.to != target {
+        transition.from = transition.current();
+        transition.to = target;
+        transition.elapsed = 0.;
+        transition.duration = duration;
+    }
+    transition.current().apply(declaration);
+}
+
+/// Advances all transitions by `delta_time` and drops the ones of elements that were not declared
+/// since the previous update.
+pub(crate) fn update_transitions(transitions: &mut Vec<Transition>, delta_time: f32) {
+    transitions.retain(|transition| transition.seen);
+    for transition in transitions.iter_mut() {
+        transition.elapsed += delta_time;
+        transition.seen = false;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        layout::Sizing, math::Dimensions, render_commands::RenderCommandConfig, Clay, Vector2,
+    };
+
+    const BASE: Color = Color::rgb(100., 100., 100.);
+    const HOVER: Color = Color::rgb(200., 200., 200.);
+
+    fn button_color(clay: &mut Clay) -> Color {
+        let mut clay = clay.begin::<(), ()>();
+        let mut style = StateStyle::new(
+            Declaration::new()
+                .id(clay.id("button"))
+                .layout()
+                .width(Sizing::Fixed(100.))
+                .height(Sizing::Fixed(100.))
+                .end()
+                .background_color(BASE),
+        );
+        style
+            .hover(*StyleOverride::new().background_color(HOVER))
+            .transition(1.);
+
+        clay.with_state_style(&style, false, |_| {});
+
+        clay.end()
+            .find_map(|command| match command.config {
+                RenderCommandConfig::Rectangle(rectangle) => Some(rectangle.color),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn resolve_layers_states() {
+        let mut style = StateStyle::<(), ()>::new(Declaration::new().background_color(BASE));
+        style
+            .hover(*StyleOverride::new().background_color(HOVER).border_width(2))
+            .active(*StyleOverride::new().corner_radius(4.))
+            .disabled(*StyleOverride::new().background_color(Color::rgb(0., 0., 0.)));
+
+        let base = style.resolve(InteractionState::default());
+        assert_eq!(Color::from(base.inner.backgroundColor), BASE);
+
+        let active = style.resolve(InteractionState {
+            hovered: true,
+            active: true,
+            ..Default::default()
+        });
+        assert_eq!(Color::from(active.inner.backgroundColor), HOVER);
+        assert_eq!(active.inner.border.width.top, 2);
+        assert_eq!(active.inner.cornerRadius.bottomRight, 4.);
+
+        let disabled = style.resolve(InteractionState {
+            hovered: true,
+            disabled: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            Color::from(disabled.inner.backgroundColor),
+            Color::rgb(0., 0., 0.)
+        );
+        assert_eq!(disabled.inner.border.width.top, 0);
+    }
+
+    #[test]
+    fn hover_transition() {
+        let mut clay = Clay::new(Dimensions::new(800.0, 600.0));
+
+        assert_eq!(button_color(&mut clay), BASE);
+
+        clay.pointer_state(Vector2::new(50., 50.), false);
+        clay.update_transitions(0.5);
+        assert_eq!(button_color(&mut clay), Color::rgb(100., 100., 100.));
+
+        clay.update_transitions(0.5);
+        assert_eq!(button_color(&mut clay), Color::rgb(150., 150., 150.));
+
+        clay.update_transitions(0.5);
+        assert_eq!(button_color(&mut clay), HOVER);
+    }
+}
@@ -0,0 +1,785 @@
+//! Tessellation of render commands into triangle meshes.
+//!
+//! GPU backends draw rectangles, borders and images as triangles. [`Tessellator`] turns them
+//! into a [`Mesh`] of indexed vertices, with rounded corners approximated within a curve
+//! tolerance and an optional anti-aliasing fringe fading out the edges. Text and custom elements
+//! are left to the backend. The module only needs `core` and `alloc`, without `std` its float
+//! functions come from the `libm` feature.
+
+use alloc::vec::Vec;
+use core::f32::consts::FRAC_PI_2;
+
+use crate::{
+    clip::{Clip, ClipStack},
+    color::Color,
+    math::{BoundingBox, Vector2},
+    render_commands::{Border, CornerRadii, Image, Rectangle, RenderCommand, RenderCommandConfig},
+};
+
+/// Float functions of `std`, or of `libm` without it.
+mod float {
+    #[cfg(feature = "std")]
+    pub fn acos(x: f32) -> f32 {
+        x.acos()
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub fn acos(x: f32) -> f32 {
+        libm::acosf(x)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn ceil(x: f32) -> f32 {
+        x.ceil()
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub fn ceil(x: f32) -> f32 {
+        libm::ceilf(x)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn cos(x: f32) -> f32 {
+        x.cos()
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub fn cos(x: f32) -> f32 {
+        libm::cosf(x)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn sin(x: f32) -> f32 {
+        x.sin()
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub fn sin(x: f32) -> f32 {
+        libm::sinf(x)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn hypot(x: f32, y: f32) -> f32 {
+        x.hypot(y)
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub fn hypot(x: f32, y: f32) -> f32 {
+        libm::hypotf(x, y)
+    }
+}
+
+/// Most segments a quarter circle is split into, whatever the tolerance.
+const MAX_CORNER_SEGMENTS: usize = 64;
+
+/// Vertex of a [`Mesh`], laid out to be uploaded as is to a vertex buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[repr(C)]
+pub struct Vertex {
+    pub position: [f32; 2],
+    /// RGBA color from 0 to 1, with the alpha faded to 0 on the anti-aliasing fringe.
+    pub color: [f32; 4],
+    /// Position in the bounds of the command, from `(0, 0)` at the top left to `(1, 1)`.
+    pub uv: [f32; 2],
+    /// Index of the clip in [`Mesh::clips`] plus one, `0` when unclipped.
+    pub clip_id: u32,
+}
+
+/// Triangles to draw, three indices into `vertices` each.
+#[derive(Debug, Clone, Default)]
+pub struct Mesh {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+    /// Clips referenced by the `clip_id` of the vertices.
+    pub clips: Vec<Clip>,
+}
+
+impl Mesh {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Removes the vertices, indices and clips, keeping the allocations.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+        self.clips.clear();
+    }
+}
+
+/// Shared attributes of the vertices of one command.
+struct Paint {
+    bounds: BoundingBox,
+    color: [f32; 4],
+    clip_id: u32,
+}
+
+impl Paint {
+    fn new(bounds: BoundingBox, color: Color, clip_id: u32) -> Self {
+        Self {
+            bounds,
+            color: [
+                color.r / 255.,
+                color.g / 255.,
+                color.b / 255.,
+                color.a / 255.,
+            ],
+            clip_id,
+        }
+    }
+
+    fn vertex(&self, position: Vector2, opaque: bool) -> Vertex {
+        let mut color = self.color;
+        if !opaque {
+            color[3] = 0.;
+        }
+        let uv = |offset: f32, size: f32| if size > 0. { offset / size } else { 0. };
+        Vertex {
+            position: [position.x, position.y],
+            color,
+            uv: [
+                uv(position.x - self.bounds.x, self.bounds.width),
+                uv(position.y - self.bounds.y, self.bounds.height),
+            ],
+            clip_id: self.clip_id,
+        }
+    }
+}
+
+/// Converts rectangles, borders and images to triangles.
+///
+/// ```
+/// use clay_layout::{
+///     math::BoundingBox,
+///     render_commands::{CornerRadii, Rectangle},
+///     tessellate::{Mesh, Tessellator},
+///     Color,
+/// };
+///
+/// let mut mesh = Mesh::new();
+/// let rectangle = Rectangle {
+///     color: Color::rgb(255., 0., 0.),
+///     corner_radii: CornerRadii::default(),
+/// };
+/// Tessellator::new().rectangle(&mut mesh, BoundingBox::new(0., 0., 10., 10.), &rectangle, 0);
+/// // A fan of four triangles around the center
+/// assert_eq!(mesh.indices.len(), 12);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Tessellator {
+    tolerance: f32,
+    fringe: f32,
+}
+
+impl Default for Tessellator {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.25,
+            fringe: 0.,
+        }
+    }
+}
+
+impl Tessellator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Largest distance between a rounded corner and its segments, `0.25` by default.
+    pub fn tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Width of the band over which the edges fade out, centered on the edges. `0`, the
+    /// default, disables anti-aliasing.
+    pub fn fringe(mut self, fringe: f32) -> Self {
+        self.fringe = fringe;
+        self
+    }
+
+    /// Tessellates the rectangles, borders and images of `render_commands`, with the clip of
+    /// each command resolved by a [`ClipStack`].
+    pub fn tessellate<'a, ImageElementData: 'a, CustomElementData: 'a>(
+        &self,
+        render_commands: impl IntoIterator<
+            Item = RenderCommand<'a, ImageElementData, CustomElementData>,
+        >,
+    ) -> Mesh {
+        let mut mesh = Mesh::new();
+        for clipped in ClipStack::new().cull(true).resolve(render_commands) {
+            let clip_id = match clipped.clip {
+                None => 0,
+                Some(clip) => {
+                    if mesh.clips.last() != Some(&clip) {
+                        mesh.clips.push(clip);
+                    }
+                    mesh.clips.len() as u32
+                }
+            };
+            let bounds = clipped.command.bounding_box;
+            match &clipped.command.config {
+                RenderCommandConfig::Rectangle(rectangle) => {
+                    self.rectangle(&mut mesh, bounds, rectangle, clip_id)
+                }
+                RenderCommandConfig::Border(border) => {
+                    self.border(&mut mesh, bounds, border, clip_id)
+                }
+                RenderCommandConfig::Image(image) => self.image(&mut mesh, bounds, image, clip_id),
+                _ => {}
+            }
+        }
+        mesh
+    }
+
+    /// Adds a filled rounded rectangle to `mesh`.
+    pub fn rectangle(
+        &self,
+        mesh: &mut Mesh,
+        bounds: BoundingBox,
+        rectangle: &Rectangle,
+        clip_id: u32,
+    ) {
+        let paint = Paint::new(bounds, rectangle.color, clip_id);
+        self.fill(mesh, &paint, &rectangle.corner_radii);
+    }
+
+    /// Adds an image quad to `mesh`, its `uv` mapping the whole texture. The vertices are tinted
+    /// by the background color of the image, or white if it is transparent.
+    pub fn image<ImageElementData>(
+        &self,
+        mesh: &mut Mesh,
+        bounds: BoundingBox,
+        image: &Image<'_, ImageElementData>,
+        clip_id: u32,
+    ) {
+        let tint = if image.background_color.a > 0. {
+            image.background_color
+        } else {
+            Color::rgba(255., 255., 255., 255.)
+        };
+        let paint = Paint::new(bounds, tint, clip_id);
+        self.fill(mesh, &paint, &image.corner_radii);
+    }
+
+    /// Adds the band between the outer edge of `bounds` and the inner edge of the border to
+    /// `mesh`. The inner corners are rounded by the outer radius less the adjacent widths.
+    pub fn border(&self, mesh: &mut Mesh, bounds: BoundingBox, border: &Border, clip_id: u32) {
+        let width = &border.width;
+        if width.left == 0 && width.right == 0 && width.top == 0 && width.bottom == 0 {
+            return;
+        }
+        let paint = Paint::new(bounds, border.color, clip_id);
+        let radii = clamp_radii(bounds, &border.corner_radii);
+        let (outer, inner) = self.border_outlines(bounds, &radii, border);
+        let count = outer.len();
+        let outer_normals = normals(&outer);
+        let inner_normals = normals(&inner);
+        let inset = self.fringe / 2.;
+
+        let base = mesh.vertices.len() as u32;
+        for index in 0..count {
+            mesh.vertices
+                .push(paint.vertex(outer[index] - outer_normals[index] * inset, true));
+        }
+        for index in 0..count {
+            mesh.vertices
+                .push(paint.vertex(inner[index] + inner_normals[index] * inset, true));
+        }
+        stitch(mesh, base, base + count as u32, count);
+
+        if self.fringe > 0. {
+            let outer_fringe = mesh.vertices.len() as u32;
+            for index in 0..count {
+                mesh.vertices
+                    .push(paint.vertex(outer[index] + outer_normals[index] * inset, false));
+            }
+            let inner_fringe = mesh.vertices.len() as u32;
+            for index in 0..count {
+                mesh.vertices
+                    .push(paint.vertex(inner[index] - inner_normals[index] * inset, false));
+            }
+            stitch(mesh, outer_fringe, base, count);
+            stitch(mesh, base + count as u32, inner_fringe, count);
+        }
+    }
+
+    /// Adds the convex rounded rectangle of `paint` as a fan around its center.
+    fn fill(&self, mesh: &mut Mesh, paint: &Paint, radii: &CornerRadii) {
+        let bounds = paint.bounds;
+        if bounds.is_empty() {
+            return;
+        }
+        let radii = clamp_radii(bounds, radii);
+        let mut outline = Vec::new();
+        for corner in Corner::ALL {
+            let radius = corner.radius(&radii);
+            let center = corner.center(bounds, radius, radius);
+            self.arc(
+                &mut outline,
+                corner,
+                center,
+                radius,
+                radius,
+                self.segments(radius),
+            );
+        }
+        outline.dedup_by(|a, b| length(*a - *b) <= f32::EPSILON);
+        if outline.len() > 1 && length(outline[0] - outline[outline.len() - 1]) <= f32::EPSILON {
+            outline.pop();
+        }
+        let count = outline.len();
+        let outline_normals = normals(&outline);
+        let inset = self.fringe / 2.;
+
+        let center = mesh.vertices.len() as u32;
+        let middle = Vector2::new(bounds.x + bounds.width / 2., bounds.y + bounds.height / 2.);
+        mesh.vertices.push(paint.vertex(middle, true));
+        for (point, normal) in outline.iter().zip(&outline_normals) {
+            mesh.vertices
+                .push(paint.vertex(*point - *normal * inset, true));
+        }
+        for index in 0..count as u32 {
+            let next = (index + 1) % count as u32;
+            mesh.indices
+                .extend([center, center + 1 + index, center + 1 + next]);
+        }
+
+        if self.fringe > 0. {
+            let fringe = mesh.vertices.len() as u32;
+            for (point, normal) in outline.iter().zip(&outline_normals) {
+                mesh.vertices
+                    .push(paint.vertex(*point + *normal * inset, false));
+            }
+            stitch(mesh, fringe, center + 1, count);
+        }
+    }
+
+    /// Returns the outer and inner outlines of a border, with the same number of points so they
+    /// can be stitched.
+    fn border_outlines(
+        &self,
+        bounds: BoundingBox,
+        radii: &CornerRadii,
+        border: &Border,
+    ) -> (Vec<Vector2>, Vec<Vector2>) {
+        let width = &border.width;
+        let mut outer = Vec::new();
+        let mut inner = Vec::new();
+        for corner in Corner::ALL {
+            let radius = corner.radius(radii);
+            let (side_x, side_y) = match corner {
+                Corner::TopLeft => (width.left, width.top),
+                Corner::TopRight => (width.right, width.top),
+                Corner::BottomRight => (width.right, width.bottom),
+                Corner::BottomLeft => (width.left, width.bottom),
+            };
+            let (side_x, side_y) = (side_x as f32, side_y as f32);
+            let segments = self.segments(radius);
+            self.arc(
+                &mut outer,
+                corner,
+                corner.center(bounds, radius, radius),
+                radius,
+                radius,
+                segments,
+            );
+            // The inner corner is square where a side is wider than the radius
+            let inner_x = radius.max(side_x);
+            let inner_y = radius.max(side_y);
+            let center = corner.center(bounds, inner_x, inner_y);
+            self.arc(
+                &mut inner,
+                corner,
+                center,
+                inner_x - side_x,
+                inner_y - side_y,
+                segments,
+            );
+        }
+        (outer, inner)
+    }
+
+    /// Number of segments a quarter circle of `radius` is split into to stay within the
+    /// tolerance, `0` for square corners.
+    fn segments(&self, radius: f32) -> usize {
+        if radius <= 0. {
+            return 0;
+        }
+        if self.tolerance <= 0. {
+            return MAX_CORNER_SEGMENTS;
+        }
+        if self.tolerance >= radius {
+            return 1;
+        }
+        // Each segment spans the angle whose chord is `tolerance` away from the arc
+        let step = 2. * float::acos(1. - self.tolerance / radius);
+        (float::ceil(FRAC_PI_2 / step) as usize).clamp(1, MAX_CORNER_SEGMENTS)
+    }
+
+    /// Appends the `segments + 1` points of the quarter ellipse of `corner`, clockwise.
+    fn arc(
+        &self,
+        outline: &mut Vec<Vector2>,
+        corner: Corner,
+        center: Vector2,
+        radius_x: f32,
+        radius_y: f32,
+        segments: usize,
+    ) {
+        let start = corner.start_angle();
+        for segment in 0..=segments {
+            let angle = if segments == 0 {
+                start
+            } else {
+                start + FRAC_PI_2 * segment as f32 / segments as f32
+            };
+            outline.push(Vector2::new(
+                center.x + radius_x * float::cos(angle),
+                center.y + radius_y * float::sin(angle),
+            ));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Corner {
+    TopLeft,
+    TopRight,
+    BottomRight,
+    BottomLeft,
+}
+
+impl Corner {
+    /// Corners in clockwise order, starting at the top left.
+    const ALL: [Corner; 4] = [
+        Corner::TopLeft,
+        Corner::TopRight,
+        Corner::BottomRight,
+        Corner::BottomLeft,
+    ];
+
+    fn radius(self, radii: &CornerRadii) -> f32 {
+        match self {
+            Corner::TopLeft => radii.top_left,
+            Corner::TopRight => radii.top_right,
+            Corner::BottomRight => radii.bottom_right,
+            Corner::BottomLeft => radii.bottom_left,
+        }
+    }
+
+    /// Center of the corner arc with the given radii inside `bounds`.
+    fn center(self, bounds: BoundingBox, radius_x: f32, radius_y: f32) -> Vector2 {
+        let left = bounds.x + radius_x;
+        let right = bounds.x + bounds.width - radius_x;
+        let top = bounds.y + radius_y;
+        let bottom = bounds.y + bounds.height - radius_y;
+        match self {
+            Corner::TopLeft => Vector2::new(left, top),
+            Corner::TopRight => Vector2::new(right, top),
+            Corner::BottomRight => Vector2::new(right, bottom),
+            Corner::BottomLeft => Vector2::new(left, bottom),
+        }
+    }
+
+    /// Angle the arc starts at, with y pointing down.
+    fn start_angle(self) -> f32 {
+        match self {
+            Corner::TopLeft => 2. * FRAC_PI_2,
+            Corner::TopRight => 3. * FRAC_PI_2,
+            Corner::BottomRight => 0.,
+            Corner::BottomLeft => FRAC_PI_2,
+        }
+    }
+}
+
+/// Scales the radii down so the corners of each side fit, like CSS does.
+fn clamp_radii(bounds: BoundingBox, radii: &CornerRadii) -> CornerRadii {
+    let fit = |length: f32, first: f32, second: f32| {
+        if first + second > length && first + second > 0. {
+            length / (first + second)
+        } else {
+            1.
+        }
+    };
+    let scale = fit(bounds.width, radii.top_left, radii.top_right)
+        .min(fit(bounds.width, radii.bottom_left, radii.bottom_right))
+        .min(fit(bounds.height, radii.top_left, radii.bottom_left))
+        .min(fit(bounds.height, radii.top_right, radii.bottom_right))
+        .max(0.);
+    CornerRadii {
+        top_left: radii.top_left.max(0.) * scale,
+        top_right: radii.top_right.max(0.) * scale,
+        bottom_left: radii.bottom_left.max(0.) * scale,
+        bottom_right: radii.bottom_right.max(0.) * scale,
+    }
+}
+
+/// Returns the outward offset directions of the points of a clockwise closed outline, scaled so
+/// the offset edges stay parallel to the original ones. Repeated points share the normal of the
+/// edges around them.
+fn normals(outline: &[Vector2]) -> Vec<Vector2> {
+    let count = outline.len();
+    let edge_normal = |from: usize, step: usize| {
+        for offset in 1..count {
+            let to = (from + step * offset) % count;
+            let delta = outline[to] - outline[from];
+            let length = length(delta);
+            if length > f32::EPSILON {
+                let direction = if step == 1 { delta } else { delta * -1. };
+                return Vector2::new(direction.y / length, -direction.x / length);
+            }
+        }
+        Vector2::new(0., 0.)
+    };
+    (0..count)
+        .map(|index| {
+            let before = edge_normal(index, count - 1);
+            let after = edge_normal(index, 1);
+            let average = (before + after) * 0.5;
+            let length_squared = average.x * average.x + average.y * average.y;
+            if length_squared > f32::EPSILON {
+                average * (1. / length_squared)
+            } else {
+                average
+            }
+        })
+        .collect()
+}
+
+fn length(vector: Vector2) -> f32 {
+    float::hypot(vector.x, vector.y)
+}
+
+/// Adds the quads between two closed outlines of `count` vertices starting at `first` and
+/// `second`.
+fn stitch(mesh: &mut Mesh, first: u32, second: u32, count: usize) {
+    let count = count as u32;
+    for index in 0..count {
+        let next = (index + 1) % count;
+        mesh.indices
+            .extend([first + index, first + next, second + next]);
+        mesh.indices
+            .extend([first + index, second + next, second + index]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::render_commands::BorderWidth;
+    use core::f32::consts::PI;
+
+    /// Area covered by the mesh, each triangle weighted by the average alpha of its vertices.
+    fn coverage(mesh: &Mesh) -> f32 {
+        mesh.indices
+            .chunks(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|corner| mesh.vertices[triangle[corner] as usize]);
+                let area = ((b.position[0] - a.position[0]) * (c.position[1] - a.position[1])
+                    - (c.position[0] - a.position[0]) * (b.position[1] - a.position[1]))
+                    .abs()
+                    / 2.;
+                let alpha = (a.color[3] + b.color[3] + c.color[3]) / 3.;
+                area * alpha
+            })
+            .sum()
+    }
+
+    fn rounded_area(width: f32, height: f32, radius: f32) -> f32 {
+        width * height - (4. - PI) * radius * radius
+    }
+
+    fn radii(radius: f32) -> CornerRadii {
+        CornerRadii {
+            top_left: radius,
+            top_right: radius,
+            bottom_left: radius,
+            bottom_right: radius,
+        }
+    }
+
+    fn rectangle(radius: f32) -> Rectangle {
+        Rectangle {
+            color: Color::rgb(255., 255., 255.),
+            corner_radii: radii(radius),
+        }
+    }
+
+    fn border(width: BorderWidth, radius: f32) -> Border {
+        Border {
+            color: Color::rgb(255., 255., 255.),
+            corner_radii: radii(radius),
+            width,
+        }
+    }
+
+    fn uniform_width(width: u16) -> BorderWidth {
+        BorderWidth {
+            left: width,
+            right: width,
+            top: width,
+            bottom: width,
+            between_children: 0,
+        }
+    }
+
+    #[test]
+    fn rectangle_area() {
+        let bounds = BoundingBox::new(10., 20., 100., 60.);
+        let mut mesh = Mesh::new();
+        Tessellator::new().rectangle(&mut mesh, bounds, &rectangle(0.), 0);
+        assert_eq!(coverage(&mesh), 6000.);
+    }
+
+    #[test]
+    fn rounded_rectangle_area_converges() {
+        let bounds = BoundingBox::new(0., 0., 100., 60.);
+        let expected = rounded_area(100., 60., 20.);
+        for tolerance in [1., 0.25, 0.01] {
+            let mut mesh = Mesh::new();
+            Tessellator::new().tolerance(tolerance).rectangle(
+                &mut mesh,
+                bounds,
+                &rectangle(20.),
+                0,
+            );
+            let area = coverage(&mesh);
+            // Chords cut inside the arcs, at most `tolerance` away along the four quarter arcs
+            assert!(area <= expected);
+            assert!(
+                expected - area <= tolerance * 2. * PI * 20.,
+                "{tolerance}: {area}"
+            );
+        }
+    }
+
+    #[test]
+    fn oversized_radii_make_a_circle() {
+        let bounds = BoundingBox::new(0., 0., 40., 40.);
+        let mut mesh = Mesh::new();
+        Tessellator::new()
+            .tolerance(0.01)
+            .rectangle(&mut mesh, bounds, &rectangle(100.), 0);
+        assert!((coverage(&mesh) - PI * 400.).abs() < 2.);
+    }
+
+    #[test]
+    fn fringe_keeps_the_area() {
+        let bounds = BoundingBox::new(0., 0., 100., 60.);
+        let mut mesh = Mesh::new();
+        Tessellator::new().tolerance(0.01).fringe(1.).rectangle(
+            &mut mesh,
+            bounds,
+            &rectangle(10.),
+            0,
+        );
+        assert!((coverage(&mesh) - rounded_area(100., 60., 10.)).abs() < 1.);
+        assert!(mesh.vertices.iter().any(|vertex| vertex.color[3] == 0.));
+    }
+
+    #[test]
+    fn border_area() {
+        let bounds = BoundingBox::new(0., 0., 100., 60.);
+        let mut mesh = Mesh::new();
+        Tessellator::new().border(&mut mesh, bounds, &border(uniform_width(5), 0.), 0);
+        assert_eq!(coverage(&mesh), 6000. - 90. * 50.);
+    }
+
+    #[test]
+    fn rounded_border_area() {
+        let bounds = BoundingBox::new(0., 0., 100., 60.);
+        let expected = rounded_area(100., 60., 20.) - rounded_area(90., 50., 15.);
+        for fringe in [0., 1.] {
+            let mut mesh = Mesh::new();
+            Tessellator::new().tolerance(0.01).fringe(fringe).border(
+                &mut mesh,
+                bounds,
+                &border(uniform_width(5), 20.),
+                0,
+            );
+            assert!(
+                (coverage(&mesh) - expected).abs() < 1.,
+                "{fringe}: {}",
+                coverage(&mesh)
+            );
+        }
+    }
+
+    #[test]
+    fn border_with_different_sides() {
+        let bounds = BoundingBox::new(0., 0., 100., 60.);
+        let width = BorderWidth {
+            left: 10,
+            right: 0,
+            top: 4,
+            bottom: 0,
+            between_children: 0,
+        };
+        let mut mesh = Mesh::new();
+        Tessellator::new().border(&mut mesh, bounds, &border(width.clone(), 0.), 0);
+        assert_eq!(coverage(&mesh), 10. * 60. + 90. * 4.);
+
+        // Inner corners keep what the sides leave of the radius: square on the left, 6 by 2 at
+        // the top right and 6 by 6 at the bottom right
+        let mut mesh = Mesh::new();
+        Tessellator::new()
+            .tolerance(0.01)
+            .border(&mut mesh, bounds, &border(width, 6.), 0);
+        let outer = rounded_area(100., 60., 6.);
+        let inner = 90. * 56. - (1. - PI / 4.) * (6. * 2. + 6. * 6.);
+        assert!(
+            (coverage(&mesh) - (outer - inner)).abs() < 0.5,
+            "{}",
+            coverage(&mesh)
+        );
+    }
+
+    #[test]
+    fn tessellate_assigns_clip_ids() {
+        let scissor = RenderCommandConfig::ScissorStart(crate::render_commands::Scissor {
+            horizontal: true,
+            vertical: true,
+            corner_radii: radii(0.),
+        });
+        let commands: [RenderCommand<'static, (), ()>; 5] = [
+            RenderCommand::new(
+                BoundingBox::new(0., 0., 10., 10.),
+                RenderCommandConfig::Rectangle(rectangle(0.)),
+                1,
+                0,
+            ),
+            RenderCommand::new(BoundingBox::new(0., 0., 5., 5.), scissor, 2, 0),
+            RenderCommand::new(
+                BoundingBox::new(0., 0., 10., 10.),
+                RenderCommandConfig::Rectangle(rectangle(0.)),
+                3,
+                0,
+            ),
+            RenderCommand::new(
+                BoundingBox::new(50., 50., 10., 10.),
+                RenderCommandConfig::Rectangle(rectangle(0.)),
+                4,
+                0,
+            ),
+            RenderCommand::new(
+                BoundingBox::default(),
+                RenderCommandConfig::ScissorEnd(),
+                2,
+                0,
+            ),
+        ];
+        let mesh = Tessellator::new().tessellate(commands);
+        assert_eq!(mesh.clips.len(), 1);
+        assert_eq!(mesh.clips[0].bounds, BoundingBox::new(0., 0., 5., 5.));
+        // The rectangle outside of the clip is culled
+        assert_eq!(mesh.vertices.len(), 10);
+        assert_eq!(mesh.vertices[0].clip_id, 0);
+        assert_eq!(mesh.vertices[5].clip_id, 1);
+        assert_eq!(mesh.vertices[5].uv, [0.5, 0.5]);
+    }
+}
@@ -0,0 +1,444 @@
+//! Headless snapshot testing of layouts.
+//!
+//! [`LayoutHarness`] runs a layout without a window or a GPU and compares the produced render
+//! commands against golden files. Set the [`BLESS_ENV`] environment variable to write the
+//! snapshots instead, for example `CLAY_BLESS=1 cargo test`.
+
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    color::Color,
+    math::{BoundingBox, Dimensions},
+    render_commands::{CornerRadii, RenderCommand, RenderCommandConfig},
+    text::TextConfig,
+    Clay, ClayLayoutScope,
+};
+
+/// Environment variable that makes [`LayoutHarness::assert_snapshot`] write the snapshots
+/// instead of comparing them.
+pub const BLESS_ENV: &str = "CLAY_BLESS";
+
+/// Deterministic text measure function: every character is half the font size wide, plus the
+/// letter spacing, and lines are `line_height` high, or `font_size` if not set.
+pub fn monospace_measure(text: &str, config: &TextConfig) -> Dimensions {
+    let char_width = config.font_size as f32 / 2. + config.letter_spacing as f32;
+    let line_height = if config.line_height > 0 {
+        config.line_height
+    } else {
+        config.font_size
+    };
+    Dimensions::new(text.chars().count() as f32 * char_width, line_height as f32)
+}
+
+/// Runs layouts with the [`monospace_measure`] function and checks their render commands
+/// against golden files.
+/// ```no_run
+/// use clay_layout::{layout::Sizing, testing::LayoutHarness, Declaration};
+///
+/// let mut harness = LayoutHarness::new();
+/// harness.assert_snapshot("square", (800., 600.).into(), |clay| {
+///     clay.with(Declaration::new()
+///         .layout()
+///             .width(Sizing::Fixed(100.))
+///             .height(Sizing::Fixed(100.))
+///             .end(), |_| {});
+/// });
+/// ```
+pub struct LayoutHarness {
+    clay: Clay,
+    snapshot_dir: PathBuf,
+}
+
+impl Default for LayoutHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LayoutHarness {
+    /// Creates a harness storing its snapshots in `tests/snapshots` of the crate being tested.
+    pub fn new() -> Self {
+        let mut clay = Clay::new(Dimensions::new(800., 600.));
+        clay.set_measure_text_function(monospace_measure);
+
+        let root = std::env::var_os("CARGO_MANIFEST_DIR").map_or_else(PathBuf::new, PathBuf::from);
+        Self {
+            clay,
+            snapshot_dir: root.join("tests").join("snapshots"),
+        }
+    }
+
+    /// Sets the directory of the golden files.
+    pub fn snapshot_dir(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+        self.snapshot_dir = dir.into();
+        self
+    }
+
+    /// Returns the clay instance used to run the layouts, to change the theme or the measure
+    /// function.
+    pub fn clay(&mut self) -> &mut Clay {
+        &mut self.clay
+    }
+
+    /// Runs `layout` at the given dimensions and returns its serialized render commands.
+    pub fn run<F>(&mut self, dimensions: Dimensions, layout: F) -> String
+    where
+        F: for<'clay, 'render> FnOnce(&mut ClayLayoutScope<'clay, 'render, (), ()>),
+    {
+        self.clay.set_layout_dimensions(dimensions);
+        let mut clay = self.clay.begin::<(), ()>();
+        layout(&mut clay);
+        serialize(clay.end())
+    }
+
+    /// Runs `layout` and compares its render commands with the snapshot `name`, panicking on
+    /// mismatch. The snapshot is written instead if [`BLESS_ENV`] is set.
+    pub fn assert_snapshot<F>(&mut self, name: &str, dimensions: Dimensions, layout: F)
+    where
+        F: for<'clay, 'render> FnOnce(&mut ClayLayoutScope<'clay, 'render, (), ()>),
+    {
+        let actual = self.run(dimensions, layout);
+        self.assert_golden(&format!("{name}.snap"), &actual);
+    }
+
+    /// Compares `actual` with the golden file `file_name` of the snapshot directory, panicking on
+    /// mismatch. Used to check the output of renderers. The file is written instead if
+    /// [`BLESS_ENV`] is set.
+    pub fn assert_golden(&self, file_name: &str, actual: &str) {
+        let path = self.snapshot_dir.join(file_name);
+
+        if std::env::var_os(BLESS_ENV).is_some_and(|value| !value.is_empty() && value != "0") {
+            write_snapshot(&path, actual);
+            return;
+        }
+
+        let expected = match std::fs::read_to_string(&path) {
+            Ok(expected) => expected,
+            Err(error) => panic!(
+                "Missing snapshot {} ({error}), run with {BLESS_ENV}=1 to create it.\n{actual}",
+                path.display()
+            ),
+        };
+
+        if expected != actual {
+            let expected_lines: Vec<&str> = expected.lines().collect();
+            let actual_lines: Vec<&str> = actual.lines().collect();
+            let line = (0..expected_lines.len().max(actual_lines.len()))
+                .find(|&line| expected_lines.get(line) != actual_lines.get(line))
+                .unwrap_or(expected_lines.len());
+            let expected_line = expected_lines.get(line).copied();
+            let actual_line = actual_lines.get(line).copied();
+
+            panic!(
+                "Snapshot {} differs at line {}\n  expected: {}\n  actual:   {}\n\
+                 Run with {BLESS_ENV}=1 to update it. Full output:\n{actual}",
+                path.display(),
+                line + 1,
+                expected_line.unwrap_or("<end of file>"),
+                actual_line.unwrap_or("<end of file>"),
+            );
+        }
+    }
+}
+
+fn write_snapshot(path: &Path, contents: &str) {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).unwrap();
+    }
+    std::fs::write(path, contents).unwrap();
+}
+
+/// Serializes render commands to a stable text format, one command per line.
+pub fn serialize<'render, ImageElementData: 'render, CustomElementData: 'render>(
+    commands: impl Iterator<Item = RenderCommand<'render, ImageElementData, CustomElementData>>,
+) -> String {
+    let mut output = String::new();
+
+    for command in commands {
+        let (kind, details) = match &command.config {
+            RenderCommandConfig::None() => ("none", String::new()),
+            RenderCommandConfig::Rectangle(rectangle) => (
+                "rectangle",
+                format!(
+                    " color={} radius={}",
+                    color(rectangle.color),
+                    radii(&rectangle.corner_radii)
+                ),
+            ),
+            RenderCommandConfig::Border(border) => (
+                "border",
+                format!(
+                    " color={} width=({}, {}, {}, {}, {}) radius={}",
+                    color(border.color),
+                    border.width.left,
+                    border.width.right,
+                    border.width.top,
+                    border.width.bottom,
+                    border.width.between_children,
+                    radii(&border.corner_radii)
+                ),
+            ),
+            RenderCommandConfig::Text(text) => (
+                "text",
+                format!(
+                    " color={} font={} size={} spacing={} line_height={} {:?}",
+                    color(text.color),
+                    text.font_id,
+                    text.font_size,
+                    text.letter_spacing,
+                    text.line_height,
+                    text.text
+                ),
+            ),
+            RenderCommandConfig::Image(image) => (
+                "image",
+                format!(
+                    " background={} radius={}",
+                    color(image.background_color),
+                    radii(&image.corner_radii)
+                ),
+            ),
+            RenderCommandConfig::ScissorStart(scissor) => (
+                "scissor_start",
+                format!(
+                    " horizontal={} vertical={}",
+                    scissor.horizontal, scissor.vertical
+                ),
+            ),
+            RenderCommandConfig::ScissorEnd() => ("scissor_end", String::new()),
+            RenderCommandConfig::Custom(custom) => (
+                "custom",
+                format!(
+                    " background={} radius={}",
+                    color(custom.background_color),
+                    radii(&custom.corner_radii)
+                ),
+            ),
+        };
+
+        writeln!(
+            output,
+            "{kind} id={:08x} z={} bbox={}{details}",
+            command.id,
+            command.z_index,
+            bounding_box(command.bounding_box)
+        )
+        .unwrap();
+    }
+
+    output
+}
+
+fn bounding_box(value: BoundingBox) -> String {
+    format!(
+        "({}, {}, {}, {})",
+        value.x, value.y, value.width, value.height
+    )
+}
+
+fn color(value: Color) -> String {
+    format!("({}, {}, {}, {})", value.r, value.g, value.b, value.a)
+}
+
+fn radii(value: &CornerRadii) -> String {
+    format!(
+        "({}, {}, {}, {})",
+        value.top_left, value.top_right, value.bottom_left, value.bottom_right
+    )
+}
+
+/// Layouts of the examples, shared by the golden tests of the renderers.
+#[cfg(test)]
+pub(crate) mod examples {
+    use crate::{
+        layout::{Alignment, LayoutAlignmentX, LayoutAlignmentY, LayoutDirection, Padding, Sizing},
+        text::TextConfig,
+        ClayLayoutScope, Declaration,
+    };
+
+    /// The layout of the `basic_rectangle` example.
+    #[rustfmt::skip]
+    pub fn basic_rectangle<ImageElementData, CustomElementData>(
+        clay: &mut ClayLayoutScope<'_, '_, ImageElementData, CustomElementData>,
+    ) {
+        clay.with(Declaration::new()
+            .id(clay.id("red_rectangle"))
+            .layout()
+                .width(Sizing::Fixed(50.))
+                .height(Sizing::Fixed(50.))
+                .end()
+            .corner_radius()
+                .all(5.)
+                .end()
+            .background_color((0xFF, 0x00, 0x00).into()), |_| {});
+    }
+
+    /// A reduced version of the layout of the `wgpu` example: a header with buttons, a sidebar
+    /// with a bordered entry and a scrolling document.
+    #[rustfmt::skip]
+    pub fn dashboard<ImageElementData, CustomElementData>(
+        clay: &mut ClayLayoutScope<'_, '_, ImageElementData, CustomElementData>,
+    ) {
+        let theme = *clay.theme();
+        let body = || TextConfig::new().style(&theme.typography.body).color(theme.palette.text).end();
+
+        clay.with(Declaration::new()
+            .id(clay.id("outer_container"))
+            .layout()
+                .width(Sizing::Grow(0., 0.))
+                .height(Sizing::Grow(0., 0.))
+                .direction(LayoutDirection::TopToBottom)
+                .padding(Padding::all(theme.spacing.md))
+                .child_gap(theme.spacing.md)
+                .end()
+            .background_color(theme.palette.background), |clay|
+        {
+            clay.with(Declaration::new()
+                .id(clay.id("header_bar"))
+                .layout()
+                    .width(Sizing::Grow(0., 0.))
+                    .height(Sizing::Fixed(60.))
+                    .padding(Padding::new(theme.spacing.md, theme.spacing.md, 0, 0))
+                    .child_gap(theme.spacing.md)
+                    .child_alignment(Alignment::new(LayoutAlignmentX::Left, LayoutAlignmentY::Center))
+                    .end()
+                .background_color(theme.palette.surface)
+                .corner_radius()
+                    .all(theme.radii.md)
+                    .end(), |clay|
+            {
+                for label in ["File", "Edit & <View>"] {
+                    clay.with(Declaration::new()
+                        .layout()
+                            .padding(Padding::new(theme.spacing.md, theme.spacing.md, theme.spacing.sm, theme.spacing.sm))
+                            .end()
+                        .background_color(theme.palette.control)
+                        .corner_radius()
+                            .all(theme.radii.sm)
+                            .end(), |clay|
+                    {
+                        clay.text(label, body());
+                    });
+                }
+            });
+
+            clay.with(Declaration::new()
+                .id(clay.id("lower_content"))
+                .layout()
+                    .width(Sizing::Grow(0., 0.))
+                    .height(Sizing::Grow(0., 0.))
+                    .child_gap(theme.spacing.md)
+                    .end(), |clay|
+            {
+                clay.with(Declaration::new()
+                    .id(clay.id("sidebar"))
+                    .layout()
+                        .width(Sizing::Percent(0.25))
+                        .height(Sizing::Grow(0., 0.))
+                        .direction(LayoutDirection::TopToBottom)
+                        .padding(Padding::all(theme.spacing.md))
+                        .child_gap(theme.spacing.sm)
+                        .end()
+                    .background_color(theme.palette.surface)
+                    .corner_radius()
+                        .all(theme.radii.md)
+                        .end(), |clay|
+                {
+                    clay.with(Declaration::new()
+                        .layout()
+                            .width(Sizing::Grow(0., 0.))
+                            .padding(Padding::all(theme.spacing.md))
+                            .end()
+                        .background_color(theme.palette.surface_variant)
+                        .corner_radius()
+                            .all(theme.radii.md)
+                            .end()
+                        .border()
+                            .left(4)
+                            .bottom(1)
+                            .color(theme.palette.border)
+                            .end(), |clay|
+                    {
+                        clay.text("Squirrels", body());
+                    });
+                });
+
+                clay.with(Declaration::new()
+                    .id(clay.id("main_content"))
+                    .layout()
+                        .width(Sizing::Grow(0., 0.))
+                        .height(Sizing::Grow(0., 0.))
+                        .direction(LayoutDirection::TopToBottom)
+                        .padding(Padding::all(theme.spacing.md))
+                        .end()
+                    .clip(false, true, (0., -10.).into())
+                    .background_color(theme.palette.surface), |clay|
+                {
+                    clay.text(
+                        "The Secret Life of Squirrels: Nature's Clever Acrobats. Squirrels are often overlooked creatures.",
+                        TextConfig::new().style(&theme.typography.heading).color(theme.palette.text).end(),
+                    );
+                });
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{layout::Sizing, Declaration};
+
+    #[rustfmt::skip]
+    fn square(clay: &mut ClayLayoutScope<'_, '_, (), ()>, size: f32) {
+        clay.with(Declaration::new()
+            .layout()
+                .width(Sizing::Fixed(size))
+                .height(Sizing::Fixed(size))
+                .end()
+            .background_color(Color::rgb(255., 0., 0.)), |clay|
+        {
+            clay.text("ab", TextConfig::new().font_size(10).end());
+        });
+    }
+
+    #[test]
+    fn serialize_and_compare() {
+        let mut harness = LayoutHarness::new();
+        let output = harness.run(Dimensions::new(100., 100.), |clay| square(clay, 50.));
+        let mut lines = output.lines();
+        assert!(lines.next().unwrap().starts_with("rectangle id="));
+        assert!(lines.next().unwrap().ends_with(
+            "bbox=(0, 0, 10, 10) color=(0, 0, 0, 0) font=0 size=10 spacing=0 line_height=0 \"ab\""
+        ));
+
+        let dir = std::env::temp_dir().join(format!("clay_snapshots_{}", std::process::id()));
+        write_snapshot(&dir.join("square.snap"), &output);
+        harness.snapshot_dir(&dir);
+        harness.assert_snapshot("square", Dimensions::new(100., 100.), |clay| {
+            square(clay, 50.)
+        });
+
+        let mismatch = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            harness.assert_snapshot("square", Dimensions::new(100., 100.), |clay| {
+                square(clay, 60.)
+            });
+        }));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(mismatch.is_err());
+    }
+
+    #[test]
+    fn example_layouts() {
+        let mut harness = LayoutHarness::new();
+        harness.assert_snapshot("basic_rectangle", Dimensions::new(800., 600.), |clay| {
+            examples::basic_rectangle(clay)
+        });
+        harness.assert_snapshot("dashboard", Dimensions::new(800., 600.), |clay| {
+            examples::dashboard(clay)
+        });
+    }
+}
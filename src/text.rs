@@ -1,7 +1,8 @@
-use crate::{bindings::*, color::Color};
+use crate::{bindings::*, color::Color, fonts::FontId, theme::TextStyle};
 
 #[derive(Debug, Clone, Copy)]
 #[repr(u8)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextElementConfigWrapMode {
     /// Wraps on whitespaces not breaking words
     Words = Clay_TextElementConfigWrapMode_CLAY_TEXT_WRAP_WORDS,
@@ -13,6 +14,7 @@ pub enum TextElementConfigWrapMode {
 
 #[derive(Debug, Clone, Copy)]
 #[repr(u8)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextAlignment {
     /// Aligns the text to the left.
     Left = Clay_TextAlignment_CLAY_TEXT_ALIGN_LEFT,
@@ -34,12 +36,14 @@ impl From<TextElementConfig> for *mut Clay_TextElementConfig {
 
 /// Configuration settings for rendering text elements.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextConfig {
     /// The color of the text.
     pub color: Color,
     /// Clay does not manage fonts. It is up to the user to assign a unique ID to each font
-    /// and provide it via the [`font_id`](Text::font_id) field.
-    pub font_id: u16,
+    /// and provide it via the [`font_id`](Text::font_id) field, see
+    /// [`FontRegistry`](crate::fonts::FontRegistry).
+    pub font_id: FontId,
     /// The font size of the text.
     pub font_size: u16,
     /// The spacing between letters.
@@ -65,10 +69,11 @@ impl TextConfig {
         self
     }
 
-    /// Sets the font ID. The user is responsible for assigning unique font IDs.
+    /// Sets the font ID, usually a [`FontId`] of the [`FontRegistry`](crate::fonts::FontRegistry)
+    /// given to the text measurer and the renderer.
     #[inline]
-    pub fn font_id(&mut self, id: u16) -> &mut Self {
-        self.font_id = id;
+    pub fn font_id(&mut self, id: impl Into<FontId>) -> &mut Self {
+        self.font_id = id.into();
         self
     }
 
@@ -93,6 +98,15 @@ impl TextConfig {
         self
     }
 
+    /// Sets the font ID, size and line height from a step of the theme typography ramp.
+    #[inline]
+    pub fn style(&mut self, style: &TextStyle) -> &mut Self {
+        self.font_id = style.font_id;
+        self.font_size = style.font_size;
+        self.line_height = style.line_height;
+        self
+    }
+
     /// Sets the text wrapping mode.
     #[inline]
     pub fn wrap_mode(&mut self, mode: TextElementConfigWrapMode) -> &mut Self {
@@ -119,7 +133,7 @@ impl Default for TextConfig {
     fn default() -> Self {
         Self {
             color: Color::rgba(0., 0., 0., 0.),
-            font_id: 0,
+            font_id: FontId(0),
             font_size: 0,
             letter_spacing: 0,
             line_height: 0,
@@ -134,7 +148,7 @@ impl From<TextConfig> for Clay_TextElementConfig {
         Self {
             userData: core::ptr::null_mut(),
             textColor: value.color.into(),
-            fontId: value.font_id,
+            fontId: value.font_id.0,
             fontSize: value.font_size,
             letterSpacing: value.letter_spacing,
             lineHeight: value.line_height,
@@ -148,7 +162,7 @@ impl From<Clay_TextElementConfig> for TextConfig {
     fn from(value: Clay_TextElementConfig) -> Self {
         Self {
             color: value.textColor.into(),
-            font_id: value.fontId,
+            font_id: FontId(value.fontId),
             font_size: value.fontSize,
             letter_spacing: value.letterSpacing,
             line_height: value.lineHeight,
@@ -0,0 +1,255 @@
+use crate::color::Color;
+use crate::fonts::FontId;
+
+/// Named color tokens used by layouts instead of literal colors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    /// The color behind every other element.
+    pub background: Color,
+    /// Panels and content containers placed on the background.
+    pub surface: Color,
+    /// Highlighted surfaces, such as the selected item of a list.
+    pub surface_variant: Color,
+    /// Interactive controls, such as buttons.
+    pub control: Color,
+    /// Floating elements drawn above the rest of the layout, such as menus.
+    pub overlay: Color,
+    /// The main accent color.
+    pub primary: Color,
+    /// The default text color.
+    pub text: Color,
+    /// Text that should stand out less than [`text`](Palette::text).
+    pub text_muted: Color,
+    /// The default border color.
+    pub border: Color,
+}
+
+/// Spacing scale used for paddings and child gaps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spacing {
+    pub xs: u16,
+    pub sm: u16,
+    pub md: u16,
+    pub lg: u16,
+    pub xl: u16,
+}
+
+/// Corner radius scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Radii {
+    pub sm: f32,
+    pub md: f32,
+    pub lg: f32,
+}
+
+/// Font settings of a single step of the typography ramp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextStyle {
+    /// Font ID given to [`TextConfig::font_id`](crate::text::TextConfig::font_id).
+    pub font_id: FontId,
+    /// The font size.
+    pub font_size: u16,
+    /// The line height, `0` lets the measure function decide.
+    pub line_height: u16,
+}
+
+impl TextStyle {
+    pub const fn new(font_id: FontId, font_size: u16, line_height: u16) -> Self {
+        Self {
+            font_id,
+            font_size,
+            line_height,
+        }
+    }
+}
+
+/// Typography ramp, from the smallest to the largest text style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Typography {
+    pub caption: TextStyle,
+    pub body: TextStyle,
+    pub title: TextStyle,
+    pub heading: TextStyle,
+}
+
+/// Whether a theme is meant for a light or a dark background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeMode {
+    Light,
+    Dark,
+}
+
+/// A set of design tokens shared by a layout.
+///
+/// The active theme is stored in [`Clay`](crate::Clay) and can be swapped at any time with
+/// [`Clay::set_theme`](crate::Clay::set_theme), the next layout will pick up the new tokens.
+/// ```
+/// use clay_layout::{theme::Theme, Clay};
+///
+/// let mut clay = Clay::new((800., 600.).into());
+/// clay.set_theme(Theme::dark());
+/// assert_eq!(clay.theme().palette.text, Theme::dark().palette.text);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub mode: ThemeMode,
+    pub palette: Palette,
+    pub spacing: Spacing,
+    pub radii: Radii,
+    pub typography: Typography,
+}
+
+impl Theme {
+    /// Default theme for light backgrounds.
+    pub const fn light() -> Self {
+        Self {
+            mode: ThemeMode::Light,
+            palette: Palette {
+                background: Color::rgb(244., 244., 246.),
+                surface: Color::rgb(255., 255., 255.),
+                surface_variant: Color::rgb(222., 222., 228.),
+                control: Color::rgb(200., 200., 208.),
+                overlay: Color::rgb(250., 250., 250.),
+                primary: Color::rgb(225., 138., 50.),
+                text: Color::rgb(30., 30., 36.),
+                text_muted: Color::rgb(110., 110., 120.),
+                border: Color::rgb(30., 30., 36.),
+            },
+            spacing: Self::SPACING,
+            radii: Self::RADII,
+            typography: Self::TYPOGRAPHY,
+        }
+    }
+
+    /// Default theme for dark backgrounds.
+    pub const fn dark() -> Self {
+        Self {
+            mode: ThemeMode::Dark,
+            palette: Palette {
+                background: Color::rgb(43., 41., 51.),
+                surface: Color::rgb(90., 90., 90.),
+                surface_variant: Color::rgb(120., 120., 120.),
+                control: Color::rgb(140., 140., 140.),
+                overlay: Color::rgb(40., 40., 40.),
+                primary: Color::rgb(225., 138., 50.),
+                text: Color::rgb(255., 255., 255.),
+                text_muted: Color::rgb(180., 180., 180.),
+                border: Color::rgb(255., 255., 255.),
+            },
+            spacing: Self::SPACING,
+            radii: Self::RADII,
+            typography: Self::TYPOGRAPHY,
+        }
+    }
+
+    /// Returns the default theme of the given mode.
+    pub const fn from_mode(mode: ThemeMode) -> Self {
+        match mode {
+            ThemeMode::Light => Self::light(),
+            ThemeMode::Dark => Self::dark(),
+        }
+    }
+
+    /// Returns the default theme of the opposite mode, used to switch between light and dark.
+    pub const fn toggled(&self) -> Self {
+        match self.mode {
+            ThemeMode::Light => Self::dark(),
+            ThemeMode::Dark => Self::light(),
+        }
+    }
+
+    const SPACING: Spacing = Spacing {
+        xs: 4,
+        sm: 8,
+        md: 16,
+        lg: 24,
+        xl: 32,
+    };
+
+    const RADII: Radii = Radii {
+        sm: 5.,
+        md: 8.,
+        lg: 16.,
+    };
+
+    const TYPOGRAPHY: Typography = Typography {
+        caption: TextStyle::new(FontId(0), 12, 0),
+        body: TextStyle::new(FontId(0), 16, 0),
+        title: TextStyle::new(FontId(0), 20, 0),
+        heading: TextStyle::new(FontId(0), 24, 0),
+    };
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        layout::{LayoutDirection, Padding},
+        render_commands::RenderCommandConfig,
+        testing::LayoutHarness,
+        text::TextConfig,
+        Clay, Declaration,
+    };
+
+    #[rustfmt::skip]
+    fn render_colors(clay: &mut Clay) -> Vec<Color> {
+        let mut clay = clay.begin::<(), ()>();
+        let theme = *clay.theme();
+
+        clay.with(Declaration::new()
+            .layout()
+                .direction(LayoutDirection::TopToBottom)
+                .padding(Padding::all(theme.spacing.md))
+                .end()
+            .background_color(theme.palette.background), |clay|
+        {
+            clay.with(Declaration::new()
+                .layout()
+                    .padding(Padding::all(theme.spacing.sm))
+                    .end()
+                .corner_radius().all(theme.radii.md).end()
+                .background_color(theme.palette.surface), |clay|
+            {
+                clay.text("themed", TextConfig::new()
+                    .style(&theme.typography.body)
+                    .color(theme.palette.text)
+                    .end());
+            });
+        });
+
+        clay.end()
+            .filter_map(|command| match command.config {
+                RenderCommandConfig::Rectangle(rectangle) => Some(rectangle.color),
+                RenderCommandConfig::Text(text) => Some(text.color),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn light_and_dark_colors() {
+        let mut harness = LayoutHarness::new();
+        let clay = harness.clay();
+
+        let light = render_colors(clay);
+        clay.set_theme(clay.theme().toggled());
+        let dark = render_colors(clay);
+
+        let palette = |theme: Theme| {
+            vec![
+                theme.palette.background,
+                theme.palette.surface,
+                theme.palette.text,
+            ]
+        };
+        assert_eq!(light, palette(Theme::light()));
+        assert_eq!(dark, palette(Theme::dark()));
+        assert!(light.iter().zip(&dark).all(|(light, dark)| light != dark));
+    }
+}
@@ -0,0 +1,392 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    bindings::*,
+    elements::{FloatingAttachPointType, FloatingAttachToElement},
+    layout::{Alignment, LayoutAlignmentX, LayoutAlignmentY, LayoutDirection, Padding, SizingType},
+    math::{BoundingBox, Dimensions, Vector2},
+};
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+struct InspectRoot {
+    layout_element_index: i32,
+    parent_id: u32,
+    z_index: i16,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct InspectElement {
+    id: u32,
+    label: Clay_String,
+    bounding_box: Clay_BoundingBox,
+    layout: Clay_LayoutConfig,
+    children: *mut i32,
+    child_count: u16,
+    is_text: bool,
+    text: Clay_String,
+    has_clip: bool,
+    clip: Clay_ClipElementConfig,
+    has_floating: bool,
+    floating: Clay_FloatingElementConfig,
+    culled: bool,
+}
+
+// Defined in clay_inspect.h
+extern "C" {
+    fn Clay__InspectElementCount() -> i32;
+    fn Clay__InspectRootCount() -> i32;
+    fn Clay__InspectGetRoot(index: i32, out: *mut InspectRoot) -> bool;
+    fn Clay__InspectGetElement(index: i32, out: *mut InspectElement) -> bool;
+}
+
+/// Sizing of an element along one axis, as it was declared.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeclaredSizing {
+    Fit {
+        min: f32,
+        max: f32,
+    },
+    Grow {
+        min: f32,
+        max: f32,
+    },
+    Fixed(f32),
+    Percent(f32),
+    /// The size depends on the other axis through a callback, which isn't exposed.
+    Constrained,
+}
+
+impl From<Clay_SizingAxis> for DeclaredSizing {
+    fn from(value: Clay_SizingAxis) -> Self {
+        let sizing_type = unsafe { core::mem::transmute::<u8, SizingType>(value.type_) };
+        unsafe {
+            match sizing_type {
+                SizingType::Fit => Self::Fit {
+                    min: value.size.minMax.min,
+                    max: value.size.minMax.max,
+                },
+                SizingType::Grow => Self::Grow {
+                    min: value.size.minMax.min,
+                    max: value.size.minMax.max,
+                },
+                SizingType::Fixed => Self::Fixed(value.size.minMax.min),
+                SizingType::Percent => Self::Percent(value.size.percent),
+                SizingType::Constrained => Self::Constrained,
+            }
+        }
+    }
+}
+
+/// Clip configuration of an element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClipInfo {
+    pub horizontal: bool,
+    pub vertical: bool,
+    pub child_offset: Vector2,
+}
+
+/// Floating configuration of an element.
+#[derive(Debug, Clone)]
+pub struct FloatingInfo {
+    pub offset: Vector2,
+    pub expand: Dimensions,
+    pub z_index: i16,
+    pub attach_to: FloatingAttachToElement,
+    /// Id of the element the floating element is attached to.
+    pub parent_id: u32,
+    /// Attach point on the floating element.
+    pub element_attach: FloatingAttachPointType,
+    /// Attach point on the parent.
+    pub parent_attach: FloatingAttachPointType,
+}
+
+/// An element of a [`LayoutTree`].
+#[derive(Debug, Clone)]
+pub struct LayoutElement {
+    /// Hashed id of the element, generated by clay for elements declared without an id.
+    pub id: u32,
+    /// The string the id was created from, if the element was declared with an id.
+    pub label: Option<String>,
+    /// Index of the parent element, `None` for the root container created by clay. Floating
+    /// elements use the element they are attached to as parent.
+    pub parent: Option<usize>,
+    /// Indices of the child elements, in declaration order followed by attached floating
+    /// elements.
+    pub children: Vec<usize>,
+    /// The resolved bounding box.
+    pub bounding_box: BoundingBox,
+    pub width: DeclaredSizing,
+    pub height: DeclaredSizing,
+    pub padding: Padding,
+    pub child_gap: u16,
+    pub child_alignment: Alignment,
+    pub direction: LayoutDirection,
+    pub clip: Option<ClipInfo>,
+    pub floating: Option<FloatingInfo>,
+    /// The text content, for text elements.
+    pub text: Option<String>,
+    /// Whether the element was outside of the layout dimensions and produced no render commands.
+    pub culled: bool,
+}
+
+/// Snapshot of every element of a layout, returned by
+/// [`ClayLayoutScope::end_with_tree`](crate::ClayLayoutScope::end_with_tree).
+///
+/// Labels are copied from the strings passed to [`Clay::id`](crate::Clay::id), so they are only
+/// available for ids created during the same frame.
+#[derive(Debug, Clone, Default)]
+pub struct LayoutTree {
+    elements: Vec<LayoutElement>,
+    roots: Vec<usize>,
+}
+
+impl LayoutTree {
+    /// Copies the layout elements of the current context.
+    ///
+    /// # Safety
+    /// Must be called after `Clay_EndLayout`, with the context of the layout set as the current
+    /// context and all strings of the layout still alive.
+    pub(crate) unsafe fn snapshot() -> Self {
+        let count = Clay__InspectElementCount().max(0) as usize;
+        let mut elements = Vec::with_capacity(count);
+
+        for index in 0..count {
+            let mut raw = core::mem::MaybeUninit::<InspectElement>::uninit();
+            if !Clay__InspectGetElement(index as i32, raw.as_mut_ptr()) {
+                break;
+            }
+            let raw = raw.assume_init();
+            elements.push(Self::convert(&raw));
+        }
+
+        let mut tree = Self {
+            elements,
+            roots: Vec::new(),
+        };
+
+        // Link the children through the arrays, then the floating roots to their parents
+        for index in 0..tree.elements.len() {
+            let children = tree.elements[index].children.clone();
+            for child in children {
+                if let Some(child) = tree.elements.get_mut(child) {
+                    child.parent = Some(index);
+                }
+            }
+        }
+
+        for index in 0..Clay__InspectRootCount().max(0) {
+            let mut root = core::mem::MaybeUninit::<InspectRoot>::uninit();
+            if !Clay__InspectGetRoot(index, root.as_mut_ptr()) {
+                break;
+            }
+            let root = root.assume_init();
+            let element = root.layout_element_index as usize;
+            if element >= tree.elements.len() {
+                continue;
+            }
+
+            match tree.position(root.parent_id) {
+                Some(parent) if root.parent_id != 0 => {
+                    tree.elements[element].parent = Some(parent);
+                    tree.elements[parent].children.push(element);
+                }
+                _ => tree.roots.push(element),
+            }
+        }
+
+        tree
+    }
+
+    unsafe fn convert(raw: &InspectElement) -> LayoutElement {
+        let children = if raw.children.is_null() {
+            Vec::new()
+        } else {
+            core::slice::from_raw_parts(raw.children, raw.child_count as usize)
+                .iter()
+                .map(|&child| child as usize)
+                .collect()
+        };
+
+        let floating = &raw.floating;
+
+        LayoutElement {
+            id: raw.id,
+            label: string(raw.label),
+            parent: None,
+            children,
+            bounding_box: raw.bounding_box.into(),
+            width: raw.layout.sizing.width.into(),
+            height: raw.layout.sizing.height.into(),
+            padding: Padding::new(
+                raw.layout.padding.left,
+                raw.layout.padding.right,
+                raw.layout.padding.top,
+                raw.layout.padding.bottom,
+            ),
+            child_gap: raw.layout.childGap,
+            child_alignment: Alignment::new(
+                core::mem::transmute::<u8, LayoutAlignmentX>(raw.layout.childAlignment.x),
+                core::mem::transmute::<u8, LayoutAlignmentY>(raw.layout.childAlignment.y),
+            ),
+            direction: core::mem::transmute::<u8, LayoutDirection>(raw.layout.layoutDirection),
+            clip: raw.has_clip.then(|| ClipInfo {
+                horizontal: raw.clip.horizontal,
+                vertical: raw.clip.vertical,
+                child_offset: raw.clip.childOffset.into(),
+            }),
+            floating: raw.has_floating.then(|| FloatingInfo {
+                offset: floating.offset.into(),
+                expand: floating.expand.into(),
+                z_index: floating.zIndex,
+                attach_to: core::mem::transmute::<u8, FloatingAttachToElement>(floating.attachTo),
+                parent_id: floating.parentId,
+                element_attach: core::mem::transmute::<u8, FloatingAttachPointType>(
+                    floating.attachPoints.element,
+                ),
+                parent_attach: core::mem::transmute::<u8, FloatingAttachPointType>(
+                    floating.attachPoints.parent,
+                ),
+            }),
+            text: if raw.is_text { string(raw.text) } else { None },
+            culled: raw.culled,
+        }
+    }
+
+    fn position(&self, id: u32) -> Option<usize> {
+        self.elements.iter().position(|element| element.id == id)
+    }
+
+    /// All elements, in declaration order.
+    pub fn elements(&self) -> &[LayoutElement] {
+        &self.elements
+    }
+
+    /// Indices of the elements without a parent. This is the root container created by clay,
+    /// followed by floating elements whose parent could not be found.
+    pub fn roots(&self) -> &[usize] {
+        &self.roots
+    }
+
+    /// Returns the element at `index`.
+    pub fn get(&self, index: usize) -> Option<&LayoutElement> {
+        self.elements.get(index)
+    }
+
+    /// Returns the element declared with `id`.
+    pub fn find(&self, id: crate::id::Id) -> Option<&LayoutElement> {
+        self.position(id.id.id).map(|index| &self.elements[index])
+    }
+
+    /// Returns the children of the element at `index`.
+    pub fn children(&self, index: usize) -> impl Iterator<Item = &LayoutElement> {
+        self.elements
+            .get(index)
+            .into_iter()
+            .flat_map(move |element| {
+                element
+                    .children
+                    .iter()
+                    .map(move |&child| &self.elements[child])
+            })
+    }
+
+    /// Returns the parent of the element at `index`.
+    pub fn parent(&self, index: usize) -> Option<&LayoutElement> {
+        self.elements
+            .get(index)?
+            .parent
+            .map(|parent| &self.elements[parent])
+    }
+}
+
+unsafe fn string(value: Clay_String) -> Option<String> {
+    if value.chars.is_null() || value.length <= 0 {
+        return None;
+    }
+    let bytes = core::slice::from_raw_parts(value.chars as *const u8, value.length as usize);
+    Some(String::from_utf8_lossy(bytes).into_owned())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        elements::FloatingAttachToElement,
+        layout::{LayoutDirection, Padding, Sizing},
+        testing::LayoutHarness,
+        text::TextConfig,
+        tree::DeclaredSizing,
+        Declaration,
+    };
+
+    #[rustfmt::skip]
+    #[test]
+    fn tree_snapshot() {
+        let mut harness = LayoutHarness::new();
+        let mut clay = harness.clay().begin::<(), ()>();
+
+        clay.with(Declaration::new()
+            .id(clay.id("parent"))
+            .layout()
+                .width(Sizing::Fixed(200.))
+                .height(Sizing::Grow(0., 0.))
+                .padding(Padding::all(10))
+                .direction(LayoutDirection::TopToBottom)
+                .end()
+            .clip(false, true, (0., 0.).into()), |clay|
+        {
+            clay.text("label", TextConfig::new().font_size(10).end());
+            clay.with(Declaration::new()
+                .id(clay.id("menu"))
+                .layout()
+                    .width(Sizing::Fixed(50.))
+                    .end()
+                .floating()
+                    .attach_to(FloatingAttachToElement::Parent)
+                    .end(), |_| {});
+            clay.with(Declaration::new()
+                .id(clay.id("hidden"))
+                .layout()
+                    .width(Sizing::Fixed(10.))
+                    .height(Sizing::Fixed(10.))
+                    .end()
+                .floating()
+                    .offset((-1000., -1000.).into())
+                    .attach_to(FloatingAttachToElement::Root)
+                    .end(), |_| {});
+        });
+
+        let parent_id = clay.id("parent");
+        let menu_id = clay.id("menu");
+        let hidden_id = clay.id("hidden");
+        let (tree, commands) = clay.end_with_tree();
+        assert!(commands.count() > 0);
+
+        let parent = tree.find(parent_id).unwrap();
+        assert_eq!(parent.label.as_deref(), Some("parent"));
+        assert_eq!(parent.width, DeclaredSizing::Fixed(200.));
+        assert_eq!(parent.height, DeclaredSizing::Grow { min: 0., max: f32::MAX });
+        assert_eq!(parent.bounding_box.width, 200.);
+        assert_eq!(parent.bounding_box.height, 600.);
+        assert_eq!(parent.padding, Padding::all(10));
+        assert!(parent.clip.unwrap().vertical);
+
+        assert_eq!(tree.roots().len(), 1);
+        let root = tree.roots()[0];
+        let parent_index = tree.get(root).unwrap().children[0];
+        assert_eq!(tree.get(parent_index).unwrap().id, parent_id.id.id);
+
+        let children: Vec<_> = tree.children(parent_index).collect();
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].text.as_deref(), Some("label"));
+        assert_eq!(children[1].id, menu_id.id.id);
+        assert!(children[1].floating.is_some());
+        assert_eq!(tree.parent(parent.children[1]).unwrap().id, parent_id.id.id);
+
+        let hidden = tree.find(hidden_id).unwrap();
+        assert_eq!(hidden.parent, Some(root));
+        assert!(hidden.culled);
+        assert!(!parent.culled);
+    }
+}